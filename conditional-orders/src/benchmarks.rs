@@ -0,0 +1,146 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use hydradx_traits::router::PoolType;
+use orml_traits::MultiCurrencyExtended;
+use sp_std::vec::Vec;
+pub const ONE: Balance = 1_000_000_000_000;
+
+const ASSET_IN: u32 = 0;
+
+benchmarks! {
+    where_clause { where
+        T::AssetId: From<u32>,
+        T::Currency: MultiCurrencyExtended<T::AccountId, Amount=i128>,
+        T: crate::pallet::Config,
+    }
+  place_order {
+        let owner: T::AccountId = create_account_with_balance::<T>("owner", 1)?;
+        let route = route_of::<T>(T::MaxRouteLength::get());
+        let asset_out = route.last().map(|trade| trade.asset_out).unwrap();
+  }:  _(RawOrigin::Signed(owner.clone()), ASSET_IN.into(), asset_out, 100 * ONE, 0, trigger(), route)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, ASSET_IN.into(), &owner), 100 * ONE);
+    }
+
+    cancel_order {
+        let owner: T::AccountId = create_account_with_balance::<T>("owner", 1)?;
+        let route = route_of::<T>(T::MaxRouteLength::get());
+        create_order::<T>(&owner, route)?;
+  }:  _(RawOrigin::Signed(owner.clone()), 0u32)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, ASSET_IN.into(), &owner), 0);
+        assert!(<Orders<T>>::get(0u32).is_none());
+    }
+
+    check_trigger {
+        let owner: T::AccountId = create_account_with_balance::<T>("owner", 1)?;
+        let route = route_of::<T>(T::MaxRouteLength::get());
+        create_order::<T>(&owner, route)?;
+        // the oracle lookup is charged separately by `Oracle::get_price_weight`
+  }: {
+        let order = <Orders<T>>::get(0u32).ok_or("order not found")?;
+        Pallet::<T>::is_triggered(&order);
+    }
+    verify {
+        assert!(<Orders<T>>::get(0u32).is_some());
+    }
+
+    execute_order {
+        let n in 1 .. T::MaxRouteLength::get();
+
+        let owner: T::AccountId = create_account_with_balance::<T>("owner", 1)?;
+        let route: RouteOf<T> = T::BenchmarkHelper::create_route(ASSET_IN.into(), n)?
+            .try_into()
+            .map_err(|_| "route is too long")?;
+        let asset_out = route.last().map(|trade| trade.asset_out).unwrap();
+        create_order::<T>(&owner, route)?;
+        let order = <Orders<T>>::get(0u32).ok_or("order not found")?;
+  }: {
+        Pallet::<T>::execute_order(0u32, order);
+    }
+    verify {
+        assert!(<Orders<T>>::get(0u32).is_none());
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, ASSET_IN.into(), &owner), 0);
+        assert!(T::Currency::free_balance(asset_out, &owner) > 0);
+    }
+}
+
+fn trigger() -> Trigger {
+    Trigger {
+        kind: TriggerKind::StopLoss,
+        price: EmaPrice::new(1, 1),
+        source: *b"omnipool",
+        period: OraclePeriod::Short,
+    }
+}
+
+/// Route of `n` connected trades starting with `ASSET_IN`. Used when the trades are not executed.
+fn route_of<T: Config>(n: u32) -> RouteOf<T>
+where
+    T::AssetId: From<u32>,
+{
+    (0..n)
+        .map(|i| Trade {
+            pool: PoolType::XYK,
+            asset_in: (ASSET_IN + i).into(),
+            asset_out: (ASSET_IN + i + 1).into(),
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+fn create_order<T: Config>(owner: &T::AccountId, route: RouteOf<T>) -> DispatchResult
+where
+    T::AssetId: From<u32>,
+{
+    let asset_out = route
+        .last()
+        .map(|trade| trade.asset_out)
+        .ok_or(Error::<T>::RouteHasNoTrades)?;
+    crate::Pallet::<T>::place_order(
+        RawOrigin::Signed(owner.clone()).into(),
+        ASSET_IN.into(),
+        asset_out,
+        100 * ONE,
+        0,
+        trigger(),
+        route,
+    )
+}
+
+fn create_account_with_balance<T: Config>(name: &'static str, index: u32) -> Result<T::AccountId, DispatchError>
+where
+    T::AssetId: From<u32>,
+    T::Currency: MultiCurrencyExtended<T::AccountId, Amount = i128>,
+{
+    let account_id: T::AccountId = account(name, index, index);
+
+    T::Currency::update_balance(ASSET_IN.into(), &account_id, 1_000 * ONE as i128)?;
+
+    Ok(account_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pallet;
+    use crate::tests::mock::*;
+    use frame_benchmarking::impl_benchmark_test_suite;
+
+    impl_benchmark_test_suite!(Pallet, super::ExtBuilder::default().build(), super::Test);
+}
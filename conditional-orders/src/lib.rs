@@ -0,0 +1,524 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// # Conditional orders pallet
+// ## General description
+// This pallet provides stop-loss and take-profit orders. An order sells `amount_in` of `asset_in` for `asset_out`
+// along a route, but stays dormant until the oracle price of `asset_in` denominated in `asset_out` crosses the
+// trigger price of the order - a stop-loss order is triggered when the price drops to or below the trigger price,
+// a take-profit order when the price rises to or above it.
+//
+// ## Notes
+// The `amount_in` of an order is held in a named reserve until the order is executed or cancelled.
+//
+// The triggers are checked in `on_idle`, as long as there is weight left in the block and at most
+// `MaxChecksPerBlock` orders per block. The orders are checked in a round robin fashion, the last checked order
+// is stored so that the next block continues from there. Every checked order is charged the weight of the trigger
+// check and of the oracle lookup, an executed order additionally the weight of its execution along a route of its length.
+//
+// A triggered order is executed as a sell of the order owner along the route of the order. The trades are executed
+// directly with the `TradeExecution` of the AMM pools, not by the route executor, so the orders are exempt from the
+// router fee. The execution is reverted if the owner receives less than `min_amount_out`. Whatever the outcome, the order is removed, the funds
+// which have not been sold are released and the result is reported with an `Executed` or `ExecutionFailed` event.
+//
+// ## Dispatachable functions
+// * `place_order` - create a new conditional order.
+// * `cancel_order` - cancel an open conditional order.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::MaxEncodedLen;
+use frame_support::{pallet_prelude::*, storage::with_transaction};
+use frame_system::{
+    ensure_signed,
+    pallet_prelude::{BlockNumberFor, OriginFor},
+    RawOrigin,
+};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OraclePeriod, Source},
+    router::{ExecutorError, TradeExecution},
+};
+use orml_traits::{GetByKey, MultiCurrency, NamedMultiReservableCurrency};
+use pallet_route_executor::Trade;
+use sp_core::U256;
+use sp_runtime::{
+    traits::{One, Saturating, Zero},
+    DispatchError, TransactionOutcome,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarks;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+pub type Balance = u128;
+pub type OrderId = u32;
+pub type NamedReserveIdentifier = [u8; 8];
+
+pub const NAMED_RESERVE_ID: NamedReserveIdentifier = *b"condordr";
+
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+pub enum TriggerKind {
+    /// Triggered when the price drops to or below the trigger price
+    StopLoss,
+    /// Triggered when the price rises to or above the trigger price
+    TakeProfit,
+}
+
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+pub struct Trigger {
+    pub kind: TriggerKind,
+    /// Price of `asset_in` denominated in `asset_out`
+    pub price: EmaPrice,
+    /// Source of the oracle entry
+    pub source: Source,
+    /// Aggregation period of the oracle entry
+    pub period: OraclePeriod,
+}
+
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, TypeInfo)]
+pub struct Order<AccountId, AssetId, Route> {
+    pub owner: AccountId,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    pub amount_in: Balance,
+    /// Min amount of `asset_out` the owner has to receive when the order is executed
+    pub min_amount_out: Balance,
+    pub trigger: Trigger,
+    pub route: Route,
+}
+
+pub type RouteOf<T> = BoundedVec<Trade<<T as Config>::AssetId>, <T as Config>::MaxRouteLength>;
+
+pub type OrderOf<T> = Order<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, RouteOf<T>>;
+
+/// Creates the pools the benchmarked orders are executed against.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId> {
+    /// Creates and funds the pools of a route of `n` trades starting with `asset_in`. Returns the route.
+    fn create_route(asset_in: AssetId, n: u32) -> Result<sp_std::vec::Vec<Trade<AssetId>>, DispatchError>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use codec::HasCompact;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Identifier for the class of asset.
+        type AssetId: Member + Parameter + Copy + HasCompact + MaybeSerializeDeserialize + MaxEncodedLen;
+
+        /// Named reservable multi currency
+        type Currency: NamedMultiReservableCurrency<
+            Self::AccountId,
+            ReserveIdentifier = NamedReserveIdentifier,
+            CurrencyId = Self::AssetId,
+            Balance = Balance,
+        >;
+
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Handlers for AMM pools to calculate and execute the trades of a route
+        type AMM: TradeExecution<
+            <Self as frame_system::Config>::RuntimeOrigin,
+            Self::AccountId,
+            Self::AssetId,
+            Balance,
+            Error = DispatchError,
+        >;
+
+        /// Oracle providing the prices the orders are triggered by
+        type Oracle: AggregatedPriceOracle<Self::AssetId, Self::BlockNumber, EmaPrice>;
+
+        type ExistentialDeposits: GetByKey<Self::AssetId, Balance>;
+
+        #[pallet::constant]
+        type ExistentialDepositMultiplier: Get<u8>;
+
+        /// Max number of trades in the route of an order
+        #[pallet::constant]
+        type MaxRouteLength: Get<u32>;
+
+        /// Max number of orders whose trigger is checked in a block
+        #[pallet::constant]
+        type MaxChecksPerBlock: Get<u32>;
+
+        /// Weight information for the extrinsics.
+        type WeightInfo: WeightInfo;
+
+        /// Helper creating the pools of the routes executed in benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AssetId>;
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// An order has been placed
+        Placed {
+            order_id: OrderId,
+            owner: T::AccountId,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            min_amount_out: Balance,
+            trigger: Trigger,
+        },
+        /// An order has been cancelled
+        Cancelled { order_id: OrderId },
+        /// An order has been triggered and executed
+        Executed {
+            order_id: OrderId,
+            owner: T::AccountId,
+            amount_in: Balance,
+            amount_out: Balance,
+        },
+        /// An order has been triggered but its execution failed. The funds have been released to the owner.
+        ExecutionFailed {
+            order_id: OrderId,
+            owner: T::AccountId,
+            error: DispatchError,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Order cannot be found
+        OrderNotFound,
+        /// Size of order is lower than min amount
+        OrderAmountTooSmall,
+        /// Route has no trades
+        RouteHasNoTrades,
+        /// Route does not start with `asset_in` or does not end with `asset_out`, or its trades are not connected
+        InvalidRoute,
+        /// Assets of the order must differ
+        SameAssets,
+        /// Trigger price cannot be zero
+        InvalidTriggerPrice,
+        /// Free balance is too low to place the order
+        InsufficientBalance,
+        /// Reserved amount not sufficient.
+        InsufficientReservedAmount,
+        /// The AMM pool of a trade is not supported
+        PoolNotSupported,
+        /// Amount received from the execution is lower than `min_amount_out`
+        TradeLimitReached,
+        /// Error with math calculations
+        MathError,
+        /// The caller does not have permission to complete the action
+        Forbidden,
+        /// Reached the maximum value for the order id
+        OrderIdOutOfBound,
+    }
+
+    /// ID sequencer for Orders
+    #[pallet::storage]
+    #[pallet::getter(fn next_order_id)]
+    pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn orders)]
+    pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, OrderOf<T>, OptionQuery>;
+
+    /// The last order whose trigger has been checked. The next check continues after this order.
+    #[pallet::storage]
+    #[pallet::getter(fn trigger_cursor)]
+    pub type TriggerCursor<T: Config> = StorageValue<_, OrderId, OptionQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::check_triggers(remaining_weight)
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new conditional order
+        ///
+        /// Parameters:
+        /// - `asset_in`: Asset which is being sold
+        /// - `asset_out`: Asset which is being bought
+        /// - `amount_in`: Amount of `asset_in` to sell
+        /// - `min_amount_out`: Min amount of `asset_out` to receive when the order is executed
+        /// - `trigger`: Oracle price at which the order is executed
+        /// - `route`: Trades to execute, from `asset_in` to `asset_out`
+        ///
+        /// Validations:
+        /// - assets must differ
+        /// - route must start with `asset_in`, end with `asset_out` and its trades must be connected
+        /// - trigger price must not be zero
+        /// - amount_in must be higher than the existential deposit of asset_in multiplied by
+        ///   ExistentialDepositMultiplier
+        ///
+        /// Emits `Placed` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::place_order())]
+        pub fn place_order(
+            origin: OriginFor<T>,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            min_amount_out: Balance,
+            trigger: Trigger,
+            route: RouteOf<T>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            ensure!(asset_in != asset_out, Error::<T>::SameAssets);
+            Self::ensure_route_is_valid(asset_in, asset_out, &route)?;
+            let (price_n, price_d): (Balance, Balance) = trigger.price.into();
+            ensure!(
+                !price_n.is_zero() && !price_d.is_zero(),
+                Error::<T>::InvalidTriggerPrice
+            );
+            let min_amount =
+                T::ExistentialDeposits::get(&asset_in).saturating_mul(T::ExistentialDepositMultiplier::get().into());
+            ensure!(amount_in >= min_amount, Error::<T>::OrderAmountTooSmall);
+            ensure!(
+                T::Currency::free_balance(asset_in, &owner) >= amount_in,
+                Error::<T>::InsufficientBalance
+            );
+
+            <NextOrderId<T>>::try_mutate(|next_id| -> DispatchResult {
+                let order_id = *next_id;
+
+                T::Currency::reserve_named(&NAMED_RESERVE_ID, asset_in, &owner, amount_in)?;
+                <Orders<T>>::insert(
+                    order_id,
+                    Order {
+                        owner: owner.clone(),
+                        asset_in,
+                        asset_out,
+                        amount_in,
+                        min_amount_out,
+                        trigger,
+                        route,
+                    },
+                );
+
+                Self::deposit_event(Event::Placed {
+                    order_id,
+                    owner,
+                    asset_in,
+                    asset_out,
+                    amount_in,
+                    min_amount_out,
+                    trigger,
+                });
+
+                *next_id = next_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOutOfBound)?;
+                Ok(())
+            })
+        }
+
+        /// Cancel an open conditional order and release its reserved funds
+        ///
+        /// Parameters:
+        /// - `order_id`: ID of the order
+        ///
+        /// Validations:
+        /// - caller is order owner
+        ///
+        /// Emits `Cancelled` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+        pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+            ensure!(order.owner == who, Error::<T>::Forbidden);
+
+            let remaining_to_unreserve =
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_in, &order.owner, order.amount_in);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+            <Orders<T>>::remove(order_id);
+
+            Self::deposit_event(Event::Cancelled { order_id });
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn ensure_route_is_valid(asset_in: T::AssetId, asset_out: T::AssetId, route: &RouteOf<T>) -> DispatchResult {
+        let first_trade = route.first().ok_or(Error::<T>::RouteHasNoTrades)?;
+        let last_trade = route.last().ok_or(Error::<T>::RouteHasNoTrades)?;
+        ensure!(
+            first_trade.asset_in == asset_in && last_trade.asset_out == asset_out,
+            Error::<T>::InvalidRoute
+        );
+        ensure!(
+            route.windows(2).all(|trades| trades[0].asset_out == trades[1].asset_in),
+            Error::<T>::InvalidRoute
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if the oracle price of `asset_in` denominated in `asset_out` crossed the trigger price.
+    /// An order is never triggered if the oracle price is not available.
+    pub fn is_triggered(order: &OrderOf<T>) -> bool {
+        let trigger = order.trigger;
+        let price = match T::Oracle::get_price(order.asset_out, order.asset_in, trigger.period, trigger.source) {
+            Ok((price, _)) => price,
+            Err(_) => return false,
+        };
+
+        let (price_n, price_d): (Balance, Balance) = price.into();
+        let (trigger_n, trigger_d): (Balance, Balance) = trigger.price.into();
+        if price_d.is_zero() {
+            return false;
+        }
+
+        let price = U256::from(price_n).saturating_mul(U256::from(trigger_d));
+        let trigger_price = U256::from(trigger_n).saturating_mul(U256::from(price_d));
+        match trigger.kind {
+            TriggerKind::StopLoss => price <= trigger_price,
+            TriggerKind::TakeProfit => price >= trigger_price,
+        }
+    }
+
+    /// Checks the triggers of the orders following the `TriggerCursor` and executes the triggered ones,
+    /// as long as there is enough weight left. Returns the used weight.
+    fn check_triggers(remaining_weight: Weight) -> Weight {
+        // reading and writing the cursor
+        let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+        if !remaining_weight.all_gte(used_weight) {
+            return Weight::zero();
+        }
+
+        let check_weight = T::WeightInfo::check_trigger().saturating_add(T::Oracle::get_price_weight());
+        // an order is checked only if it can also be executed in case it is triggered
+        let max_order_weight = check_weight.saturating_add(T::WeightInfo::execute_order(T::MaxRouteLength::get()));
+
+        let orders = match <TriggerCursor<T>>::get() {
+            Some(cursor) => <Orders<T>>::iter_from(<Orders<T>>::hashed_key_for(cursor)),
+            None => <Orders<T>>::iter(),
+        };
+
+        let mut checked: u32 = 0;
+        let mut cursor = None;
+        let mut finished = true;
+        for (order_id, order) in orders {
+            if checked >= T::MaxChecksPerBlock::get()
+                || !remaining_weight.all_gte(used_weight.saturating_add(max_order_weight))
+            {
+                finished = false;
+                break;
+            }
+            used_weight.saturating_accrue(check_weight);
+            checked.saturating_inc();
+            cursor = Some(order_id);
+
+            if Self::is_triggered(&order) {
+                used_weight.saturating_accrue(T::WeightInfo::execute_order(order.route.len() as u32));
+                Self::execute_order(order_id, order);
+            }
+        }
+
+        if finished {
+            // all orders have been checked, the next check starts from the beginning
+            <TriggerCursor<T>>::kill();
+        } else if cursor.is_some() {
+            <TriggerCursor<T>>::set(cursor);
+        }
+
+        used_weight
+    }
+
+    /// Executes a triggered order and removes it. The funds of a failed execution are released to the owner.
+    fn execute_order(order_id: OrderId, order: OrderOf<T>) {
+        let result = with_transaction(|| match Self::do_execute_order(&order) {
+            Ok(amount_out) => TransactionOutcome::Commit(Ok(amount_out)),
+            Err(e) => TransactionOutcome::Rollback(Err(e)),
+        });
+
+        <Orders<T>>::remove(order_id);
+
+        match result {
+            Ok(amount_out) => Self::deposit_event(Event::Executed {
+                order_id,
+                owner: order.owner,
+                amount_in: order.amount_in,
+                amount_out,
+            }),
+            Err(error) => {
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_in, &order.owner, order.amount_in);
+                Self::deposit_event(Event::ExecutionFailed {
+                    order_id,
+                    owner: order.owner,
+                    error,
+                });
+            }
+        }
+    }
+
+    /// Sells the reserved `amount_in` along the route of the order. Returns the amount received by the owner.
+    /// The trades are executed with `T::AMM` directly, no router fee is charged.
+    fn do_execute_order(order: &OrderOf<T>) -> Result<Balance, DispatchError> {
+        let remaining_to_unreserve =
+            T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_in, &order.owner, order.amount_in);
+        ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+
+        let balance_before = T::Currency::free_balance(order.asset_out, &order.owner);
+        let origin: T::RuntimeOrigin = RawOrigin::Signed(order.owner.clone()).into();
+
+        let mut amount_in = order.amount_in;
+        for trade in order.route.iter() {
+            let amount_out = T::AMM::calculate_sell(trade.pool, trade.asset_in, trade.asset_out, amount_in)
+                .map_err(Self::executor_error)?;
+            T::AMM::execute_sell(
+                origin.clone(),
+                trade.pool,
+                trade.asset_in,
+                trade.asset_out,
+                amount_in,
+                amount_out,
+            )
+            .map_err(Self::executor_error)?;
+            amount_in = amount_out;
+        }
+
+        let amount_out = T::Currency::free_balance(order.asset_out, &order.owner)
+            .checked_sub(balance_before)
+            .ok_or(Error::<T>::MathError)?;
+        ensure!(amount_out >= order.min_amount_out, Error::<T>::TradeLimitReached);
+
+        Ok(amount_out)
+    }
+
+    fn executor_error(error: ExecutorError<DispatchError>) -> DispatchError {
+        match error {
+            ExecutorError::NotSupported => Error::<T>::PoolNotSupported.into(),
+            ExecutorError::Error(e) => e,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as conditional_orders;
+use crate::tests::mock::*;
+use crate::{Error, Event, TriggerKind};
+use frame_support::{assert_noop, assert_ok};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::router::PoolType;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+fn place_order() {
+    assert_ok!(ConditionalOrders::place_order(
+        RuntimeOrigin::signed(ALICE),
+        HDX,
+        DAI,
+        100 * ONE,
+        150 * ONE,
+        trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+        route(vec![(PoolType::XYK, HDX, DAI)])
+    ));
+}
+
+#[test]
+fn cancel_order_should_release_reserved_amount() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order();
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        assert_ok!(ConditionalOrders::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_none());
+        assert_eq!(
+            Tokens::reserved_balance_named(&conditional_orders::NAMED_RESERVE_ID, HDX, &ALICE),
+            0
+        );
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before + 100 * ONE);
+
+        expect_events(vec![Event::Cancelled { order_id: 0 }.into()]);
+    });
+}
+
+#[test]
+fn cancel_order_should_throw_error_when_order_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::cancel_order(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::OrderNotFound
+        );
+    });
+}
+
+#[test]
+fn cancel_order_should_throw_error_when_called_by_non_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order();
+
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::cancel_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::Forbidden
+        );
+    });
+}
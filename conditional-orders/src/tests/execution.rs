@@ -0,0 +1,282 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as conditional_orders;
+use crate::tests::mock::*;
+use crate::weights::WeightInfo;
+use crate::{Error, Event, TriggerCursor, TriggerKind};
+use frame_support::{assert_ok, traits::Hooks, weights::Weight};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::router::PoolType;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+fn place_order(
+    kind: TriggerKind,
+    price: EmaPrice,
+    min_amount_out: Balance,
+    trades: Vec<(PoolType<AssetId>, AssetId, AssetId)>,
+) {
+    assert_ok!(ConditionalOrders::place_order(
+        RuntimeOrigin::signed(ALICE),
+        HDX,
+        *trades.last().map(|(_, _, asset_out)| asset_out).unwrap(),
+        100 * ONE,
+        min_amount_out,
+        trigger(kind, price),
+        route(trades)
+    ));
+}
+
+fn place_stop_loss_order(min_amount_out: Balance) {
+    place_order(
+        TriggerKind::StopLoss,
+        EmaPrice::new(1, 2),
+        min_amount_out,
+        vec![(PoolType::XYK, HDX, DAI)],
+    );
+}
+
+fn run_on_idle() -> Weight {
+    ConditionalOrders::on_idle(System::block_number(), Weight::MAX)
+}
+
+#[test]
+fn stop_loss_order_should_not_be_executed_when_price_is_above_trigger_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_stop_loss_order(150 * ONE);
+
+        // Act
+        run_on_idle();
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_some());
+        assert_eq!(
+            Tokens::reserved_balance_named(&conditional_orders::NAMED_RESERVE_ID, HDX, &ALICE),
+            100 * ONE
+        );
+    });
+}
+
+#[test]
+fn stop_loss_order_should_be_executed_when_price_drops_to_trigger_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_stop_loss_order(150 * ONE);
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        run_on_idle();
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_none());
+        assert_eq!(
+            Tokens::reserved_balance_named(&conditional_orders::NAMED_RESERVE_ID, HDX, &ALICE),
+            0
+        );
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 200 * ONE);
+
+        expect_events(vec![Event::Executed {
+            order_id: 0,
+            owner: ALICE,
+            amount_in: 100 * ONE,
+            amount_out: 200 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn take_profit_order_should_be_executed_only_when_price_rises_to_trigger_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(
+            TriggerKind::TakeProfit,
+            EmaPrice::new(2, 1),
+            150 * ONE,
+            vec![(PoolType::XYK, HDX, DAI)],
+        );
+
+        // Act & Assert
+        run_on_idle();
+        assert!(ConditionalOrders::orders(0).is_some());
+
+        set_oracle_price(Some(EmaPrice::new(3, 1)));
+        run_on_idle();
+        assert!(ConditionalOrders::orders(0).is_none());
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 200 * ONE);
+    });
+}
+
+#[test]
+fn order_should_not_be_executed_when_oracle_price_is_not_available() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_stop_loss_order(150 * ONE);
+        set_oracle_price(None);
+
+        // Act
+        run_on_idle();
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_some());
+    });
+}
+
+#[test]
+fn failed_execution_should_release_funds_when_min_amount_out_is_not_reached() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_stop_loss_order(250 * ONE);
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        run_on_idle();
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_none());
+        assert_eq!(
+            Tokens::reserved_balance_named(&conditional_orders::NAMED_RESERVE_ID, HDX, &ALICE),
+            0
+        );
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before + 100 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+
+        expect_events(vec![Event::ExecutionFailed {
+            order_id: 0,
+            owner: ALICE,
+            error: Error::<Test>::TradeLimitReached.into(),
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn failed_execution_should_revert_executed_trades_of_route() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        // the pool has no DOT, so the second trade fails
+        place_order(
+            TriggerKind::StopLoss,
+            EmaPrice::new(1, 2),
+            0,
+            vec![(PoolType::XYK, HDX, DAI), (PoolType::XYK, DAI, DOT)],
+        );
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        run_on_idle();
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_none());
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before + 100 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(DAI, &POOL), 10_000 * ONE);
+
+        expect_events(vec![Event::ExecutionFailed {
+            order_id: 0,
+            owner: ALICE,
+            error: orml_tokens::Error::<Test>::BalanceTooLow.into(),
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn failed_execution_should_report_unsupported_pool() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(
+            TriggerKind::StopLoss,
+            EmaPrice::new(1, 2),
+            0,
+            vec![(PoolType::LBP, HDX, DAI)],
+        );
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+
+        // Act
+        run_on_idle();
+
+        // Assert
+        expect_events(vec![Event::ExecutionFailed {
+            order_id: 0,
+            owner: ALICE,
+            error: Error::<Test>::PoolNotSupported.into(),
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn on_idle_should_check_at_most_max_checks_per_block_and_continue_in_next_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        for _ in 0..3 {
+            place_stop_loss_order(150 * ONE);
+        }
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+
+        // Act & Assert
+        run_on_idle();
+        assert_eq!(conditional_orders::Orders::<Test>::iter().count(), 1);
+        assert!(TriggerCursor::<Test>::get().is_some());
+
+        run_on_idle();
+        assert_eq!(conditional_orders::Orders::<Test>::iter().count(), 0);
+        assert!(TriggerCursor::<Test>::get().is_none());
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 600 * ONE);
+    });
+}
+
+#[test]
+fn on_idle_should_not_check_orders_when_weight_is_insufficient() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_stop_loss_order(150 * ONE);
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+
+        // Act
+        let used_weight = ConditionalOrders::on_idle(System::block_number(), Weight::zero());
+
+        // Assert
+        assert_eq!(used_weight, Weight::zero());
+        assert!(ConditionalOrders::orders(0).is_some());
+    });
+}
+
+#[test]
+fn on_idle_should_charge_execution_weight_of_route_length() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_stop_loss_order(150 * ONE);
+        set_oracle_price(Some(EmaPrice::new(1, 2)));
+
+        // Act
+        let used_weight = run_on_idle();
+
+        // Assert
+        assert!(ConditionalOrders::orders(0).is_none());
+        assert_eq!(
+            used_weight,
+            <Test as conditional_orders::Config>::WeightInfo::check_trigger()
+                .saturating_add(<Test as conditional_orders::Config>::WeightInfo::execute_order(1))
+        );
+    });
+}
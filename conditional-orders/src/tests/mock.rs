@@ -0,0 +1,336 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as conditional_orders;
+use crate::{Config, RouteOf, Trigger, TriggerKind};
+use frame_support::{
+    parameter_types,
+    traits::{ConstU32, Everything, GenesisBuild, Nothing},
+    weights::Weight,
+};
+use frame_system as system;
+use frame_system::ensure_signed;
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OraclePeriod, Source},
+    router::{ExecutorError, PoolType, TradeExecution},
+};
+use orml_tokens::AccountData;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+use pallet_route_executor::Trade;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Amount = i128;
+pub type AssetId = u32;
+pub type Balance = u128;
+pub type NamedReserveIdentifier = [u8; 8];
+
+pub const HDX: AssetId = 0;
+pub const DAI: AssetId = 2;
+pub const DOT: AssetId = 3;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const POOL: AccountId = 100;
+
+/// Every trade of the mocked AMM returns twice the sold amount
+pub const SELL_RATE: Balance = 2;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+     Block = Block,
+     NodeBlock = Block,
+     UncheckedExtrinsic = UncheckedExtrinsic,
+     {
+         System: frame_system,
+         ConditionalOrders: conditional_orders,
+         Tokens: orml_tokens,
+     }
+);
+
+thread_local! {
+    pub static ORACLE_PRICE: RefCell<Option<EmaPrice>> = RefCell::new(None);
+}
+
+parameter_types! {
+    pub ExistentialDepositMultiplier: u8 = 5;
+}
+
+parameter_type_with_key! {
+    pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+        1_000
+    };
+}
+
+pub struct PriceOracle;
+
+impl AggregatedPriceOracle<AssetId, u64, EmaPrice> for PriceOracle {
+    type Error = ();
+
+    fn get_price(
+        _asset_a: AssetId,
+        _asset_b: AssetId,
+        _period: OraclePeriod,
+        _source: Source,
+    ) -> Result<(EmaPrice, u64), Self::Error> {
+        ORACLE_PRICE.with(|v| *v.borrow()).map(|price| (price, 1)).ok_or(())
+    }
+
+    fn get_price_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+pub struct MockedAmm;
+
+impl TradeExecution<RuntimeOrigin, AccountId, AssetId, Balance> for MockedAmm {
+    type Error = DispatchError;
+
+    fn calculate_sell(
+        pool_type: PoolType<AssetId>,
+        _asset_in: AssetId,
+        _asset_out: AssetId,
+        amount_in: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        if !matches!(pool_type, PoolType::XYK) {
+            return Err(ExecutorError::NotSupported);
+        }
+
+        Ok(amount_in * SELL_RATE)
+    }
+
+    fn calculate_buy(
+        _pool_type: PoolType<AssetId>,
+        _asset_in: AssetId,
+        _asset_out: AssetId,
+        _amount_out: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        Err(ExecutorError::NotSupported)
+    }
+
+    fn execute_sell(
+        who: RuntimeOrigin,
+        pool_type: PoolType<AssetId>,
+        asset_in: AssetId,
+        asset_out: AssetId,
+        amount_in: Balance,
+        min_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        if !matches!(pool_type, PoolType::XYK) {
+            return Err(ExecutorError::NotSupported);
+        }
+
+        let who = ensure_signed(who).map_err(|e| ExecutorError::Error(e.into()))?;
+        let amount_out = amount_in * SELL_RATE;
+        if amount_out < min_limit {
+            return Err(ExecutorError::Error(DispatchError::Other("Trade limit reached")));
+        }
+
+        <Tokens as MultiCurrency<AccountId>>::transfer(asset_in, &who, &POOL, amount_in)
+            .map_err(ExecutorError::Error)?;
+        <Tokens as MultiCurrency<AccountId>>::transfer(asset_out, &POOL, &who, amount_out)
+            .map_err(ExecutorError::Error)?;
+
+        Ok(())
+    }
+
+    fn execute_buy(
+        _who: RuntimeOrigin,
+        _pool_type: PoolType<AssetId>,
+        _asset_in: AssetId,
+        _asset_out: AssetId,
+        _amount_out: Balance,
+        _max_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        Err(ExecutorError::NotSupported)
+    }
+}
+
+impl Config for Test {
+    type AssetId = AssetId;
+    type Currency = Tokens;
+    type RuntimeEvent = RuntimeEvent;
+    type AMM = MockedAmm;
+    type Oracle = PriceOracle;
+    type ExistentialDeposits = ExistentialDeposits;
+    type ExistentialDepositMultiplier = ExistentialDepositMultiplier;
+    type MaxRouteLength = ConstU32<3>;
+    type MaxChecksPerBlock = ConstU32<2>;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockedAmmBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockedAmmBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<AssetId> for MockedAmmBenchmarkHelper {
+    fn create_route(asset_in: AssetId, n: u32) -> Result<Vec<Trade<AssetId>>, DispatchError> {
+        let mut trades = Vec::new();
+        let mut asset = asset_in;
+        for i in 0..n {
+            let asset_out = 100 + i;
+            <Tokens as MultiCurrency<AccountId>>::deposit(asset_out, &POOL, 1_000_000 * ONE)?;
+            trades.push(Trade {
+                pool: PoolType::XYK,
+                asset_in: asset,
+                asset_out,
+            });
+            asset = asset_out;
+        }
+
+        Ok(trades)
+    }
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 63;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl orml_tokens::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type Amount = Amount;
+    type CurrencyId = AssetId;
+    type WeightInfo = ();
+    type ExistentialDeposits = ExistentialDeposits;
+    type MaxLocks = ();
+    type DustRemovalWhitelist = Nothing;
+    type ReserveIdentifier = NamedReserveIdentifier;
+    type MaxReserves = MaxReserves;
+    type CurrencyHooks = ();
+}
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        // If eg. tests running on one thread only, this thread local is shared.
+        // let's make sure that it is set to the default value for each test case
+        ORACLE_PRICE.with(|v| {
+            *v.borrow_mut() = Some(EmaPrice::new(1, 1));
+        });
+
+        Self {
+            endowed_accounts: vec![
+                (ALICE, HDX, 1_000 * ONE),
+                (BOB, HDX, 1_000 * ONE),
+                (POOL, HDX, 10_000 * ONE),
+                (POOL, DAI, 10_000 * ONE),
+            ],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn with_endowed_accounts(mut self, accounts: Vec<(AccountId, AssetId, Balance)>) -> Self {
+        self.endowed_accounts.extend(accounts);
+        self
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        orml_tokens::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut r: sp_io::TestExternalities = t.into();
+
+        r.execute_with(|| {
+            System::set_block_number(1);
+        });
+
+        r
+    }
+}
+
+pub fn set_oracle_price(price: Option<EmaPrice>) {
+    ORACLE_PRICE.with(|v| {
+        *v.borrow_mut() = price;
+    });
+}
+
+pub fn trigger(kind: TriggerKind, price: EmaPrice) -> Trigger {
+    Trigger {
+        kind,
+        price,
+        source: *b"omnipool",
+        period: OraclePeriod::Short,
+    }
+}
+
+pub fn route(trades: Vec<(PoolType<AssetId>, AssetId, AssetId)>) -> RouteOf<Test> {
+    trades
+        .into_iter()
+        .map(|(pool, asset_in, asset_out)| Trade {
+            pool,
+            asset_in,
+            asset_out,
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+pub fn expect_events(e: Vec<RuntimeEvent>) {
+    test_utils::expect_events::<RuntimeEvent, Test>(e);
+}
@@ -0,0 +1,203 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as conditional_orders;
+use crate::tests::mock::*;
+use crate::{Error, Event, TriggerKind};
+use frame_support::{assert_noop, assert_ok};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::router::PoolType;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn place_order_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let trigger = trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2));
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        assert_ok!(ConditionalOrders::place_order(
+            RuntimeOrigin::signed(ALICE),
+            HDX,
+            DAI,
+            100 * ONE,
+            150 * ONE,
+            trigger,
+            route(vec![(PoolType::XYK, HDX, DAI)])
+        ));
+
+        // Assert
+        let order = ConditionalOrders::orders(0).unwrap();
+        assert_eq!(order.owner, ALICE);
+        assert_eq!(order.asset_in, HDX);
+        assert_eq!(order.asset_out, DAI);
+        assert_eq!(order.amount_in, 100 * ONE);
+        assert_eq!(order.min_amount_out, 150 * ONE);
+        assert_eq!(order.trigger, trigger);
+        assert_eq!(ConditionalOrders::next_order_id(), 1);
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&conditional_orders::NAMED_RESERVE_ID, HDX, &ALICE),
+            100 * ONE
+        );
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before - 100 * ONE);
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            owner: ALICE,
+            asset_in: HDX,
+            asset_out: DAI,
+            amount_in: 100 * ONE,
+            min_amount_out: 150 * ONE,
+            trigger,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_assets_are_the_same() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                HDX,
+                100 * ONE,
+                150 * ONE,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+                route(vec![(PoolType::XYK, HDX, HDX)])
+            ),
+            Error::<Test>::SameAssets
+        );
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_route_is_empty() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                100 * ONE,
+                150 * ONE,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+                route(vec![])
+            ),
+            Error::<Test>::RouteHasNoTrades
+        );
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_route_does_not_match_assets() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                100 * ONE,
+                150 * ONE,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+                route(vec![(PoolType::XYK, HDX, DOT)])
+            ),
+            Error::<Test>::InvalidRoute
+        );
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_route_trades_are_not_connected() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                100 * ONE,
+                150 * ONE,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+                route(vec![(PoolType::XYK, HDX, DOT), (PoolType::XYK, HDX, DAI)])
+            ),
+            Error::<Test>::InvalidRoute
+        );
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_trigger_price_is_zero() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                100 * ONE,
+                150 * ONE,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(0, 2)),
+                route(vec![(PoolType::XYK, HDX, DAI)])
+            ),
+            Error::<Test>::InvalidTriggerPrice
+        );
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_amount_is_too_low() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                4_999,
+                0,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+                route(vec![(PoolType::XYK, HDX, DAI)])
+            ),
+            Error::<Test>::OrderAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_balance_is_insufficient() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            ConditionalOrders::place_order(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                1_001 * ONE,
+                0,
+                trigger(TriggerKind::StopLoss, EmaPrice::new(1, 2)),
+                route(vec![(PoolType::XYK, HDX, DAI)])
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
@@ -0,0 +1,155 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Hooks;
+use hydradx_traits::router::PoolType;
+use orml_traits::MultiCurrencyExtended;
+use sp_std::vec::Vec;
+pub const ONE: Balance = 1_000_000_000_000;
+
+const ASSET_IN: u32 = 0;
+
+benchmarks! {
+    where_clause { where
+        AssetIdOf<T>: From<u32>,
+        <T as Config>::Currency: MultiCurrencyExtended<T::AccountId, Amount=i128>,
+        T: crate::pallet::Config,
+    }
+  schedule {
+        let owner: T::AccountId = create_account_with_balance::<T>("owner", 1)?;
+        let schedule = schedule_of::<T>(&owner, route_of::<T>(T::MaxRouteLength::get()));
+        let start_block = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+        // the schedule is planned in the last block searched for a free slot
+        for i in 0..MAX_PLANNING_ATTEMPTS - 1 {
+            fill_block::<T>(start_block.saturating_add(i.into()));
+        }
+        let planned_block = start_block.saturating_add((MAX_PLANNING_ATTEMPTS - 1).into());
+  }:  _(RawOrigin::Signed(owner.clone()), schedule, Some(start_block))
+    verify {
+        assert!(<Schedules<T>>::get(0u32).is_some());
+        assert_eq!(<ScheduleIdsPerBlock<T>>::get(planned_block).to_vec(), sp_std::vec![0u32]);
+    }
+
+    terminate {
+        let owner: T::AccountId = create_account_with_balance::<T>("owner", 1)?;
+        let schedule = schedule_of::<T>(&owner, route_of::<T>(T::MaxRouteLength::get()));
+        let start_block = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+        crate::Pallet::<T>::schedule(RawOrigin::Signed(owner.clone()).into(), schedule, Some(start_block))?;
+        // the block of the schedule is full
+        <ScheduleIdsPerBlock<T>>::try_mutate(start_block, |schedule_ids| -> DispatchResult {
+            while schedule_ids.len() < T::MaxSchedulesPerBlock::get() as usize {
+                schedule_ids.try_push(u32::MAX).map_err(|_| "block is full")?;
+            }
+            Ok(())
+        })?;
+  }:  _(RawOrigin::Signed(owner.clone()), 0u32, Some(start_block))
+    verify {
+        assert!(<Schedules<T>>::get(0u32).is_none());
+        assert_eq!(<T as Config>::Currency::reserved_balance_named(&NAMED_RESERVE_ID, ASSET_IN.into(), &owner), 0);
+    }
+
+    on_initialize {
+        let n in 1 .. T::MaxSchedulesPerBlock::get();
+
+        let start_block = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+        let mut owners = Vec::new();
+        for i in 0..n {
+            let owner: T::AccountId = create_account_with_balance::<T>("owner", i)?;
+            // routes of a single trade - the route weights are charged per executed schedule on top of this benchmark
+            let route: RouteOf<T> = T::BenchmarkHelper::create_route(ASSET_IN.into(), 1)?
+                .try_into()
+                .map_err(|_| "route is too long")?;
+            let schedule = schedule_of::<T>(&owner, route);
+            crate::Pallet::<T>::schedule(RawOrigin::Signed(owner.clone()).into(), schedule, Some(start_block))?;
+            owners.push(owner);
+        }
+        <frame_system::Pallet<T>>::set_block_number(start_block);
+  }: {
+        crate::Pallet::<T>::on_initialize(start_block);
+    }
+    verify {
+        for (id, owner) in owners.iter().enumerate() {
+            assert_eq!(<Retries<T>>::get(id as u32), 0);
+            assert_eq!(<RemainingAmounts<T>>::get(id as u32), Some(900 * ONE));
+            assert_eq!(<T as Config>::Currency::reserved_balance_named(&NAMED_RESERVE_ID, ASSET_IN.into(), owner), 900 * ONE);
+        }
+    }
+}
+
+/// Route of `n` connected trades starting with `ASSET_IN`. Used when the trades are not executed.
+fn route_of<T: Config>(n: u32) -> RouteOf<T>
+where
+    AssetIdOf<T>: From<u32>,
+{
+    (0..n)
+        .map(|i| Trade {
+            pool: PoolType::XYK,
+            asset_in: (ASSET_IN + i).into(),
+            asset_out: (ASSET_IN + i + 1).into(),
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+/// Schedule selling `100 * ONE` of `ASSET_IN` along `route` every `MinPeriod` blocks, with a budget of `1_000 * ONE`.
+/// The slippage does not limit the trades, as the oracle price does not have to match the price of the pools.
+fn schedule_of<T: Config>(owner: &T::AccountId, route: RouteOf<T>) -> ScheduleOf<T>
+where
+    AssetIdOf<T>: From<u32>,
+{
+    let asset_out = route.last().map(|trade| trade.asset_out).unwrap();
+    Schedule {
+        owner: owner.clone(),
+        asset_in: ASSET_IN.into(),
+        asset_out,
+        amount_per_trade: 100 * ONE,
+        period: T::MinPeriod::get().max(One::one()),
+        total_amount: 1_000 * ONE,
+        slippage: Permill::from_percent(100),
+        route,
+    }
+}
+
+fn fill_block<T: Config>(block: T::BlockNumber) {
+    let schedule_ids: BoundedVec<ScheduleId, T::MaxSchedulesPerBlock> =
+        sp_std::vec![u32::MAX; T::MaxSchedulesPerBlock::get() as usize]
+            .try_into()
+            .unwrap();
+    <ScheduleIdsPerBlock<T>>::insert(block, schedule_ids);
+}
+
+fn create_account_with_balance<T: Config>(name: &'static str, index: u32) -> Result<T::AccountId, DispatchError>
+where
+    AssetIdOf<T>: From<u32>,
+    <T as Config>::Currency: MultiCurrencyExtended<T::AccountId, Amount = i128>,
+{
+    let account_id: T::AccountId = account(name, index, index);
+
+    <T as Config>::Currency::update_balance(ASSET_IN.into(), &account_id, 10_000 * ONE as i128)?;
+
+    Ok(account_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pallet;
+    use crate::tests::mock::*;
+    use frame_benchmarking::impl_benchmark_test_suite;
+
+    impl_benchmark_test_suite!(Pallet, super::ExtBuilder::default().build(), super::Test);
+}
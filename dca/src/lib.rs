@@ -0,0 +1,616 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// # DCA pallet
+// ## General description
+// This pallet provides dollar-cost averaging (DCA). A user can `schedule` the purchase of an asset in small
+// instalments - every `period` blocks, `amount_per_trade` of `asset_in` is sold for `asset_out` along a route,
+// until the `total_amount` budget is spent.
+//
+// ## Notes
+// The budget of a schedule is reserved when the schedule is created. The trades of the schedules planned for a
+// block are executed in `on_initialize`, at most `MaxSchedulesPerBlock` of them. Besides the benchmarked
+// `on_initialize` weight, the route executor `sell` and `router_fee` weights of the route and the oracle
+// `get_price_weight` are charged for every executed schedule.
+//
+// The trades are executed by the `sell` of the route executor, so the router fee is charged the same way as for
+// trades submitted by users. A trade is reverted if it receives less than the oracle price of `asset_in`
+// denominated in `asset_out`, reduced by the `slippage` of the schedule.
+//
+// An `ExecutionFee` share of every trade is paid to `ExecutionFeeReceiver`, which can be the treasury or the block author.
+//
+// A failed trade is retried after `RetryDelay` blocks, but not earlier than in the next block. The delay doubles
+// with each consecutive failure.
+// The schedule is terminated and its remaining budget released after more than `MaxRetries` consecutive failures.
+//
+// ## Dispatachable functions
+// * `schedule` - create a new DCA schedule.
+// * `terminate` - terminate a DCA schedule and release its remaining budget.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{pallet_prelude::*, storage::with_transaction};
+use frame_system::{
+    ensure_signed,
+    pallet_prelude::{BlockNumberFor, OriginFor},
+    RawOrigin,
+};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::oracle::{AggregatedPriceOracle, OraclePeriod, Source};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pallet_route_executor::weights::WeightInfo as RouterWeightInfo;
+use pallet_route_executor::Trade;
+use sp_core::U256;
+use sp_runtime::{
+    traits::{One, Saturating, Zero},
+    DispatchError, Permill, TransactionOutcome,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarks;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+pub type Balance = u128;
+pub type ScheduleId = u32;
+pub type NamedReserveIdentifier = [u8; 8];
+
+pub const NAMED_RESERVE_ID: NamedReserveIdentifier = *b"dcaorder";
+
+/// Max number of consecutive blocks searched for a free slot when a schedule is planned
+pub const MAX_PLANNING_ATTEMPTS: u32 = 10;
+
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, TypeInfo)]
+pub struct Schedule<AccountId, AssetId, BlockNumber, Route> {
+    pub owner: AccountId,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    /// Amount of `asset_in` spent by a single trade, including the execution fee
+    pub amount_per_trade: Balance,
+    /// Number of blocks between two trades
+    pub period: BlockNumber,
+    /// Budget of `asset_in` for all trades of the schedule
+    pub total_amount: Balance,
+    /// Max difference between the oracle price and the execution price of a trade
+    pub slippage: Permill,
+    pub route: Route,
+}
+
+pub type AssetIdOf<T> = <T as pallet_route_executor::Config>::AssetId;
+
+pub type RouteOf<T> = BoundedVec<Trade<AssetIdOf<T>>, <T as Config>::MaxRouteLength>;
+
+pub type ScheduleOf<T> = Schedule<
+    <T as frame_system::Config>::AccountId,
+    AssetIdOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+    RouteOf<T>,
+>;
+
+/// Creates the pools the benchmarked schedules are executed against.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId> {
+    /// Creates and funds the pools of a route of `n` trades starting with `asset_in` and makes the oracle price of
+    /// `asset_in` and the last asset of the route available. Returns the route.
+    fn create_route(asset_in: AssetId, n: u32) -> Result<sp_std::vec::Vec<Trade<AssetId>>, DispatchError>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_route_executor::Config<Balance = Balance> {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Named reservable multi currency
+        type Currency: NamedMultiReservableCurrency<
+            Self::AccountId,
+            ReserveIdentifier = NamedReserveIdentifier,
+            CurrencyId = AssetIdOf<Self>,
+            Balance = Balance,
+        >;
+
+        /// Oracle providing the reference price for the slippage limit
+        type Oracle: AggregatedPriceOracle<AssetIdOf<Self>, Self::BlockNumber, EmaPrice>;
+
+        /// Source of the oracle price
+        #[pallet::constant]
+        type OracleSource: Get<Source>;
+
+        /// Aggregation period of the oracle price
+        #[pallet::constant]
+        type OraclePricePeriod: Get<OraclePeriod>;
+
+        /// Share of every trade paid to `ExecutionFeeReceiver`
+        #[pallet::constant]
+        type ExecutionFee: Get<Permill>;
+
+        /// Account which receives the execution fees, e.g. the treasury or the block author
+        type ExecutionFeeReceiver: Get<Self::AccountId>;
+
+        /// Min amount of a single trade
+        #[pallet::constant]
+        type MinTradeAmount: Get<Balance>;
+
+        /// Min number of blocks between two trades of a schedule
+        #[pallet::constant]
+        type MinPeriod: Get<Self::BlockNumber>;
+
+        /// Max number of trades in the route of a schedule
+        #[pallet::constant]
+        type MaxRouteLength: Get<u32>;
+
+        /// Max number of schedules executed in a block
+        #[pallet::constant]
+        type MaxSchedulesPerBlock: Get<u32>;
+
+        /// Max number of consecutive failed trades after which a schedule is terminated
+        #[pallet::constant]
+        type MaxRetries: Get<u8>;
+
+        /// Number of blocks after which a failed trade is retried. Doubles with each consecutive failure.
+        #[pallet::constant]
+        type RetryDelay: Get<Self::BlockNumber>;
+
+        /// Weight information for the extrinsics.
+        type WeightInfo: WeightInfo;
+
+        /// Helper creating the pools of the routes executed in benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<AssetIdOf<Self>>;
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A schedule has been created
+        Scheduled { id: ScheduleId, who: T::AccountId },
+        /// A schedule has been planned for execution in a block
+        ExecutionPlanned {
+            id: ScheduleId,
+            who: T::AccountId,
+            block: T::BlockNumber,
+        },
+        /// A trade of a schedule has been executed
+        TradeExecuted {
+            id: ScheduleId,
+            who: T::AccountId,
+            amount_in: Balance,
+            amount_out: Balance,
+            fee: Balance,
+        },
+        /// A trade of a schedule has failed and will be retried
+        TradeFailed {
+            id: ScheduleId,
+            who: T::AccountId,
+            error: DispatchError,
+        },
+        /// The budget of a schedule has been spent
+        Completed { id: ScheduleId, who: T::AccountId },
+        /// A schedule has been terminated, its remaining budget has been released
+        Terminated {
+            id: ScheduleId,
+            who: T::AccountId,
+            error: Option<DispatchError>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Schedule cannot be found
+        ScheduleNotFound,
+        /// Amount per trade is lower than `MinTradeAmount`
+        TradeAmountTooSmall,
+        /// Total amount is lower than the amount per trade
+        TotalAmountTooSmall,
+        /// Period is zero or lower than `MinPeriod`
+        PeriodTooShort,
+        /// Execution block must be in the future
+        InvalidExecutionBlock,
+        /// Route has no trades
+        RouteHasNoTrades,
+        /// Route does not start with `asset_in` or does not end with `asset_out`, or its trades are not connected
+        InvalidRoute,
+        /// Free balance is too low to reserve the total amount
+        InsufficientBalance,
+        /// Reserved amount not sufficient.
+        InsufficientReservedAmount,
+        /// No block with a free slot has been found to plan the schedule
+        NoFreeBlockFound,
+        /// Oracle price is not available
+        OraclePriceNotAvailable,
+        /// Amount received from the trade is lower than allowed by the slippage limit
+        TradeLimitReached,
+        /// Error with math calculations
+        MathError,
+        /// The caller does not have permission to complete the action
+        Forbidden,
+        /// Reached the maximum value for the schedule id
+        ScheduleIdOutOfBound,
+    }
+
+    /// ID sequencer for Schedules
+    #[pallet::storage]
+    #[pallet::getter(fn next_schedule_id)]
+    pub type NextScheduleId<T: Config> = StorageValue<_, ScheduleId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn schedules)]
+    pub type Schedules<T: Config> = StorageMap<_, Blake2_128Concat, ScheduleId, ScheduleOf<T>, OptionQuery>;
+
+    /// Remaining budget of the schedules
+    #[pallet::storage]
+    #[pallet::getter(fn remaining_amounts)]
+    pub type RemainingAmounts<T: Config> = StorageMap<_, Blake2_128Concat, ScheduleId, Balance, OptionQuery>;
+
+    /// Number of consecutive failed trades of the schedules
+    #[pallet::storage]
+    #[pallet::getter(fn retries)]
+    pub type Retries<T: Config> = StorageMap<_, Blake2_128Concat, ScheduleId, u8, ValueQuery>;
+
+    /// Schedules to execute, indexed by the execution block
+    #[pallet::storage]
+    #[pallet::getter(fn schedule_ids_per_block)]
+    pub type ScheduleIdsPerBlock<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<ScheduleId, T::MaxSchedulesPerBlock>, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let schedule_ids = <ScheduleIdsPerBlock<T>>::take(now);
+
+            let mut weight = <T as Config>::WeightInfo::on_initialize(schedule_ids.len() as u32);
+            for schedule_id in schedule_ids.iter() {
+                weight.saturating_accrue(Self::execute_schedule(*schedule_id, now));
+            }
+
+            weight
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new DCA schedule and plan its first trade
+        ///
+        /// Parameters:
+        /// - `schedule`: Parameters of the schedule. The `owner` is replaced by the caller.
+        /// - `start_execution_block`: Block of the first trade. The next block is used if not set.
+        ///
+        /// Validations:
+        /// - amount_per_trade must not be lower than `MinTradeAmount`
+        /// - total_amount must not be lower than amount_per_trade
+        /// - period must not be zero nor lower than `MinPeriod`
+        /// - route must start with `asset_in`, end with `asset_out` and its trades must be connected
+        /// - start_execution_block must be in the future
+        ///
+        /// Emits `Scheduled` and `ExecutionPlanned` events when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::schedule())]
+        pub fn schedule(
+            origin: OriginFor<T>,
+            schedule: ScheduleOf<T>,
+            start_execution_block: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let schedule = Schedule {
+                owner: who.clone(),
+                ..schedule
+            };
+
+            ensure!(
+                schedule.amount_per_trade >= T::MinTradeAmount::get(),
+                Error::<T>::TradeAmountTooSmall
+            );
+            ensure!(
+                schedule.total_amount >= schedule.amount_per_trade,
+                Error::<T>::TotalAmountTooSmall
+            );
+            ensure!(
+                !schedule.period.is_zero() && schedule.period >= T::MinPeriod::get(),
+                Error::<T>::PeriodTooShort
+            );
+            Self::ensure_route_is_valid(&schedule)?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let start_execution_block = start_execution_block.unwrap_or_else(|| now.saturating_add(One::one()));
+            ensure!(start_execution_block > now, Error::<T>::InvalidExecutionBlock);
+
+            ensure!(
+                <T as Config>::Currency::free_balance(schedule.asset_in, &who) >= schedule.total_amount,
+                Error::<T>::InsufficientBalance
+            );
+
+            <NextScheduleId<T>>::try_mutate(|next_id| -> DispatchResult {
+                let id = *next_id;
+
+                <T as Config>::Currency::reserve_named(
+                    &NAMED_RESERVE_ID,
+                    schedule.asset_in,
+                    &who,
+                    schedule.total_amount,
+                )?;
+                <RemainingAmounts<T>>::insert(id, schedule.total_amount);
+                <Schedules<T>>::insert(id, schedule);
+
+                Self::deposit_event(Event::Scheduled { id, who: who.clone() });
+                Self::plan_schedule(id, &who, start_execution_block)?;
+
+                *next_id = next_id
+                    .checked_add(One::one())
+                    .ok_or(Error::<T>::ScheduleIdOutOfBound)?;
+                Ok(())
+            })
+        }
+
+        /// Terminate a DCA schedule and release its remaining budget
+        ///
+        /// Parameters:
+        /// - `schedule_id`: ID of the schedule
+        /// - `next_execution_block`: Block in which the schedule is planned. If not set, the schedule is removed
+        ///   from the plan lazily - it is skipped when its block is reached.
+        ///
+        /// Validations:
+        /// - caller is schedule owner
+        ///
+        /// Emits `Terminated` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::terminate())]
+        pub fn terminate(
+            origin: OriginFor<T>,
+            schedule_id: ScheduleId,
+            next_execution_block: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let schedule = <Schedules<T>>::get(schedule_id).ok_or(Error::<T>::ScheduleNotFound)?;
+
+            ensure!(schedule.owner == who, Error::<T>::Forbidden);
+
+            if let Some(block) = next_execution_block {
+                <ScheduleIdsPerBlock<T>>::mutate(block, |schedule_ids| {
+                    schedule_ids.retain(|id| *id != schedule_id);
+                });
+            }
+
+            Self::remove_schedule(schedule_id, &schedule)?;
+
+            Self::deposit_event(Event::Terminated {
+                id: schedule_id,
+                who,
+                error: None,
+            });
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn ensure_route_is_valid(schedule: &ScheduleOf<T>) -> DispatchResult {
+        let first_trade = schedule.route.first().ok_or(Error::<T>::RouteHasNoTrades)?;
+        let last_trade = schedule.route.last().ok_or(Error::<T>::RouteHasNoTrades)?;
+        ensure!(
+            first_trade.asset_in == schedule.asset_in && last_trade.asset_out == schedule.asset_out,
+            Error::<T>::InvalidRoute
+        );
+        ensure!(
+            schedule
+                .route
+                .windows(2)
+                .all(|trades| trades[0].asset_out == trades[1].asset_in),
+            Error::<T>::InvalidRoute
+        );
+
+        Ok(())
+    }
+
+    /// Adds the schedule to the first block with a free slot, starting with `block`.
+    fn plan_schedule(schedule_id: ScheduleId, who: &T::AccountId, block: T::BlockNumber) -> DispatchResult {
+        let mut block = block;
+        for _ in 0..MAX_PLANNING_ATTEMPTS {
+            let planned = <ScheduleIdsPerBlock<T>>::mutate(block, |schedule_ids| schedule_ids.try_push(schedule_id));
+            if planned.is_ok() {
+                Self::deposit_event(Event::ExecutionPlanned {
+                    id: schedule_id,
+                    who: who.clone(),
+                    block,
+                });
+                return Ok(());
+            }
+            block.saturating_inc();
+        }
+
+        Err(Error::<T>::NoFreeBlockFound.into())
+    }
+
+    /// Releases the remaining budget of a schedule and removes it from storage.
+    fn remove_schedule(schedule_id: ScheduleId, schedule: &ScheduleOf<T>) -> DispatchResult {
+        let remaining_amount = <RemainingAmounts<T>>::take(schedule_id).unwrap_or_default();
+        <Schedules<T>>::remove(schedule_id);
+        <Retries<T>>::remove(schedule_id);
+
+        let remaining_to_unreserve = <T as Config>::Currency::unreserve_named(
+            &NAMED_RESERVE_ID,
+            schedule.asset_in,
+            &schedule.owner,
+            remaining_amount,
+        );
+        ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+
+        Ok(())
+    }
+
+    /// Executes the next trade of a schedule and plans the following one.
+    /// Returns the weight of selling along the route of the schedule and of the oracle price lookup, which are not
+    /// part of the `on_initialize` benchmark, as they depend on the route executor and the oracle.
+    fn execute_schedule(schedule_id: ScheduleId, now: T::BlockNumber) -> Weight {
+        // the schedule has been terminated
+        let schedule = match <Schedules<T>>::get(schedule_id) {
+            Some(schedule) => schedule,
+            None => return Weight::zero(),
+        };
+        // the weight is charged whether the trade succeeds or not
+        let trade_weight = <T as pallet_route_executor::Config>::WeightInfo::sell(schedule.route.len() as u32)
+            .saturating_add(<T as pallet_route_executor::Config>::WeightInfo::router_fee())
+            .saturating_add(T::Oracle::get_price_weight());
+
+        let result = with_transaction(|| match Self::execute_trade(schedule_id, &schedule) {
+            Ok(amounts) => TransactionOutcome::Commit(Ok(amounts)),
+            Err(e) => TransactionOutcome::Rollback(Err(e)),
+        });
+
+        let next_block = match result {
+            Ok((amount_in, amount_out, fee)) => {
+                <Retries<T>>::remove(schedule_id);
+                Self::deposit_event(Event::TradeExecuted {
+                    id: schedule_id,
+                    who: schedule.owner.clone(),
+                    amount_in,
+                    amount_out,
+                    fee,
+                });
+
+                let remaining_amount = <RemainingAmounts<T>>::get(schedule_id).unwrap_or_default();
+                if remaining_amount < T::MinTradeAmount::get() {
+                    // the rest of the budget is too small to be traded
+                    let _ = Self::remove_schedule(schedule_id, &schedule);
+                    Self::deposit_event(Event::Completed {
+                        id: schedule_id,
+                        who: schedule.owner,
+                    });
+                    return trade_weight;
+                }
+
+                now.saturating_add(schedule.period)
+            }
+            Err(error) => {
+                let retries = <Retries<T>>::mutate(schedule_id, |retries| {
+                    retries.saturating_inc();
+                    *retries
+                });
+                if retries > T::MaxRetries::get() {
+                    let _ = Self::remove_schedule(schedule_id, &schedule);
+                    Self::deposit_event(Event::Terminated {
+                        id: schedule_id,
+                        who: schedule.owner,
+                        error: Some(error),
+                    });
+                    return trade_weight;
+                }
+
+                Self::deposit_event(Event::TradeFailed {
+                    id: schedule_id,
+                    who: schedule.owner.clone(),
+                    error,
+                });
+
+                let backoff = 2u32.saturating_pow(u32::from(retries).saturating_sub(1));
+                // the retry is planned in a later block even if no delay is configured
+                let delay = T::RetryDelay::get().saturating_mul(backoff.into()).max(One::one());
+                now.saturating_add(delay)
+            }
+        };
+
+        if let Err(error) = Self::plan_schedule(schedule_id, &schedule.owner, next_block) {
+            let _ = Self::remove_schedule(schedule_id, &schedule);
+            Self::deposit_event(Event::Terminated {
+                id: schedule_id,
+                who: schedule.owner,
+                error: Some(error),
+            });
+        }
+
+        trade_weight
+    }
+
+    /// Sells the next instalment of a schedule along its route with the route executor, which charges the router fee.
+    /// Returns the amount spent from the budget, the amount received and the execution fee.
+    fn execute_trade(
+        schedule_id: ScheduleId,
+        schedule: &ScheduleOf<T>,
+    ) -> Result<(Balance, Balance, Balance), DispatchError> {
+        let remaining_amount = <RemainingAmounts<T>>::get(schedule_id).ok_or(Error::<T>::ScheduleNotFound)?;
+        let amount_in = schedule.amount_per_trade.min(remaining_amount);
+        let fee = T::ExecutionFee::get().mul_floor(amount_in);
+        let amount_to_sell = amount_in.checked_sub(fee).ok_or(Error::<T>::MathError)?;
+
+        let remaining_to_unreserve =
+            <T as Config>::Currency::unreserve_named(&NAMED_RESERVE_ID, schedule.asset_in, &schedule.owner, amount_in);
+        ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+        <RemainingAmounts<T>>::insert(schedule_id, remaining_amount.saturating_sub(amount_in));
+
+        if !fee.is_zero() {
+            <T as Config>::Currency::transfer(
+                schedule.asset_in,
+                &schedule.owner,
+                &T::ExecutionFeeReceiver::get(),
+                fee,
+            )?;
+        }
+
+        let min_amount_out = Self::min_amount_out(schedule, amount_to_sell)?;
+
+        let origin: <T as frame_system::Config>::RuntimeOrigin = RawOrigin::Signed(schedule.owner.clone()).into();
+        let balance_before = <T as Config>::Currency::free_balance(schedule.asset_out, &schedule.owner);
+        pallet_route_executor::Pallet::<T>::sell(
+            origin,
+            schedule.asset_in,
+            schedule.asset_out,
+            amount_to_sell,
+            min_amount_out,
+            schedule.route.to_vec(),
+        )?;
+
+        let received = <T as Config>::Currency::free_balance(schedule.asset_out, &schedule.owner)
+            .checked_sub(balance_before)
+            .ok_or(Error::<T>::MathError)?;
+        ensure!(received >= min_amount_out, Error::<T>::TradeLimitReached);
+
+        Ok((amount_in, received, fee))
+    }
+
+    /// Min amount of `asset_out` for `amount_in` - the oracle price reduced by the slippage of the schedule.
+    fn min_amount_out(schedule: &ScheduleOf<T>, amount_in: Balance) -> Result<Balance, DispatchError> {
+        let (price, _) = T::Oracle::get_price(
+            schedule.asset_out,
+            schedule.asset_in,
+            T::OraclePricePeriod::get(),
+            T::OracleSource::get(),
+        )
+        .map_err(|_| Error::<T>::OraclePriceNotAvailable)?;
+        let (price_n, price_d): (Balance, Balance) = price.into();
+        ensure!(!price_d.is_zero(), Error::<T>::OraclePriceNotAvailable);
+
+        let expected_amount_out = U256::from(amount_in)
+            .checked_mul(U256::from(price_n))
+            .and_then(|v| v.checked_div(U256::from(price_d)))
+            .ok_or(Error::<T>::MathError)?;
+        let expected_amount_out = Balance::try_from(expected_amount_out).map_err(|_| Error::<T>::MathError)?;
+
+        Ok(expected_amount_out.saturating_sub(schedule.slippage.mul_floor(expected_amount_out)))
+    }
+}
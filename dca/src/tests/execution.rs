@@ -0,0 +1,397 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::weights::WeightInfo;
+use crate::{Error, Event, NAMED_RESERVE_ID};
+use frame_support::assert_ok;
+use frame_support::traits::Hooks;
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::oracle::AggregatedPriceOracle;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pallet_route_executor::weights::WeightInfo as RouterWeightInfo;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+fn create_schedule(total_amount: Balance) {
+    assert_ok!(DCA::schedule(
+        RuntimeOrigin::signed(ALICE),
+        schedule(100 * ONE, total_amount),
+        None
+    ));
+}
+
+#[test]
+fn trade_should_be_executed_in_planned_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 500 * ONE);
+        assert_eq!(
+            Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE),
+            400 * ONE
+        );
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 198 * ONE);
+        assert_eq!(DCA::remaining_amounts(0), Some(400 * ONE));
+        assert!(DCA::schedule_ids_per_block(2).is_empty());
+        assert_eq!(DCA::schedule_ids_per_block(2 + PERIOD).to_vec(), vec![0]);
+
+        expect_events(vec![
+            Event::TradeExecuted {
+                id: 0,
+                who: ALICE,
+                amount_in: 100 * ONE,
+                amount_out: 198 * ONE,
+                fee: ONE,
+            }
+            .into(),
+            Event::ExecutionPlanned {
+                id: 0,
+                who: ALICE,
+                block: 2 + PERIOD,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn execution_fee_should_be_transferred_to_fee_receiver() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &TREASURY), ONE);
+        assert_eq!(Tokens::free_balance(HDX, &POOL), 10_099 * ONE);
+    });
+}
+
+#[test]
+fn router_fee_should_be_charged_from_received_amount() {
+    ExtBuilder::default()
+        .with_router_fee(Permill::from_percent(1))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            create_schedule(500 * ONE);
+
+            // Act
+            run_to_block(2);
+
+            // Assert
+            let router_fee = 198 * ONE / 100;
+            assert_eq!(Tokens::free_balance(DAI, &ALICE), 198 * ONE - router_fee);
+            assert_eq!(Tokens::free_balance(DAI, &TREASURY), router_fee);
+
+            expect_events(vec![
+                Event::TradeExecuted {
+                    id: 0,
+                    who: ALICE,
+                    amount_in: 100 * ONE,
+                    amount_out: 198 * ONE - router_fee,
+                    fee: ONE,
+                }
+                .into(),
+                Event::ExecutionPlanned {
+                    id: 0,
+                    who: ALICE,
+                    block: 2 + PERIOD,
+                }
+                .into(),
+            ]);
+        });
+}
+
+#[test]
+fn schedule_should_be_completed_when_budget_is_spent() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(200 * ONE);
+        run_to_block(2);
+
+        // Act
+        run_to_block(2 + PERIOD);
+
+        // Assert
+        assert_eq!(DCA::schedules(0), None);
+        assert_eq!(DCA::remaining_amounts(0), None);
+        assert!(DCA::schedule_ids_per_block(2 + 2 * PERIOD).is_empty());
+        assert_eq!(Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 800 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 396 * ONE);
+
+        expect_events(vec![
+            Event::TradeExecuted {
+                id: 0,
+                who: ALICE,
+                amount_in: 100 * ONE,
+                amount_out: 198 * ONE,
+                fee: ONE,
+            }
+            .into(),
+            Event::Completed { id: 0, who: ALICE }.into(),
+        ]);
+    });
+}
+
+#[test]
+fn last_trade_should_sell_rest_of_budget() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(250 * ONE);
+        run_to_block(2);
+        run_to_block(2 + PERIOD);
+
+        // Act
+        run_to_block(2 + 2 * PERIOD);
+
+        // Assert
+        assert_eq!(DCA::schedules(0), None);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 750 * ONE);
+
+        expect_events(vec![
+            Event::TradeExecuted {
+                id: 0,
+                who: ALICE,
+                amount_in: 50 * ONE,
+                amount_out: 99 * ONE,
+                fee: ONE / 2,
+            }
+            .into(),
+            Event::Completed { id: 0, who: ALICE }.into(),
+        ]);
+    });
+}
+
+#[test]
+fn failed_trade_should_be_retried_after_retry_delay() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        set_oracle_price(None);
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(DCA::retries(0), 1);
+        assert_eq!(DCA::remaining_amounts(0), Some(500 * ONE));
+        assert_eq!(
+            Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE),
+            500 * ONE
+        );
+        assert_eq!(Tokens::free_balance(HDX, &TREASURY), 0);
+        assert_eq!(DCA::schedule_ids_per_block(5).to_vec(), vec![0]);
+
+        expect_events(vec![
+            Event::TradeFailed {
+                id: 0,
+                who: ALICE,
+                error: Error::<Test>::OraclePriceNotAvailable.into(),
+            }
+            .into(),
+            Event::ExecutionPlanned {
+                id: 0,
+                who: ALICE,
+                block: 5,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn retry_delay_should_double_with_each_failed_retry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        set_oracle_price(None);
+        run_to_block(2);
+
+        // Act
+        run_to_block(5);
+
+        // Assert
+        assert_eq!(DCA::retries(0), 2);
+        assert_eq!(DCA::schedule_ids_per_block(11).to_vec(), vec![0]);
+    });
+}
+
+#[test]
+fn failed_trade_should_be_retried_in_next_block_when_retry_delay_is_zero() {
+    ExtBuilder::default().with_retry_delay(0).build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        set_oracle_price(None);
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(DCA::retries(0), 1);
+        assert!(DCA::schedule_ids_per_block(2).is_empty());
+        assert_eq!(DCA::schedule_ids_per_block(3).to_vec(), vec![0]);
+    });
+}
+
+#[test]
+fn schedule_should_be_terminated_when_retries_are_exhausted() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        set_oracle_price(None);
+        run_to_block(2);
+        run_to_block(5);
+
+        // Act
+        run_to_block(11);
+
+        // Assert
+        assert_eq!(DCA::schedules(0), None);
+        assert_eq!(DCA::retries(0), 0);
+        assert_eq!(Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 1_000 * ONE);
+
+        expect_events(vec![Event::Terminated {
+            id: 0,
+            who: ALICE,
+            error: Some(Error::<Test>::OraclePriceNotAvailable.into()),
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn successful_trade_should_reset_retries() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        set_oracle_price(None);
+        run_to_block(2);
+        set_oracle_price(Some(EmaPrice::new(SELL_RATE, 1)));
+
+        // Act
+        run_to_block(5);
+
+        // Assert
+        assert_eq!(DCA::retries(0), 0);
+        assert_eq!(DCA::remaining_amounts(0), Some(400 * ONE));
+        assert_eq!(DCA::schedule_ids_per_block(5 + PERIOD).to_vec(), vec![0]);
+    });
+}
+
+#[test]
+fn trade_should_fail_when_amount_out_is_below_oracle_price_with_slippage() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        set_oracle_price(Some(EmaPrice::new(3, 1)));
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(DCA::retries(0), 1);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+
+        expect_events(vec![
+            Event::TradeFailed {
+                id: 0,
+                who: ALICE,
+                error: pallet_route_executor::Error::<Test>::TradingLimitReached.into(),
+            }
+            .into(),
+            Event::ExecutionPlanned {
+                id: 0,
+                who: ALICE,
+                block: 5,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn trade_should_be_executed_when_amount_out_is_within_slippage() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        // expected 99 * 2.1 = 207.9, min 197.505
+        set_oracle_price(Some(EmaPrice::new(21, 10)));
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(DCA::retries(0), 0);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 198 * ONE);
+    });
+}
+
+#[test]
+fn schedules_should_be_planned_in_next_free_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        create_schedule(500 * ONE);
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(BOB),
+            schedule(100 * ONE, 500 * ONE),
+            Some(2 + PERIOD)
+        ));
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(BOB),
+            schedule(100 * ONE, 500 * ONE),
+            Some(2 + PERIOD)
+        ));
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(DCA::schedule_ids_per_block(2 + PERIOD).to_vec(), vec![2, 3]);
+        assert_eq!(DCA::schedule_ids_per_block(3 + PERIOD).to_vec(), vec![0, 1]);
+    });
+}
+
+#[test]
+fn on_initialize_weight_should_include_sell_and_oracle_weights_of_executed_schedules() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        create_schedule(500 * ONE);
+        System::set_block_number(2);
+
+        // Act
+        let weight = DCA::on_initialize(2);
+
+        // Assert
+        assert_eq!(
+            weight,
+            <Test as crate::Config>::WeightInfo::on_initialize(1)
+                .saturating_add(<Test as pallet_route_executor::Config>::WeightInfo::sell(1))
+                .saturating_add(<Test as pallet_route_executor::Config>::WeightInfo::router_fee())
+                .saturating_add(<PriceOracle as AggregatedPriceOracle<_, _, _>>::get_price_weight())
+        );
+    });
+}
@@ -0,0 +1,239 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event, Schedule, NAMED_RESERVE_ID};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::PoolType;
+use orml_traits::NamedMultiReservableCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn schedule_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 500 * ONE),
+            None
+        ));
+
+        // Assert
+        assert_eq!(DCA::next_schedule_id(), 1);
+        assert_eq!(DCA::schedules(0), Some(schedule(100 * ONE, 500 * ONE)));
+        assert_eq!(DCA::remaining_amounts(0), Some(500 * ONE));
+        assert_eq!(DCA::schedule_ids_per_block(2).to_vec(), vec![0]);
+        assert_eq!(
+            Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE),
+            500 * ONE
+        );
+
+        expect_events(vec![
+            Event::Scheduled { id: 0, who: ALICE }.into(),
+            Event::ExecutionPlanned {
+                id: 0,
+                who: ALICE,
+                block: 2,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn schedule_should_use_caller_as_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(BOB),
+            schedule(100 * ONE, 500 * ONE),
+            None
+        ));
+
+        // Assert
+        assert_eq!(DCA::schedules(0).unwrap().owner, BOB);
+        assert_eq!(Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &BOB), 500 * ONE);
+        assert_eq!(Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE), 0);
+    });
+}
+
+#[test]
+fn schedule_should_plan_execution_in_given_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 500 * ONE),
+            Some(10)
+        ));
+
+        // Assert
+        assert!(DCA::schedule_ids_per_block(2).is_empty());
+        assert_eq!(DCA::schedule_ids_per_block(10).to_vec(), vec![0]);
+    });
+}
+
+#[test]
+fn schedule_should_plan_execution_in_next_block_when_block_is_full() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 200 * ONE),
+            None
+        ));
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 200 * ONE),
+            None
+        ));
+
+        // Act
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 200 * ONE),
+            None
+        ));
+
+        // Assert
+        assert_eq!(DCA::schedule_ids_per_block(2).to_vec(), vec![0, 1]);
+        assert_eq!(DCA::schedule_ids_per_block(3).to_vec(), vec![2]);
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_trade_amount_is_too_small() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule(ONE - 1, 500 * ONE), None),
+            Error::<Test>::TradeAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_total_amount_is_lower_than_trade_amount() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule(100 * ONE, 50 * ONE), None),
+            Error::<Test>::TotalAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_period_is_too_short() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let schedule = Schedule {
+            period: 4,
+            ..schedule(100 * ONE, 500 * ONE)
+        };
+
+        // Act & Assert
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule, None),
+            Error::<Test>::PeriodTooShort
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_period_is_zero() {
+    ExtBuilder::default().with_min_period(0).build().execute_with(|| {
+        // Arrange
+        let schedule = Schedule {
+            period: 0,
+            ..schedule(100 * ONE, 500 * ONE)
+        };
+
+        // Act & Assert
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule, None),
+            Error::<Test>::PeriodTooShort
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_route_is_empty() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let schedule = Schedule {
+            route: route(vec![]),
+            ..schedule(100 * ONE, 500 * ONE)
+        };
+
+        // Act & Assert
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule, None),
+            Error::<Test>::RouteHasNoTrades
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_route_does_not_end_with_asset_out() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let schedule = Schedule {
+            route: route(vec![(PoolType::XYK, HDX, DOT)]),
+            ..schedule(100 * ONE, 500 * ONE)
+        };
+
+        // Act & Assert
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule, None),
+            Error::<Test>::InvalidRoute
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_route_trades_are_not_connected() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let schedule = Schedule {
+            route: route(vec![(PoolType::XYK, HDX, DOT), (PoolType::XYK, HDX, DAI)]),
+            ..schedule(100 * ONE, 500 * ONE)
+        };
+
+        // Act & Assert
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule, None),
+            Error::<Test>::InvalidRoute
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_execution_block_is_not_in_future() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule(100 * ONE, 500 * ONE), Some(1)),
+            Error::<Test>::InvalidExecutionBlock
+        );
+    });
+}
+
+#[test]
+fn schedule_should_fail_when_balance_is_insufficient() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            DCA::schedule(RuntimeOrigin::signed(ALICE), schedule(100 * ONE, 2_000 * ONE), None),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
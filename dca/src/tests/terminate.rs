@@ -0,0 +1,117 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event, NAMED_RESERVE_ID};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn terminate_should_release_remaining_budget() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 500 * ONE),
+            None
+        ));
+
+        // Act
+        assert_ok!(DCA::terminate(RuntimeOrigin::signed(ALICE), 0, Some(2)));
+
+        // Assert
+        assert_eq!(DCA::schedules(0), None);
+        assert_eq!(DCA::remaining_amounts(0), None);
+        assert!(DCA::schedule_ids_per_block(2).is_empty());
+        assert_eq!(Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 1_000 * ONE);
+
+        expect_events(vec![Event::Terminated {
+            id: 0,
+            who: ALICE,
+            error: None,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn terminate_should_release_remaining_budget_after_trade() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 500 * ONE),
+            None
+        ));
+        run_to_block(2);
+
+        // Act
+        assert_ok!(DCA::terminate(RuntimeOrigin::signed(ALICE), 0, None));
+
+        // Assert
+        assert_eq!(Tokens::reserved_balance_named(&NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 900 * ONE);
+    });
+}
+
+#[test]
+fn terminated_schedule_should_not_be_executed() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 500 * ONE),
+            None
+        ));
+        assert_ok!(DCA::terminate(RuntimeOrigin::signed(ALICE), 0, None));
+
+        // Act
+        run_to_block(2);
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 1_000 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+    });
+}
+
+#[test]
+fn terminate_should_fail_when_schedule_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            DCA::terminate(RuntimeOrigin::signed(ALICE), 0, None),
+            Error::<Test>::ScheduleNotFound
+        );
+    });
+}
+
+#[test]
+fn terminate_should_fail_when_called_by_non_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(DCA::schedule(
+            RuntimeOrigin::signed(ALICE),
+            schedule(100 * ONE, 500 * ONE),
+            None
+        ));
+
+        // Act & Assert
+        assert_noop!(
+            DCA::terminate(RuntimeOrigin::signed(BOB), 0, None),
+            Error::<Test>::Forbidden
+        );
+    });
+}
@@ -0,0 +1,99 @@
+// This file is part of galacticcouncil/warehouse.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_dca
+//!
+//! The benchmarks in `benchmarks.rs` have not been run yet, the figures below are estimates which have to be
+//! replaced by the generated weights. The route executor and oracle weights of the executed schedules are charged
+//! on top of `on_initialize`.
+
+// Command to generate the weights:
+// target/release/hydradx
+// benchmark
+// pallet
+// --chain=dev
+// --steps=5
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --template=.maintain/pallet-weight-template.hbs
+// --pallet=pallet-dca
+// --output=dca.rs
+// --extrinsic=*
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_dca.
+pub trait WeightInfo {
+    fn on_initialize(n: u32) -> Weight;
+    fn schedule() -> Weight;
+    fn terminate() -> Weight;
+}
+
+/// Weights for pallet_dca using the hydraDX node and recommended hardware.
+pub struct HydraWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
+    fn on_initialize(n: u32) -> Weight {
+        Weight::from_ref_time(6_418_000 as u64)
+            .saturating_add(Weight::from_ref_time(213_745_000 as u64).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().reads((16 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+            .saturating_add(T::DbWeight::get().writes((11 as u64).saturating_mul(n as u64)))
+    }
+    fn schedule() -> Weight {
+        Weight::from_ref_time(63_905_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(4 as u64))
+            .saturating_add(T::DbWeight::get().writes(5 as u64))
+    }
+    fn terminate() -> Weight {
+        Weight::from_ref_time(56_372_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(4 as u64))
+            .saturating_add(T::DbWeight::get().writes(5 as u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn on_initialize(n: u32) -> Weight {
+        Weight::from_ref_time(6_418_000 as u64)
+            .saturating_add(Weight::from_ref_time(213_745_000 as u64).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads((16 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes((11 as u64).saturating_mul(n as u64)))
+    }
+    fn schedule() -> Weight {
+        Weight::from_ref_time(63_905_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(4 as u64))
+            .saturating_add(RocksDbWeight::get().writes(5 as u64))
+    }
+    fn terminate() -> Weight {
+        Weight::from_ref_time(56_372_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(4 as u64))
+            .saturating_add(RocksDbWeight::get().writes(5 as u64))
+    }
+}
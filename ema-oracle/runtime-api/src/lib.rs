@@ -0,0 +1,60 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the EMA oracle pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use hydradx_traits::{AggregatedEntry, OraclePeriod};
+pub use pallet_ema_oracle::{AssetId, Balance, OracleError, Price, Source};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// The API to read the EMA oracles.
+    ///
+    /// The entries are updated to the parent block like the ones returned by `AggregatedOracle::get_entry`.
+    pub trait EmaOracleApi<BlockNumber> where
+        BlockNumber: Codec,
+    {
+        /// Returns the entry of the oracle of `period` for `source` and the assets, adjusted to the order of the assets.
+        fn get_entry(
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            period: OraclePeriod,
+        ) -> Result<AggregatedEntry<Balance, BlockNumber, Price>, OracleError>;
+
+        /// Returns the entries of all tracked periods for `source` and the assets, adjusted to the order of the assets.
+        fn get_entries(
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+        ) -> Vec<(OraclePeriod, AggregatedEntry<Balance, BlockNumber, Price>)>;
+
+        /// Returns a page of the tracked combinations of source and ordered asset pair.
+        fn oracle_keys(offset: u32, limit: u32) -> Vec<(Source, (AssetId, AssetId))>;
+
+        /// Returns the entry of `period` for `source` and the assets, derived through intermediate assets if the
+        /// pair is not tracked directly. Falls back to the path with the fewest intermediate assets if no path is
+        /// configured for the pair.
+        fn get_cross_rate_entry(
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            period: OraclePeriod,
+        ) -> Result<AggregatedEntry<Balance, BlockNumber, Price>, OracleError>;
+    }
+}
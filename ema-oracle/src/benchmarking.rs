@@ -22,8 +22,9 @@ use super::*;
 pub const HDX: AssetId = 1_000;
 pub const DOT: AssetId = 2_000;
 
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{account, benchmarks};
 use frame_support::{assert_ok, traits::Hooks};
+use frame_system::RawOrigin;
 
 #[cfg(test)]
 use pretty_assertions::assert_eq;
@@ -59,14 +60,17 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: FixedU128::zero(),
             timestamp: block_num,
         };
 
-        assert_eq!(Accumulator::<T>::get().into_inner(), [((SOURCE, ordered_pair(HDX, DOT)), entry.clone())].into_iter().collect());
+        assert_eq!(Accumulator::<T>::get((SOURCE, ordered_pair(HDX, DOT))), Some(entry.clone()));
+        assert_eq!(AccumulatorCount::<T>::get(), 1);
 
     }: { EmaOracle::<T>::on_finalize(block_num); }
     verify {
-        assert!(Accumulator::<T>::get().is_empty());
+        assert!(Accumulator::<T>::iter().next().is_none());
+        assert_eq!(AccumulatorCount::<T>::get(), 0);
         assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LastBlock)).unwrap(), (entry, block_num));
     }
 
@@ -91,20 +95,29 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: FixedU128::zero(),
             timestamp: block_num,
         };
 
-        assert_eq!(Accumulator::<T>::get().into_inner(), [((SOURCE, ordered_pair(HDX, DOT)), entry.clone())].into_iter().collect());
+        assert_eq!(Accumulator::<T>::get((SOURCE, ordered_pair(HDX, DOT))), Some(entry.clone()));
+        assert_eq!(AccumulatorCount::<T>::get(), 1);
 
     }: { EmaOracle::<T>::on_finalize(block_num); }
     verify {
-        assert!(Accumulator::<T>::get().is_empty());
+        assert!(Accumulator::<T>::iter().next().is_none());
+        assert_eq!(AccumulatorCount::<T>::get(), 0);
         assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LastBlock)).unwrap(), (entry, initial_data_block));
     }
 
     on_finalize_multiple_tokens {
         let b in 1 .. (T::MaxUniqueEntries::get() - 1);
 
+        // worst case: all custom periods are tracked
+        let update_origin = T::UpdatePeriodsOrigin::try_successful_origin().unwrap();
+        for i in 0 .. T::MaxCustomPeriods::get() {
+            assert_ok!(EmaOracle::<T>::add_custom_period(update_origin.clone(), 30 + i, 0));
+        }
+
         let initial_data_block: T::BlockNumber = 5u32.into();
         let block_num = initial_data_block.saturating_add(1_000_000u32.into());
 
@@ -132,6 +145,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: FixedU128::zero(),
             timestamp: block_num,
         };
 
@@ -139,6 +153,7 @@ benchmarks! {
             let asset_a = i * 1_000;
             let asset_b = asset_a + 500;
             assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(asset_a, asset_b), LastBlock)).unwrap(), (entry.clone(), initial_data_block));
+            assert!(Oracles::<T>::contains_key((SOURCE, ordered_pair(asset_a, asset_b), Custom(30))));
         }
     }
 
@@ -167,6 +182,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: FixedU128::zero(),
             timestamp: block_num,
         };
         for i in 0 .. b {
@@ -189,7 +205,10 @@ benchmarks! {
         assert_ok!(*res.borrow());
         entries.push(((SOURCE, ordered_pair(asset_a, asset_b)), entry.clone()));
 
-        assert_eq!(Accumulator::<T>::get().into_inner(), entries.into_iter().collect());
+        assert_eq!(AccumulatorCount::<T>::get(), entries.len() as u32);
+        for (key, entry) in entries {
+            assert_eq!(Accumulator::<T>::get(key), Some(entry));
+        }
     }
 
     on_liquidity_changed_multiple_tokens {
@@ -217,6 +236,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_a, liquidity_asset_b)),
             volume: Volume::from_a_in_b_out(amount_a, amount_b),
             liquidity: Liquidity::new(liquidity_asset_a, liquidity_asset_b),
+            volatility: FixedU128::zero(),
             timestamp: block_num,
         };
         for i in 0 .. b {
@@ -241,11 +261,15 @@ benchmarks! {
             price: Price::from((liquidity_asset_a, liquidity_asset_b)),
             volume: Volume::default(),
             liquidity: Liquidity::new(liquidity_asset_a, liquidity_asset_b),
+            volatility: FixedU128::zero(),
             timestamp: block_num,
         };
         entries.push(((SOURCE, ordered_pair(asset_a, asset_b)), liquidity_entry));
 
-        assert_eq!(Accumulator::<T>::get().into_inner(), entries.into_iter().collect());
+        assert_eq!(AccumulatorCount::<T>::get(), entries.len() as u32);
+        for (key, entry) in entries {
+            assert_eq!(Accumulator::<T>::get(key), Some(entry));
+        }
     }
 
     get_entry {
@@ -280,5 +304,237 @@ benchmarks! {
         }));
     }
 
+    add_custom_period {
+        let b in 1 .. (T::MaxUniqueEntries::get() - 1);
+
+        // worst case: all other custom periods are tracked, so every pair has the most oracles to iterate
+        let origin = T::UpdatePeriodsOrigin::try_successful_origin().unwrap();
+        for i in 1 .. T::MaxCustomPeriods::get() {
+            assert_ok!(EmaOracle::<T>::add_custom_period(origin.clone(), 30 + i, 0));
+        }
+
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        let (amount_in, amount_out) = (1_000_000_000_000, 2_000_000_000_000);
+        let (liquidity_asset_in, liquidity_asset_out) = (1_000_000_000_000_000, 2_000_000_000_000_000);
+        for i in 0 .. b {
+            let asset_a = i * 1_000;
+            let asset_b = asset_a + 500;
+            assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, asset_a, asset_b, amount_in, amount_out, liquidity_asset_in, liquidity_asset_out));
+        }
+        EmaOracle::<T>::on_finalize(block_num);
+
+        let period = 30;
+    }: _<T::RuntimeOrigin>(origin, period, b)
+    verify {
+        for i in 0 .. b {
+            let asset_a = i * 1_000;
+            let asset_b = asset_a + 500;
+            assert!(Oracles::<T>::contains_key((SOURCE, ordered_pair(asset_a, asset_b), Custom(period))));
+        }
+    }
+
+    remove_custom_period {
+        let b in 1 .. (T::MaxUniqueEntries::get() - 1);
+
+        // worst case: all custom periods are tracked, so every pair has the most oracles to iterate
+        let origin = T::UpdatePeriodsOrigin::try_successful_origin().unwrap();
+        for i in 1 .. T::MaxCustomPeriods::get() {
+            assert_ok!(EmaOracle::<T>::add_custom_period(origin.clone(), 30 + i, 0));
+        }
+
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        let (amount_in, amount_out) = (1_000_000_000_000, 2_000_000_000_000);
+        let (liquidity_asset_in, liquidity_asset_out) = (1_000_000_000_000_000, 2_000_000_000_000_000);
+        for i in 0 .. b {
+            let asset_a = i * 1_000;
+            let asset_b = asset_a + 500;
+            assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, asset_a, asset_b, amount_in, amount_out, liquidity_asset_in, liquidity_asset_out));
+        }
+        EmaOracle::<T>::on_finalize(block_num);
+
+        let period = 30;
+        assert_ok!(EmaOracle::<T>::add_custom_period(origin.clone(), period, b));
+    }: _<T::RuntimeOrigin>(origin, period, b)
+    verify {
+        for i in 0 .. b {
+            let asset_a = i * 1_000;
+            let asset_b = asset_a + 500;
+            assert!(!Oracles::<T>::contains_key((SOURCE, ordered_pair(asset_a, asset_b), Custom(period))));
+        }
+    }
+
+    get_twap {
+        let initial_data_block: T::BlockNumber = 5u32.into();
+        let (amount_in, amount_out) = (1_000_000_000_000, 2_000_000_000_000);
+        let (liquidity_asset_in, liquidity_asset_out) = (1_000_000_000_000_000, 2_000_000_000_000_000);
+        let asset_a = 1_000;
+        let asset_b = asset_a + 500;
+
+        // fill the checkpoint ring, the window starts at the oldest checkpoint
+        let max_checkpoints = T::MaxTwapCheckpoints::get();
+        for i in 0 .. max_checkpoints {
+            let block_num = initial_data_block.saturating_add(i.into());
+            frame_system::Pallet::<T>::set_block_number(block_num);
+            EmaOracle::<T>::on_initialize(block_num);
+            assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, asset_a, asset_b, amount_in, amount_out, liquidity_asset_in, liquidity_asset_out));
+            EmaOracle::<T>::on_finalize(block_num);
+        }
+
+        let block_num = initial_data_block.saturating_add(max_checkpoints.into());
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        let window: T::BlockNumber = max_checkpoints.into();
+
+        let res = core::cell::RefCell::new(Err(OracleError::NotPresent));
+
+    }: { let _ = res.replace(EmaOracle::<T>::get_twap(asset_a, asset_b, SOURCE, window)); }
+    verify {
+        let (price, _) = fixed_prices(Price::from((liquidity_asset_in, liquidity_asset_out)));
+        assert_eq!(*res.borrow(), Ok(Price::new(price.into_inner(), FixedU128::DIV)));
+    }
+
+    update_twap_checkpoints {
+        let initial_data_block: T::BlockNumber = 5u32.into();
+        let (amount_in, amount_out) = (1_000_000_000_000, 2_000_000_000_000);
+        let (liquidity_asset_in, liquidity_asset_out) = (1_000_000_000_000_000, 2_000_000_000_000_000);
+        let asset_a = 1_000;
+        let asset_b = asset_a + 500;
+
+        // worst case: the ring is full, so the oldest checkpoint is dropped
+        let max_checkpoints = T::MaxTwapCheckpoints::get();
+        for i in 0 .. max_checkpoints {
+            let block_num = initial_data_block.saturating_add(i.into());
+            frame_system::Pallet::<T>::set_block_number(block_num);
+            EmaOracle::<T>::on_initialize(block_num);
+            assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, asset_a, asset_b, amount_in, amount_out, liquidity_asset_in, liquidity_asset_out));
+            EmaOracle::<T>::on_finalize(block_num);
+        }
+
+        let block_num = initial_data_block.saturating_add(max_checkpoints.into());
+        let entry = OracleEntry {
+            price: Price::from((liquidity_asset_in, liquidity_asset_out)),
+            volume: Volume::from_a_in_b_out(amount_in, amount_out),
+            liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: FixedU128::zero(),
+            timestamp: block_num,
+        };
+    }: { EmaOracle::<T>::update_twap(SOURCE, ordered_pair(asset_a, asset_b), &entry); }
+    verify {
+        let checkpoints = TwapCheckpoints::<T>::get((SOURCE, ordered_pair(asset_a, asset_b)));
+        assert_eq!(checkpoints.len() as u32, max_checkpoints);
+        assert_eq!(checkpoints.last().map(|checkpoint| checkpoint.timestamp), Some(block_num));
+    }
+
+    remove_oracle {
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        let (amount_in, amount_out) = (1_000_000_000_000, 2_000_000_000_000);
+        let (liquidity_asset_in, liquidity_asset_out) = (1_000_000_000_000_000, 2_000_000_000_000_000);
+        assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, HDX, DOT, amount_in, amount_out, liquidity_asset_in, liquidity_asset_out));
+        EmaOracle::<T>::on_finalize(block_num);
+
+        // worst case: all custom periods are tracked
+        let update_origin = T::UpdatePeriodsOrigin::try_successful_origin().unwrap();
+        for i in 0 .. T::MaxCustomPeriods::get() {
+            assert_ok!(EmaOracle::<T>::add_custom_period(update_origin.clone(), 30 + i, 1));
+        }
+
+        let origin = T::RemoveOracleOrigin::try_successful_origin().unwrap();
+    }: _<T::RuntimeOrigin>(origin, SOURCE, HDX, DOT)
+    verify {
+        for period in EmaOracle::<T>::tracked_periods() {
+            assert!(!Oracles::<T>::contains_key((SOURCE, ordered_pair(HDX, DOT), period)));
+        }
+        assert!(TwapCheckpoints::<T>::get((SOURCE, ordered_pair(HDX, DOT))).is_empty());
+    }
+
+    add_feeder {
+        let origin = T::ManageFeedersOrigin::try_successful_origin().unwrap();
+        for i in 0 .. T::MaxFeeders::get() - 1 {
+            assert_ok!(EmaOracle::<T>::add_feeder(origin.clone(), account("feeder", i, 0)));
+        }
+        let who: T::AccountId = account("feeder", T::MaxFeeders::get(), 0);
+    }: _<T::RuntimeOrigin>(origin, who.clone())
+    verify {
+        assert!(EmaOracle::<T>::feeders().contains(&who));
+    }
+
+    remove_feeder {
+        let origin = T::ManageFeedersOrigin::try_successful_origin().unwrap();
+        for i in 0 .. T::MaxFeeders::get() {
+            assert_ok!(EmaOracle::<T>::add_feeder(origin.clone(), account("feeder", i, 0)));
+        }
+        let who: T::AccountId = account("feeder", T::MaxFeeders::get() - 1, 0);
+    }: _<T::RuntimeOrigin>(origin, who.clone())
+    verify {
+        assert!(!EmaOracle::<T>::feeders().contains(&who));
+    }
+
+    submit_price {
+        let origin = T::ManageFeedersOrigin::try_successful_origin().unwrap();
+        let feeders: Vec<T::AccountId> = (0 .. T::MaxFeeders::get()).map(|i| account("feeder", i, 0)).collect();
+        for feeder in feeders.iter() {
+            assert_ok!(EmaOracle::<T>::add_feeder(origin.clone(), feeder.clone()));
+        }
+        let price = Price::new(2_000_000_000_000, 1_000_000_000_000);
+        let liquidity = Liquidity::new(1_000_000_000_000_000, 500_000_000_000_000);
+
+        // worst case: the price is checked against the last block oracle
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        assert_ok!(EmaOracle::<T>::submit_price(RawOrigin::Signed(feeders[0].clone()).into(), HDX, DOT, price, liquidity));
+        EmaOracle::<T>::on_finalize(block_num);
+
+        // worst case: all other feeders already submitted a price in this block
+        let block_num: T::BlockNumber = 6u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        for feeder in feeders.iter().skip(1) {
+            assert_ok!(EmaOracle::<T>::submit_price(RawOrigin::Signed(feeder.clone()).into(), HDX, DOT, price, liquidity));
+        }
+        let caller = feeders[0].clone();
+    }: _(RawOrigin::Signed(caller), HDX, DOT, price, liquidity)
+    verify {
+        assert_eq!(EmaOracle::<T>::external_submissions(ordered_pair(HDX, DOT)).len() as u32, T::MaxFeeders::get());
+        assert!(Accumulator::<T>::contains_key((EXTERNAL_SOURCE, ordered_pair(HDX, DOT))));
+    }
+
+    clear_external_submissions {
+        let b in 1 .. T::MaxUniqueEntries::get();
+
+        // worst case: all feeders submitted a price of every pair
+        let submissions: BoundedVec<(T::AccountId, Price, Liquidity<Balance>), T::MaxFeeders> = (0 .. T::MaxFeeders::get())
+            .map(|i| (account("feeder", i, 0), Price::new(2_000_000_000_000, 1_000_000_000_000), Liquidity::new(1_000_000_000_000_000, 500_000_000_000_000)))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        for i in 0 .. b {
+            let asset_a = i * 1_000;
+            let asset_b = asset_a + 500;
+            ExternalSubmissions::<T>::insert(ordered_pair(asset_a, asset_b), submissions.clone());
+        }
+    }: { let _ = ExternalSubmissions::<T>::clear(b, None); }
+    verify {
+        assert!(ExternalSubmissions::<T>::iter().next().is_none());
+    }
+
+    set_cross_rate_path {
+        let origin = T::UpdateCrossRatePathsOrigin::try_successful_origin().unwrap();
+        let path: BoundedVec<AssetId, T::MaxCrossRateHops> = (0 .. T::MaxCrossRateHops::get())
+            .map(|i| 10_000 + i)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+    }: _<T::RuntimeOrigin>(origin, SOURCE, HDX, DOT, path.clone())
+    verify {
+        assert_eq!(EmaOracle::<T>::cross_rate_paths((SOURCE, ordered_pair(HDX, DOT))), Some(path));
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -53,28 +53,80 @@
 //! this aggregation is NOT based on EMA, yet, it just sums the volume and replaces price and
 //! liquidity with the most recent value.
 //!
+//! Each `(source, asset_pair)` is accumulated in its own storage entry, so the cost of recording an
+//! entry does not grow with the number of pairs traded in the block. At most `MaxUniqueEntries`
+//! different pairs are accumulated per block, which bounds the weight of `on_finalize`. Entries of
+//! pairs already accumulated are always updated. Entries of further pairs are dropped: `on_trade`
+//! and `on_liquidity_changed` return `TooManyUniqueEntries` and nothing is recorded for the pair in
+//! this block. The calling pallet decides whether the operation fails on this error (e.g. the xyk
+//! pallet fails the trade) or proceeds without updating the oracle.
+//!
 //! At the end of the block, all the entries are merged into permanent storage via the exponential
 //! moving average logic defined in the math package this pallet depens on. There is one oracle
 //! entry for each combination of `(source, asset_pair, period)` in storage.
 //!
+//! If `EmitOracleUpdatedEvents` is set, an `OracleUpdated` event with the price, volume and
+//! liquidity of the block is emitted for every updated `(source, asset_pair)`. An
+//! `OracleInitialized` event is emitted whenever the oracles of a `(source, asset_pair)` are
+//! initialized.
+//!
+//! Alongside the EMA oracles, the pallet keeps cumulative prices of every `(source, asset_pair)`
+//! with a ring of the most recent checkpoints, from which time-weighted average prices over an
+//! exact window of blocks are calculated (see `TwapOracle`).
+//!
+//! Besides `SupportedPeriods`, custom periods of a given length in blocks can be added and removed
+//! by `UpdatePeriodsOrigin`. Their smoothing factor is derived from the length. A new custom period
+//! is initialized with the `LastBlock` oracles of all tracked pairs.
+//!
+//! The oracles of a pair can be removed by `RemoveOracleOrigin`, e.g. after an asset was delisted.
+//! Oracles of pairs without activity for more than `OracleInactivityThreshold` blocks are removed in
+//! `on_idle`, within the remaining weight of the block.
+//!
+//! Prices of assets without an on-chain pool can be submitted by a set of feeders managed by
+//! `ManageFeedersOrigin`. The median of the prices submitted for a pair in a block enters the
+//! accumulator under the `EXTERNAL_SOURCE` source. Submissions containing zeros or deviating more
+//! than `MaxExternalPriceDeviation` from the `LastBlock` oracle of the pair are rejected with an
+//! `ExternalPriceRejected` event.
+//!
+//! Prices of pairs without a direct oracle can be derived through intermediate assets. The path of
+//! intermediate assets of a pair can be configured by `UpdateCrossRatePathsOrigin`. On-chain, the
+//! `CrossRatePriceOracle` implementation of `PriceOracle` only derives prices through configured
+//! paths. The `get_cross_rate_entry` runtime API falls back to the path with the fewest intermediate
+//! assets among the tracked pairs of the source, which requires iterating over all oracles.
+//!
+//! Every oracle also tracks the volatility of the price as the exponentially weighted variance of the
+//! price returns between blocks with activity, integrated with the same smoothing factor as the
+//! price. It is exposed via the `VolatilityOracle` trait.
+//!
 //! Oracle values are accessed lazily. This means that the storage does not contain the most recent
 //! value, but the value calculated the last time it was updated via trade or liquidity change. On a
 //! read the values are read from storage and then fast-forwarded (assuming the volume to be zero
 //! and the price and liquidity to be constant) to the last block. Note: The most recent oracle
 //! values are always from the last block. This avoids e.g. sandwiching risks. If you want current
 //! prices you should use a spot price or similar.
+//!
+//! `OraclePriceProvider` provides the prices of the oracles of a configured period as
+//! `NativePriceOracle` and `SpotPriceProvider`, e.g. to price transaction fees. It falls back to the
+//! next configured source if the oracle of a source is missing or was not updated recently enough.
+//!
+//! Off-chain services can read the updated oracle values via the runtime API defined in
+//! `pallet-ema-oracle-runtime-api`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::pallet_prelude::*;
 use frame_support::sp_runtime::traits::{BlockNumberProvider, One, Zero};
 use hydradx_traits::{
-    AggregatedEntry, AggregatedOracle, AggregatedPriceOracle, Liquidity, OnCreatePoolHandler,
-    OnLiquidityChangedHandler, OnTradeHandler,
+    pools::SpotPriceProvider,
+    AggregatedEntry, AggregatedOracle, AggregatedPriceOracle, Liquidity, NativePriceOracle, OnCreatePoolHandler,
+    OnLiquidityChangedHandler, OnTradeHandler, OracleLastUpdate,
     OraclePeriod::{self, *},
-    Volume,
+    PriceOracle, TwapOracle, VolatilityOracle, Volume,
 };
-use sp_arithmetic::traits::Saturating;
+use sp_arithmetic::traits::{CheckedSub, SaturatedConversion, Saturating};
+use sp_arithmetic::{FixedPointNumber, FixedU128, Permill};
+use sp_core::U256;
+use sp_std::collections::btree_map::BTreeMap;
 use sp_std::marker::PhantomData;
 use sp_std::prelude::*;
 
@@ -90,13 +142,19 @@ use weights::WeightInfo;
 
 mod benchmarking;
 
+pub mod migration;
+
 /// Maximum number of unique oracle entries expected in one block. Empirically determined by running
 /// `trades_estimation.py` and rounding up from 212 to 300. Not necessarily representative for all
-/// chains, configure `MaxUniqueEntries` according to your chain.
+/// chains, configure `MaxUniqueEntries` according to your chain. The cost of accumulating an entry
+/// does not depend on this limit, it only bounds the weight of `on_finalize`.
 pub const MAX_UNIQUE_ENTRIES: u32 = 300;
 /// The maximum number of periods that could have corresponding oracles.
 pub const MAX_PERIODS: u32 = OraclePeriod::all_periods().len() as u32;
 
+/// Source of the prices submitted by the external feeders.
+pub const EXTERNAL_SOURCE: Source = *b"external";
+
 const LOG_TARGET: &str = "runtime::ema-oracle";
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
@@ -106,10 +164,13 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::BoundedBTreeMap;
-    use frame_system::pallet_prelude::BlockNumberFor;
+    use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -125,28 +186,149 @@ pub mod pallet {
         /// The periods supported by the pallet. I.e. which oracles to track.
         type SupportedPeriods: Get<BoundedVec<OraclePeriod, ConstU32<MAX_PERIODS>>>;
 
-        /// Maximum number of unique oracle entries expected in one block.
+        /// Maximum number of unique oracle entries accumulated in one block. Bounds the weight of
+        /// `on_finalize`. Activity of further pairs in the block is dropped and rejected with
+        /// `TooManyUniqueEntries`.
         #[pallet::constant]
         type MaxUniqueEntries: Get<u32>;
+
+        /// Origin that can add and remove custom periods.
+        type UpdatePeriodsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of custom periods. The weight of `on_trade` and `on_liquidity_changed`
+        /// includes updating the oracles of this many custom periods in `on_finalize`.
+        #[pallet::constant]
+        type MaxCustomPeriods: Get<u32>;
+
+        /// Number of price updates of a pair kept to calculate time-weighted average prices.
+        #[pallet::constant]
+        type MaxTwapCheckpoints: Get<u32>;
+
+        /// Origin that can remove the oracles of a pair.
+        type RemoveOracleOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of blocks without activity after which the oracles of a pair are removed in
+        /// `on_idle`. Zero disables the removal of inactive oracles.
+        #[pallet::constant]
+        type OracleInactivityThreshold: Get<Self::BlockNumber>;
+
+        /// Origin that can add and remove external price feeders.
+        type ManageFeedersOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of external price feeders.
+        #[pallet::constant]
+        type MaxFeeders: Get<u32>;
+
+        /// Maximum deviation of a submitted price from the `LastBlock` oracle of the external source.
+        #[pallet::constant]
+        type MaxExternalPriceDeviation: Get<Permill>;
+
+        /// Origin that can set the paths used to derive cross-rate prices.
+        type UpdateCrossRatePathsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of intermediate assets on the path of a cross-rate price.
+        #[pallet::constant]
+        type MaxCrossRateHops: Get<u32>;
+
+        /// If set, an `OracleUpdated` event is emitted for every updated source and asset pair at
+        /// the end of the block.
+        #[pallet::constant]
+        type EmitOracleUpdatedEvents: Get<bool>;
     }
 
     #[pallet::error]
+    #[cfg_attr(test, derive(PartialEq, Eq))]
     pub enum Error<T> {
         TooManyUniqueEntries,
         OnTradeValueZero,
+        /// Custom period must be longer than one block.
+        InvalidPeriod,
+        /// The period is already tracked.
+        PeriodAlreadyTracked,
+        /// The custom period does not exist.
+        CustomPeriodNotFound,
+        /// The maximum number of custom periods has been reached.
+        TooManyCustomPeriods,
+        /// More oracles are tracked than declared by the caller.
+        TrackedPairsWitnessTooLow,
+        /// No oracle is tracked for the source and assets.
+        OracleNotFound,
+        /// The account is not an external price feeder.
+        NotFeeder,
+        /// The account is already an external price feeder.
+        FeederAlreadyAdded,
+        /// The maximum number of external price feeders has been reached.
+        TooManyFeeders,
+        /// The feeder already submitted a price of the pair in this block.
+        AlreadySubmitted,
+        /// The assets of the pair must differ.
+        SameAssets,
+        /// The intermediate assets of a path must differ from each other and from the pair.
+        InvalidPath,
     }
 
     #[pallet::event]
-    pub enum Event<T: Config> {}
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A custom period was added and its oracles were initialized.
+        CustomPeriodAdded { period: u32 },
+        /// A custom period was removed and its oracles were removed.
+        CustomPeriodRemoved { period: u32 },
+        /// All oracles of a source and (ordered) asset pair were removed.
+        OracleRemoved { source: Source, assets: (AssetId, AssetId) },
+        /// An external price feeder was added.
+        FeederAdded { who: T::AccountId },
+        /// An external price feeder was removed.
+        FeederRemoved { who: T::AccountId },
+        /// A feeder submitted the price and liquidity of an (ordered) asset pair.
+        ExternalPriceSubmitted {
+            feeder: T::AccountId,
+            assets: (AssetId, AssetId),
+            price: Price,
+            liquidity: Liquidity<Balance>,
+        },
+        /// A price submitted by a feeder was rejected and not taken into account.
+        ExternalPriceRejected {
+            feeder: T::AccountId,
+            assets: (AssetId, AssetId),
+            price: Price,
+            liquidity: Liquidity<Balance>,
+            reason: ExternalPriceRejection,
+        },
+        /// The path of intermediate assets of an (ordered) asset pair was set.
+        CrossRatePathSet {
+            source: Source,
+            assets: (AssetId, AssetId),
+            path: Vec<AssetId>,
+        },
+        /// The path of intermediate assets of an (ordered) asset pair was removed.
+        CrossRatePathRemoved { source: Source, assets: (AssetId, AssetId) },
+        /// The oracles of a source and (ordered) asset pair were updated with the values of the
+        /// block. Only emitted if `EmitOracleUpdatedEvents` is set.
+        OracleUpdated {
+            source: Source,
+            assets: (AssetId, AssetId),
+            price: Price,
+            volume: Volume<Balance>,
+            liquidity: Liquidity<Balance>,
+        },
+        /// The oracles of a source and (ordered) asset pair were initialized.
+        OracleInitialized { source: Source, assets: (AssetId, AssetId) },
+    }
 
     /// Accumulator for oracle data in current block that will be recorded at the end of the block.
+    ///
+    /// Keyed by data source and involved asset ids, so that accumulating an entry does not depend on
+    /// the number of entries already accumulated in the block.
     #[pallet::storage]
     #[pallet::getter(fn accumulator)]
-    pub type Accumulator<T: Config> = StorageValue<
-        _,
-        BoundedBTreeMap<(Source, (AssetId, AssetId)), OracleEntry<T::BlockNumber>, T::MaxUniqueEntries>,
-        ValueQuery,
-    >;
+    pub type Accumulator<T: Config> =
+        StorageMap<_, Twox64Concat, (Source, (AssetId, AssetId)), OracleEntry<T::BlockNumber>, OptionQuery>;
+
+    /// Number of entries in the accumulator, limited by `MaxUniqueEntries`.
+    #[pallet::storage]
+    #[pallet::getter(fn accumulator_count)]
+    pub type AccumulatorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
     /// Orace storage keyed by data source, involved asset ids and the period length of the oracle.
     ///
@@ -164,6 +346,58 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Ring of the most recent cumulative price checkpoints keyed by data source and involved
+    /// asset ids, oldest first. The last checkpoint is the current accumulator.
+    #[pallet::storage]
+    #[pallet::getter(fn twap_checkpoints)]
+    pub type TwapCheckpoints<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        (Source, (AssetId, AssetId)),
+        BoundedVec<TwapCheckpoint<T::BlockNumber>, T::MaxTwapCheckpoints>,
+        ValueQuery,
+    >;
+
+    /// Lengths in blocks of the custom periods tracked in addition to `SupportedPeriods`.
+    #[pallet::storage]
+    #[pallet::getter(fn custom_periods)]
+    pub type CustomPeriods<T: Config> = StorageValue<_, BoundedVec<u32, T::MaxCustomPeriods>, ValueQuery>;
+
+    /// Key of the last oracle checked for inactivity in `on_idle`. The next check continues after
+    /// it, the iteration starts over when it is not set.
+    #[pallet::storage]
+    #[pallet::getter(fn pruning_cursor)]
+    pub type PruningCursor<T: Config> = StorageValue<_, (Source, (AssetId, AssetId), OraclePeriod), OptionQuery>;
+
+    /// Accounts allowed to submit prices of the external source.
+    #[pallet::storage]
+    #[pallet::getter(fn feeders)]
+    pub type Feeders<T: Config> = StorageValue<_, BoundedVec<T::AccountId, T::MaxFeeders>, ValueQuery>;
+
+    /// Prices and liquidity submitted by the feeders in the current block, keyed by the ordered
+    /// asset pair. Cleared at the end of the block.
+    #[pallet::storage]
+    #[pallet::getter(fn external_submissions)]
+    pub type ExternalSubmissions<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        (AssetId, AssetId),
+        BoundedVec<(T::AccountId, Price, Liquidity<Balance>), T::MaxFeeders>,
+        ValueQuery,
+    >;
+
+    /// Intermediate assets used to derive the cross-rate price of an asset pair, keyed by data
+    /// source and the ordered asset pair. Ordered from the first to the second asset of the pair.
+    #[pallet::storage]
+    #[pallet::getter(fn cross_rate_paths)]
+    pub type CrossRatePaths<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        (Source, (AssetId, AssetId)),
+        BoundedVec<AssetId, T::MaxCrossRateHops>,
+        OptionQuery,
+    >;
+
     #[pallet::genesis_config]
     #[derive(Default)]
     pub struct GenesisConfig {
@@ -179,6 +413,7 @@ pub mod pallet {
                         price,
                         volume: Volume::default(),
                         liquidity,
+                        volatility: FixedU128::zero(),
                         timestamp: T::BlockNumber::zero(),
                     };
                     if ordered_pair(asset_a, asset_b) == (asset_a, asset_b) {
@@ -191,6 +426,7 @@ pub mod pallet {
                 for period in T::SupportedPeriods::get() {
                     Pallet::<T>::update_oracle(source, ordered_pair(asset_a, asset_b), period, entry.clone());
                 }
+                Pallet::<T>::update_twap(source, ordered_pair(asset_a, asset_b), &entry);
             }
         }
     }
@@ -198,12 +434,20 @@ pub mod pallet {
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            // on_finalize clears the submissions of at most `MaxUniqueEntries` external pairs
             T::WeightInfo::on_finalize_no_entry()
+                .saturating_add(T::WeightInfo::clear_external_submissions(T::MaxUniqueEntries::get()))
         }
 
         fn on_finalize(_n: BlockNumberFor<T>) {
             // update oracles based on data accumulated during the block
             Self::update_oracles_from_accumulator();
+            // the medians of the external prices are part of the accumulated data
+            let _ = ExternalSubmissions::<T>::clear(T::MaxUniqueEntries::get(), None);
+        }
+
+        fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::remove_inactive_oracles(n, remaining_weight)
         }
 
         fn integrity_test() {
@@ -211,30 +455,363 @@ pub mod pallet {
                 T::MaxUniqueEntries::get() > 0,
                 "At least one trade should be possible per block."
             );
+            assert!(
+                T::MaxTwapCheckpoints::get() > 0,
+                "At least the current cumulative price should be kept."
+            );
         }
     }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Add a custom period of `period` blocks, its smoothing factor is derived from the length.
+        ///
+        /// The oracles of the period are initialized with the `LastBlock` oracles of all tracked
+        /// pairs, so `tracked_pairs` must be at least the number of tracked pairs. The weight per pair
+        /// covers iterating the oracles of all periods of the pair.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `UpdatePeriodsOrigin`
+        /// - `period`: length of the period in blocks
+        /// - `tracked_pairs`: upper bound of the number of tracked pairs
+        ///
+        /// Emits `CustomPeriodAdded` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_custom_period(*tracked_pairs))]
+        pub fn add_custom_period(origin: OriginFor<T>, period: u32, tracked_pairs: u32) -> DispatchResultWithPostInfo {
+            T::UpdatePeriodsOrigin::ensure_origin(origin)?;
+
+            ensure!(period > 1, Error::<T>::InvalidPeriod);
+            ensure!(
+                !Self::tracked_periods().contains(&Custom(period)),
+                Error::<T>::PeriodAlreadyTracked
+            );
+
+            let last_block_oracles: Vec<_> = Oracles::<T>::iter()
+                .filter(|((_, _, p), _)| *p == LastBlock)
+                .map(|((src, assets, _), (entry, _))| (src, assets, entry))
+                .collect();
+            let pairs = last_block_oracles.len() as u32;
+            ensure!(pairs <= tracked_pairs, Error::<T>::TrackedPairsWitnessTooLow);
+
+            CustomPeriods::<T>::try_mutate(|periods| periods.try_push(period))
+                .map_err(|_| Error::<T>::TooManyCustomPeriods)?;
+
+            for (src, assets, entry) in last_block_oracles {
+                let initialized = entry.timestamp;
+                Oracles::<T>::insert((src, assets, Custom(period)), (entry, initialized));
+            }
+
+            Self::deposit_event(Event::CustomPeriodAdded { period });
+
+            Ok(Some(<T as Config>::WeightInfo::add_custom_period(pairs)).into())
+        }
+
+        /// Remove a custom period and all of its oracles.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `UpdatePeriodsOrigin`
+        /// - `period`: length of the period in blocks
+        /// - `tracked_pairs`: upper bound of the number of tracked pairs
+        ///
+        /// Emits `CustomPeriodRemoved` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_custom_period(*tracked_pairs))]
+        pub fn remove_custom_period(
+            origin: OriginFor<T>,
+            period: u32,
+            tracked_pairs: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdatePeriodsOrigin::ensure_origin(origin)?;
+
+            let index = Self::custom_periods()
+                .iter()
+                .position(|p| *p == period)
+                .ok_or(Error::<T>::CustomPeriodNotFound)?;
+
+            let keys: Vec<_> = Oracles::<T>::iter_keys()
+                .filter(|(_, _, p)| *p == Custom(period))
+                .collect();
+            let pairs = keys.len() as u32;
+            ensure!(pairs <= tracked_pairs, Error::<T>::TrackedPairsWitnessTooLow);
+
+            CustomPeriods::<T>::mutate(|periods| periods.remove(index));
+
+            for key in keys {
+                Oracles::<T>::remove(key);
+            }
+
+            Self::deposit_event(Event::CustomPeriodRemoved { period });
+
+            Ok(Some(<T as Config>::WeightInfo::remove_custom_period(pairs)).into())
+        }
+
+        /// Remove the oracles of all periods and the TWAP checkpoints of the given source and
+        /// assets, e.g. after an asset was delisted.
+        ///
+        /// Oracles are created again on new activity of the pair.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `RemoveOracleOrigin`
+        /// - `source`: source of the oracles
+        /// - `asset_a`: asset id of the first asset of the pair
+        /// - `asset_b`: asset id of the second asset of the pair
+        ///
+        /// Emits `OracleRemoved` event when successful.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_oracle())]
+        pub fn remove_oracle(
+            origin: OriginFor<T>,
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+        ) -> DispatchResult {
+            T::RemoveOracleOrigin::ensure_origin(origin)?;
+
+            let assets = ordered_pair(asset_a, asset_b);
+            ensure!(
+                Oracles::<T>::contains_key((source, assets, LastBlock)),
+                Error::<T>::OracleNotFound
+            );
+
+            Self::do_remove_oracle(source, assets);
+
+            Ok(())
+        }
+
+        /// Allow `who` to submit prices of the external source.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `ManageFeedersOrigin`
+        /// - `who`: account of the feeder
+        ///
+        /// Emits `FeederAdded` event when successful.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_feeder())]
+        pub fn add_feeder(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::ManageFeedersOrigin::ensure_origin(origin)?;
+
+            Feeders::<T>::try_mutate(|feeders| -> DispatchResult {
+                ensure!(!feeders.contains(&who), Error::<T>::FeederAlreadyAdded);
+                feeders
+                    .try_push(who.clone())
+                    .map_err(|_| Error::<T>::TooManyFeeders.into())
+            })?;
+
+            Self::deposit_event(Event::FeederAdded { who });
+
+            Ok(())
+        }
+
+        /// Revoke the permission of `who` to submit prices of the external source.
+        ///
+        /// Prices submitted by `who` in the current block are still taken into account.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `ManageFeedersOrigin`
+        /// - `who`: account of the feeder
+        ///
+        /// Emits `FeederRemoved` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_feeder())]
+        pub fn remove_feeder(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::ManageFeedersOrigin::ensure_origin(origin)?;
+
+            Feeders::<T>::try_mutate(|feeders| -> DispatchResult {
+                let index = feeders
+                    .iter()
+                    .position(|feeder| *feeder == who)
+                    .ok_or(Error::<T>::NotFeeder)?;
+                feeders.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::FeederRemoved { who });
+
+            Ok(())
+        }
+
+        /// Submit the price and liquidity of an asset pair as the external source.
+        ///
+        /// The median of the prices submitted for the pair in a block enters the accumulator,
+        /// together with the liquidity of the median submission. Submissions containing zeros or
+        /// deviating more than `MaxExternalPriceDeviation` from the `LastBlock` oracle of the
+        /// external source are rejected.
+        ///
+        /// Parameters:
+        /// - `origin`: must be a feeder
+        /// - `asset_a`: asset id of the first asset of the pair
+        /// - `asset_b`: asset id of the second asset of the pair
+        /// - `price`: price of `asset_b` denominated in `asset_a`
+        /// - `liquidity`: liquidity of `asset_a` and `asset_b`
+        ///
+        /// Emits `ExternalPriceSubmitted` event when the price was accepted and
+        /// `ExternalPriceRejected` event when it was rejected. The values in the events are adjusted
+        /// to the ordered pair.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_price()
+            .saturating_add(fractional_on_finalize_weight::<T>(T::MaxUniqueEntries::get())))]
+        pub fn submit_price(
+            origin: OriginFor<T>,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            price: Price,
+            liquidity: Liquidity<Balance>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Self::feeders().contains(&who), Error::<T>::NotFeeder);
+            ensure!(asset_a != asset_b, Error::<T>::SameAssets);
+
+            let assets = ordered_pair(asset_a, asset_b);
+            let mut submissions = Self::external_submissions(assets);
+            ensure!(
+                !submissions.iter().any(|(feeder, _, _)| *feeder == who),
+                Error::<T>::AlreadySubmitted
+            );
+
+            let (price, liquidity) = if assets == (asset_a, asset_b) {
+                (price, liquidity)
+            } else {
+                let (n, d): (Balance, Balance) = price.into();
+                (Price::new(d, n), liquidity.inverted())
+            };
+
+            if let Some(reason) = Self::validate_external_price(assets, price, liquidity) {
+                Self::deposit_event(Event::ExternalPriceRejected {
+                    feeder: who,
+                    assets,
+                    price,
+                    liquidity,
+                    reason,
+                });
+                return Ok(());
+            }
+
+            // there is at most one submission per feeder
+            submissions
+                .try_push((who.clone(), price, liquidity))
+                .map_err(|_| Error::<T>::TooManyFeeders)?;
+
+            // the submissions contain at least the current one
+            let (median_price, median_liquidity) = Self::median_submission(&submissions).unwrap_or((price, liquidity));
+            let entry = OracleEntry {
+                price: median_price,
+                volume: Volume::default(),
+                liquidity: median_liquidity,
+                volatility: FixedU128::zero(),
+                timestamp: T::BlockNumberProvider::current_block_number(),
+            };
+            Self::on_entry(EXTERNAL_SOURCE, assets, entry).map_err(|_| Error::<T>::TooManyUniqueEntries)?;
+
+            ExternalSubmissions::<T>::insert(assets, submissions);
+
+            Self::deposit_event(Event::ExternalPriceSubmitted {
+                feeder: who,
+                assets,
+                price,
+                liquidity,
+            });
+
+            Ok(())
+        }
+
+        /// Set the intermediate assets used to derive the cross-rate price of an asset pair, instead
+        /// of searching for a path among the tracked pairs. An empty path removes the configured path.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `UpdateCrossRatePathsOrigin`
+        /// - `source`: source of the oracles on the path
+        /// - `asset_a`: asset id of the first asset of the pair
+        /// - `asset_b`: asset id of the second asset of the pair
+        /// - `path`: intermediate assets ordered from `asset_a` to `asset_b`
+        ///
+        /// Emits `CrossRatePathSet` or `CrossRatePathRemoved` event when successful.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_cross_rate_path())]
+        pub fn set_cross_rate_path(
+            origin: OriginFor<T>,
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            path: BoundedVec<AssetId, T::MaxCrossRateHops>,
+        ) -> DispatchResult {
+            T::UpdateCrossRatePathsOrigin::ensure_origin(origin)?;
+
+            ensure!(asset_a != asset_b, Error::<T>::SameAssets);
+
+            let assets = ordered_pair(asset_a, asset_b);
+            if path.is_empty() {
+                CrossRatePaths::<T>::remove((source, assets));
+                Self::deposit_event(Event::CrossRatePathRemoved { source, assets });
+                return Ok(());
+            }
+
+            let mut visited = vec![asset_a, asset_b];
+            for asset in path.iter() {
+                ensure!(!visited.contains(asset), Error::<T>::InvalidPath);
+                visited.push(*asset);
+            }
+
+            let path: BoundedVec<AssetId, T::MaxCrossRateHops> = if assets == (asset_a, asset_b) {
+                path
+            } else {
+                // reversing keeps the length within bounds
+                path.into_iter()
+                    .rev()
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .map_err(|_| Error::<T>::InvalidPath)?
+            };
+            CrossRatePaths::<T>::insert((source, assets), path.clone());
+
+            Self::deposit_event(Event::CrossRatePathSet {
+                source,
+                assets,
+                path: path.into_inner(),
+            });
+
+            Ok(())
+        }
+    }
 }
 
 impl<T: Config> Pallet<T> {
     /// Insert or update data in the accumulator from received entry. Aggregates volume and
     /// takes the most recent data for the rest.
+    ///
+    /// Entries of pairs already in the accumulator are always updated. A new pair is rejected once
+    /// the accumulator holds `MaxUniqueEntries` entries, so its oracles miss the block.
+    ///
+    /// The volatility of the entry is set to the squared return of its price against the price of
+    /// the last block with activity.
     pub(crate) fn on_entry(
         src: Source,
         assets: (AssetId, AssetId),
-        oracle_entry: OracleEntry<T::BlockNumber>,
+        mut oracle_entry: OracleEntry<T::BlockNumber>,
     ) -> Result<(), ()> {
-        Accumulator::<T>::mutate(|accumulator| {
-            if let Some(entry) = accumulator.get_mut(&(src, assets)) {
+        oracle_entry.volatility = Self::oracle((src, assets, LastBlock))
+            .map(|(last_block, _)| squared_return(last_block.price, oracle_entry.price))
+            .unwrap_or_else(FixedU128::zero);
+        Accumulator::<T>::try_mutate((src, assets), |maybe_entry| match maybe_entry {
+            Some(entry) => {
                 entry.accumulate_volume_and_update_from(&oracle_entry);
                 Ok(())
-            } else {
-                accumulator
-                    .try_insert((src, assets), oracle_entry)
-                    .map(|_| ())
-                    .map_err(|_| ())
+            }
+            None => {
+                AccumulatorCount::<T>::try_mutate(|count| {
+                    if *count >= T::MaxUniqueEntries::get() {
+                        log::warn!(
+                            target: LOG_TARGET,
+                            "Accumulator is full, dropping entry of ({src:?}, {assets:?})."
+                        );
+                        return Err(());
+                    }
+                    *count = count.saturating_add(1);
+                    Ok(())
+                })?;
+                *maybe_entry = Some(oracle_entry);
+                Ok(())
             }
         })
     }
@@ -281,31 +858,180 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    /// The supported periods followed by the custom periods.
+    pub fn tracked_periods() -> Vec<OraclePeriod> {
+        T::SupportedPeriods::get()
+            .into_iter()
+            .chain(Self::custom_periods().into_iter().map(Custom))
+            .collect()
+    }
+
+    /// Return the reason to reject the external price of the ordered `assets`, if any.
+    fn validate_external_price(
+        assets: (AssetId, AssetId),
+        price: Price,
+        liquidity: Liquidity<Balance>,
+    ) -> Option<ExternalPriceRejection> {
+        let (n, d): (Balance, Balance) = price.into();
+        if n.is_zero() || d.is_zero() || liquidity.a.is_zero() || liquidity.b.is_zero() {
+            return Some(ExternalPriceRejection::ZeroValue);
+        }
+
+        let parent = T::BlockNumberProvider::current_block_number().saturating_sub(One::one());
+        match Self::last_block_oracle(EXTERNAL_SOURCE, assets, parent) {
+            Some((last_block, _))
+                if !last_block.price.is_zero()
+                    && exceeds_deviation(price, last_block.price, T::MaxExternalPriceDeviation::get()) =>
+            {
+                Some(ExternalPriceRejection::Outlier)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the median price of the submissions and the liquidity submitted with it. For an even
+    /// number of submissions the lower median is returned.
+    fn median_submission(
+        submissions: &[(T::AccountId, Price, Liquidity<Balance>)],
+    ) -> Option<(Price, Liquidity<Balance>)> {
+        let mut sorted: Vec<(Price, Liquidity<Balance>)> = submissions
+            .iter()
+            .map(|(_, price, liquidity)| (*price, *liquidity))
+            .collect();
+        sorted.sort_by(|(a, _), (b, _)| compare_prices(*a, *b));
+        sorted.get(sorted.len().saturating_sub(1) / 2).copied()
+    }
+
+    /// Remove all oracles and TWAP checkpoints of the given source and assets.
+    fn do_remove_oracle(source: Source, assets: (AssetId, AssetId)) {
+        // also removes oracles of periods that are not tracked anymore
+        let limit = MAX_PERIODS.saturating_add(T::MaxCustomPeriods::get());
+        let _ = Oracles::<T>::clear_prefix((source, assets), limit, None);
+        TwapCheckpoints::<T>::remove((source, assets));
+
+        Self::deposit_event(Event::OracleRemoved { source, assets });
+    }
+
+    /// Remove the oracles of pairs without activity for more than `OracleInactivityThreshold`
+    /// blocks, using at most `remaining_weight`.
+    ///
+    /// The oracles are checked in storage order, continuing after the last checked oracle in the
+    /// next block. Pairs with activity in the current block are kept.
+    fn remove_inactive_oracles(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        let threshold = T::OracleInactivityThreshold::get();
+        // reading and writing the cursor
+        let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+        if threshold.is_zero() || !remaining_weight.all_gte(used_weight) {
+            return Weight::zero();
+        }
+
+        let check_weight = T::DbWeight::get().reads(1);
+        // checking the accumulator and removing the oracles
+        let removal_weight = T::WeightInfo::remove_oracle().saturating_add(T::DbWeight::get().reads(1));
+        // an oracle is checked only if it can also be removed in case it is inactive
+        let max_oracle_weight = check_weight.saturating_add(removal_weight);
+
+        let oracles = match Self::pruning_cursor() {
+            Some(cursor) => Oracles::<T>::iter_from(Oracles::<T>::hashed_key_for(cursor)),
+            None => Oracles::<T>::iter(),
+        };
+
+        let mut cursor = None;
+        let mut finished = true;
+        for ((source, assets, period), (entry, _)) in oracles {
+            if !remaining_weight.all_gte(used_weight.saturating_add(max_oracle_weight)) {
+                finished = false;
+                break;
+            }
+            used_weight.saturating_accrue(check_weight);
+            cursor = Some((source, assets, period));
+
+            // every tracked pair has a `LastBlock` oracle holding the block of its last activity
+            if period == LastBlock && now.saturating_sub(entry.timestamp) > threshold {
+                used_weight.saturating_accrue(removal_weight);
+                if !Accumulator::<T>::contains_key((source, assets)) {
+                    Self::do_remove_oracle(source, assets);
+                }
+            }
+        }
+
+        if finished {
+            // all oracles have been checked, the next check starts from the beginning
+            PruningCursor::<T>::kill();
+        } else if cursor.is_some() {
+            PruningCursor::<T>::set(cursor);
+        }
+
+        used_weight
+    }
+
     /// Update oracles based on data accumulated during the block.
     fn update_oracles_from_accumulator() {
-        for ((src, assets), oracle_entry) in Accumulator::<T>::take().into_iter() {
+        let periods = Self::tracked_periods();
+        AccumulatorCount::<T>::kill();
+        for ((src, assets), oracle_entry) in Accumulator::<T>::drain() {
             // First we update the non-immediate oracles with the value of the `LastBlock` oracle.
-            for period in T::SupportedPeriods::get().into_iter().filter(|p| *p != LastBlock) {
+            for period in periods.iter().copied().filter(|p| *p != LastBlock) {
                 Self::update_oracle(src, assets, period, oracle_entry.clone());
             }
             // As we use (the old value of) the `LastBlock` entry to update the other oracles it
             // gets updated last.
-            Self::update_oracle(src, assets, LastBlock, oracle_entry.clone());
+            if Self::update_oracle(src, assets, LastBlock, oracle_entry.clone()) {
+                Self::deposit_event(Event::OracleInitialized { source: src, assets });
+            }
+            Self::update_twap(src, assets, &oracle_entry);
+            if T::EmitOracleUpdatedEvents::get() {
+                Self::deposit_event(Event::OracleUpdated {
+                    source: src,
+                    assets,
+                    price: oracle_entry.price,
+                    volume: oracle_entry.volume,
+                    liquidity: oracle_entry.liquidity,
+                });
+            }
         }
     }
 
+    /// Add a cumulative price checkpoint of the given source and assets with the price of
+    /// `oracle_entry`, dropping the oldest checkpoint if the ring is full.
+    fn update_twap(src: Source, assets: (AssetId, AssetId), oracle_entry: &OracleEntry<T::BlockNumber>) {
+        TwapCheckpoints::<T>::mutate((src, assets), |checkpoints| {
+            let checkpoint = match checkpoints.last() {
+                Some(last) => match last.next(oracle_entry.price, oracle_entry.timestamp) {
+                    Some(checkpoint) => checkpoint,
+                    None => {
+                        log::warn!(
+                            target: LOG_TARGET,
+                            "Updating TWAP accumulator ({src:?}, {assets:?}) failed. Keeping previous value."
+                        );
+                        debug_assert!(false, "Updating TWAP accumulator should not fail.");
+                        return;
+                    }
+                },
+                None => TwapCheckpoint::new(oracle_entry.price, oracle_entry.timestamp),
+            };
+            if checkpoints.len() >= T::MaxTwapCheckpoints::get() as usize {
+                checkpoints.remove(0);
+            }
+            // cannot fail, there is space for at least one checkpoint
+            let _ = checkpoints.try_push(checkpoint);
+        });
+    }
+
     /// Update the oracle of the given source, assets and period with `oracle_entry`.
+    ///
+    /// Returns `true` if the oracle did not exist and was initialized with `oracle_entry`.
     fn update_oracle(
         src: Source,
         assets: (AssetId, AssetId),
         period: OraclePeriod,
         incoming_entry: OracleEntry<T::BlockNumber>,
-    ) {
+    ) -> bool {
         Oracles::<T>::mutate((src, assets, period), |oracle| {
             // initialize the oracle entry if it doesn't exist
             if oracle.is_none() {
                 *oracle = Some((incoming_entry.clone(), T::BlockNumberProvider::current_block_number()));
-                return;
+                return true;
             }
             if let Some((prev_entry, _)) = oracle.as_mut() {
                 let parent = T::BlockNumberProvider::current_block_number().saturating_sub(One::one());
@@ -333,7 +1059,8 @@ impl<T: Config> Pallet<T> {
                         debug_assert!(false, "Updating to new value should not fail.");
                 });
             };
-        });
+            false
+        })
     }
 
     /// Return the updated oracle entry for the given source, assets and period.
@@ -361,6 +1088,273 @@ impl<T: Config> Pallet<T> {
         }
         .map(|return_entry| (return_entry, init))
     }
+
+    /// Return the updated entries of all tracked periods for the given source and assets.
+    ///
+    /// The entries are adjusted to the order of the assets like in `get_entry`. Periods without an
+    /// oracle are skipped.
+    pub fn get_entries(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+    ) -> Vec<(OraclePeriod, AggregatedEntry<Balance, T::BlockNumber, Price>)> {
+        Self::tracked_periods()
+            .into_iter()
+            .filter_map(|period| {
+                Self::get_entry(asset_a, asset_b, period, source)
+                    .ok()
+                    .map(|entry| (period, entry))
+            })
+            .collect()
+    }
+
+    /// Return the time-weighted average price of the given assets over the last `window` blocks,
+    /// excluding the current block. The price is adjusted to the order of the assets like in
+    /// `get_entry`.
+    ///
+    /// Fails if the oldest kept checkpoint is more recent than the start of the window.
+    pub fn get_twap(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        source: Source,
+        window: T::BlockNumber,
+    ) -> Result<Price, OracleError> {
+        if asset_a == asset_b {
+            return Err(OracleError::SameAsset);
+        }
+        if window.is_zero() {
+            return Err(OracleError::ZeroWindow);
+        }
+
+        let now = T::BlockNumberProvider::current_block_number();
+        let start = now.checked_sub(&window).ok_or(OracleError::InsufficientHistory)?;
+
+        let checkpoints = Self::twap_checkpoints((source, ordered_pair(asset_a, asset_b)));
+        let latest = checkpoints.last().ok_or(OracleError::NotPresent)?;
+        let past = checkpoints
+            .iter()
+            .rev()
+            .find(|checkpoint| checkpoint.timestamp <= start)
+            .ok_or(OracleError::InsufficientHistory)?;
+
+        let (price_now, inverted_now) = latest.cumulative_at(now).ok_or(OracleError::InsufficientHistory)?;
+        let (price_start, inverted_start) = past.cumulative_at(start).ok_or(OracleError::InsufficientHistory)?;
+        let (cumulative_now, cumulative_start) = if (asset_a, asset_b) == ordered_pair(asset_a, asset_b) {
+            (price_now, price_start)
+        } else {
+            (inverted_now, inverted_start)
+        };
+
+        let blocks = U256::from(window.saturated_into::<u64>());
+        let twap = cumulative_now.saturating_sub(cumulative_start) / blocks;
+        let twap = u128::try_from(twap).map_err(|_| OracleError::Overflow)?;
+
+        Ok(Price::new(twap, FixedU128::DIV))
+    }
+
+    /// Return the entry of the given assets, derived through intermediate assets if the pair is not
+    /// tracked directly.
+    ///
+    /// The configured path of the pair is used if there is one, otherwise the path with the fewest
+    /// intermediate assets (at most `MaxCrossRateHops`) among the tracked pairs of `source` is
+    /// searched for. Note: The search iterates over all oracles and is unbounded, it is meant for the
+    /// runtime API. Use `get_configured_cross_rate_entry` on-chain.
+    pub fn get_cross_rate_entry(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<AggregatedEntry<Balance, T::BlockNumber, Price>, OracleError> {
+        Self::cross_rate_entry(asset_a, asset_b, period, source, true)
+    }
+
+    /// Return the entry of the given assets, derived through the configured path of intermediate
+    /// assets if the pair is not tracked directly. Pairs without a configured path are not derived.
+    ///
+    /// Reads the direct oracle, the configured path and at most `MaxCrossRateHops + 1` oracles along
+    /// the path, so it can be used on-chain.
+    pub fn get_configured_cross_rate_entry(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<AggregatedEntry<Balance, T::BlockNumber, Price>, OracleError> {
+        Self::cross_rate_entry(asset_a, asset_b, period, source, false)
+    }
+
+    /// Return the direct entry of the given assets, or the entry derived through the configured path
+    /// of the pair or, if `search_path` is set, through the path found by `find_path`.
+    fn cross_rate_entry(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+        search_path: bool,
+    ) -> Result<AggregatedEntry<Balance, T::BlockNumber, Price>, OracleError> {
+        if asset_a == asset_b {
+            return Err(OracleError::SameAsset);
+        }
+        if let Ok(entry) = Self::get_entry(asset_a, asset_b, period, source) {
+            return Ok(entry);
+        }
+        let path = match Self::configured_cross_rate_path(source, asset_a, asset_b) {
+            Some(path) => path,
+            None if search_path => Self::find_path(source, asset_a, asset_b).ok_or(OracleError::NotPresent)?,
+            None => return Err(OracleError::NotPresent),
+        };
+        Self::get_entry_via(asset_a, asset_b, period, source, &path)
+    }
+
+    /// Return the entry of the given assets derived from the oracles of the pairs along `path`, the
+    /// intermediate assets ordered from `asset_a` to `asset_b`.
+    ///
+    /// The price is the product of the prices along the path. The oracle age is the oldest age on
+    /// the path. The liquidity of each asset is the smallest liquidity on the path, converted into
+    /// `asset_a` and `asset_b` respectively. Cross-rate entries carry no volume.
+    pub fn get_entry_via(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+        path: &[AssetId],
+    ) -> Result<AggregatedEntry<Balance, T::BlockNumber, Price>, OracleError> {
+        let assets: Vec<AssetId> = sp_std::iter::once(asset_a)
+            .chain(path.iter().copied())
+            .chain(sp_std::iter::once(asset_b))
+            .collect();
+        let entries = assets
+            .windows(2)
+            .map(|pair| Self::get_entry(pair[0], pair[1], period, source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let one = Price::new(1, 1);
+        // `prefix[k]`: price of the first asset of hop `k` in `asset_a`
+        let prefix: Vec<Price> = entries
+            .iter()
+            .scan(one, |price, entry| {
+                let current = *price;
+                *price = multiply_prices(current, entry.price);
+                Some(current)
+            })
+            .collect();
+        // `suffix[k]`: price of `asset_b` in the second asset of hop `k`
+        let mut suffix: Vec<Price> = entries
+            .iter()
+            .rev()
+            .scan(one, |price, entry| {
+                let current = *price;
+                *price = multiply_prices(entry.price, current);
+                Some(current)
+            })
+            .collect();
+        suffix.reverse();
+
+        let price = prefix
+            .last()
+            .zip(entries.last())
+            .map(|(prefix, entry)| multiply_prices(*prefix, entry.price))
+            .ok_or(OracleError::NotPresent)?;
+        let liquidity = entries
+            .iter()
+            .zip(prefix.iter().zip(suffix.iter()))
+            .map(|(entry, (prefix, suffix))| {
+                Liquidity::new(
+                    convert_by_price(entry.liquidity.a, *prefix),
+                    convert_by_price(entry.liquidity.b, inverted_price(*suffix)),
+                )
+            })
+            .fold(Liquidity::new(Balance::MAX, Balance::MAX), |min, liquidity| {
+                Liquidity::new(min.a.min(liquidity.a), min.b.min(liquidity.b))
+            });
+        let oracle_age = entries
+            .iter()
+            .map(|entry| entry.oracle_age)
+            .max()
+            .unwrap_or_else(Zero::zero);
+
+        Ok(AggregatedEntry {
+            price,
+            volume: Volume::default(),
+            liquidity,
+            oracle_age,
+        })
+    }
+
+    /// Return the configured path of the given assets, ordered from `asset_a` to `asset_b`.
+    fn configured_cross_rate_path(source: Source, asset_a: AssetId, asset_b: AssetId) -> Option<Vec<AssetId>> {
+        let assets = ordered_pair(asset_a, asset_b);
+        Self::cross_rate_paths((source, assets)).map(|path| {
+            let mut path = path.into_inner();
+            if assets != (asset_a, asset_b) {
+                path.reverse();
+            }
+            path
+        })
+    }
+
+    /// Breadth-first search for the path with the fewest intermediate assets (at most
+    /// `MaxCrossRateHops`) between the given assets among the tracked pairs of `source`.
+    fn find_path(source: Source, asset_a: AssetId, asset_b: AssetId) -> Option<Vec<AssetId>> {
+        // every tracked pair has a `LastBlock` oracle
+        let mut pairs: Vec<(AssetId, AssetId)> = Oracles::<T>::iter_keys()
+            .filter(|(src, _, period)| *src == source && *period == LastBlock)
+            .map(|(_, assets, _)| assets)
+            .collect();
+        // sort to make the result independent of the storage order
+        pairs.sort();
+
+        // the asset through which each visited asset was reached first
+        let mut reached_from: BTreeMap<AssetId, AssetId> = BTreeMap::new();
+        let mut frontier = vec![asset_a];
+        // a path with `n` intermediate assets consists of `n + 1` pairs
+        for _ in 0..=T::MaxCrossRateHops::get() {
+            let mut next = Vec::new();
+            for asset in frontier {
+                let neighbours = pairs.iter().filter_map(|&(x, y)| {
+                    if x == asset {
+                        Some(y)
+                    } else if y == asset {
+                        Some(x)
+                    } else {
+                        None
+                    }
+                });
+                for neighbour in neighbours {
+                    if neighbour == asset_a || reached_from.contains_key(&neighbour) {
+                        continue;
+                    }
+                    reached_from.insert(neighbour, asset);
+                    if neighbour == asset_b {
+                        let mut path = Vec::new();
+                        let mut current = asset;
+                        while current != asset_a {
+                            path.push(current);
+                            current = *reached_from.get(&current)?;
+                        }
+                        path.reverse();
+                        return Some(path);
+                    }
+                    next.push(neighbour);
+                }
+            }
+            frontier = next;
+        }
+        None
+    }
+
+    /// Return a page of the tracked combinations of source and (ordered) asset pair, sorted by
+    /// source and assets.
+    pub fn oracle_keys(offset: u32, limit: u32) -> Vec<(Source, (AssetId, AssetId))> {
+        // every tracked pair has a `LastBlock` oracle, as it is always updated
+        let mut keys: Vec<(Source, (AssetId, AssetId))> = Oracles::<T>::iter_keys()
+            .filter(|(_, _, period)| *period == LastBlock)
+            .map(|(src, assets, _)| (src, assets))
+            .collect();
+
+        keys.sort();
+
+        keys.into_iter().skip(offset as usize).take(limit as usize).collect()
+    }
 }
 
 /// A callback handler for trading and liquidity activity that schedules oracle updates.
@@ -371,14 +1365,22 @@ impl<T: Config> OnCreatePoolHandler<AssetId> for OnActivityHandler<T> {
     fn on_create_pool(_asset_a: AssetId, _asset_b: AssetId) -> DispatchResult {
         Ok(())
     }
+
+    fn on_create_pool_weight() -> Weight {
+        Weight::zero()
+    }
 }
 
 /// Calculate the weight contribution of one `on_trade`/`on_liquidity_changed` call towards
 /// `on_finalize`.
+///
+/// The `on_finalize_multiple_tokens` benchmark starts with empty checkpoint rings, so updating a
+/// full ring of the pair is charged on top.
 pub(crate) fn fractional_on_finalize_weight<T: Config>(max_entries: u32) -> Weight {
     T::WeightInfo::on_finalize_multiple_tokens(max_entries)
         .saturating_sub(T::WeightInfo::on_finalize_no_entry())
         .saturating_div(max_entries.into())
+        .saturating_add(T::WeightInfo::update_twap_checkpoints())
 }
 
 impl<T: Config> OnTradeHandler<AssetId, Balance> for OnActivityHandler<T> {
@@ -405,6 +1407,7 @@ impl<T: Config> OnTradeHandler<AssetId, Balance> for OnActivityHandler<T> {
             price,
             volume,
             liquidity,
+            volatility: FixedU128::zero(),
             timestamp,
         };
         Pallet::<T>::on_trade(source, ordered_pair(asset_a, asset_b), entry)
@@ -446,6 +1449,7 @@ impl<T: Config> OnLiquidityChangedHandler<AssetId, Balance> for OnActivityHandle
             // liquidity provision does not count as trade volume
             volume: Volume::default(),
             liquidity,
+            volatility: FixedU128::zero(),
             timestamp,
         };
         Pallet::<T>::on_liquidity_changed(source, ordered_pair(asset_a, asset_b), entry)
@@ -518,6 +1522,12 @@ pub enum OracleError {
     NotPresent,
     /// The oracle is not defined if the asset ids are the same.
     SameAsset,
+    /// The time-weighted average price is not defined over zero blocks.
+    ZeroWindow,
+    /// Not enough price history is kept to cover the requested window.
+    InsufficientHistory,
+    /// The time-weighted average price does not fit into a price.
+    Overflow,
 }
 
 impl<T: Config> AggregatedOracle<AssetId, Balance, T::BlockNumber, Price> for Pallet<T> {
@@ -571,3 +1581,158 @@ impl<T: Config> AggregatedPriceOracle<AssetId, T::BlockNumber, Price> for Pallet
         Self::get_entry_weight()
     }
 }
+
+impl<T: Config> TwapOracle<AssetId, T::BlockNumber, Price> for Pallet<T> {
+    type Error = OracleError;
+
+    /// Returns the time-weighted average price of `asset_a` and `asset_b` over the last `window`
+    /// blocks. See [`Pallet::get_twap`].
+    fn get_twap(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        source: Source,
+        window: T::BlockNumber,
+    ) -> Result<Price, Self::Error> {
+        Pallet::<T>::get_twap(asset_a, asset_b, source, window)
+    }
+
+    fn get_twap_weight() -> Weight {
+        T::WeightInfo::get_twap()
+    }
+}
+
+impl<T: Config> OracleLastUpdate<AssetId, T::BlockNumber> for Pallet<T> {
+    /// Returns the block of the last activity of the given assets, i.e. the timestamp of their
+    /// `LastBlock` oracle.
+    fn last_update(asset_a: AssetId, asset_b: AssetId, source: Source) -> Option<T::BlockNumber> {
+        Self::oracle((source, ordered_pair(asset_a, asset_b), LastBlock)).map(|(entry, _)| entry.timestamp)
+    }
+
+    fn last_update_weight() -> Weight {
+        T::DbWeight::get().reads(1)
+    }
+}
+
+impl<T: Config> VolatilityOracle<AssetId, T::BlockNumber, FixedU128> for Pallet<T> {
+    type Error = OracleError;
+
+    /// Returns the volatility of the price of the given assets and period together with the age of
+    /// the oracle. The volatility is updated to the state of the parent block like in `get_entry`
+    /// and does not depend on the order of the assets.
+    fn get_volatility(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<(FixedU128, T::BlockNumber), Self::Error> {
+        if asset_a == asset_b {
+            return Err(OracleError::SameAsset);
+        };
+        Self::get_updated_entry(source, ordered_pair(asset_a, asset_b), period)
+            .ok_or(OracleError::NotPresent)
+            .map(|(entry, initialized)| (entry.volatility, entry.timestamp.saturating_sub(initialized)))
+    }
+
+    fn get_volatility_weight() -> Weight {
+        T::WeightInfo::get_entry()
+    }
+}
+
+/// Price oracle of the asset pairs of the source `S`, deriving the prices of pairs that are not
+/// tracked directly through their configured intermediate assets. See
+/// [`Pallet::get_configured_cross_rate_entry`].
+pub struct CrossRatePriceOracle<T, S>(PhantomData<(T, S)>);
+
+impl<T: Config, S: Get<Source>> PriceOracle<AssetId> for CrossRatePriceOracle<T, S> {
+    type Price = Price;
+
+    fn price(asset_a: AssetId, asset_b: AssetId, period: OraclePeriod) -> Option<Self::Price> {
+        Pallet::<T>::get_configured_cross_rate_entry(asset_a, asset_b, period, S::get())
+            .ok()
+            .map(|entry| entry.price)
+    }
+}
+
+/// Adapter providing the prices of the `Period` oracles as `NativePriceOracle` and
+/// `SpotPriceProvider`, e.g. for pricing transaction fees.
+///
+/// The sources of `Sources` are tried in order and the price of the first source with an oracle of
+/// the pair that was updated at most `MaxOracleAge` blocks before the parent block is returned.
+/// Note that this is the age of the oracle data and not the `oracle_age` of the entry, which
+/// counts the blocks since the oracle was initialized.
+///
+/// Zero prices are treated as not available.
+pub struct OraclePriceProvider<T, NativeAssetId, Sources, Period, MaxOracleAge>(
+    PhantomData<(T, NativeAssetId, Sources, Period, MaxOracleAge)>,
+);
+
+impl<T, NativeAssetId, Sources, Period, MaxOracleAge>
+    OraclePriceProvider<T, NativeAssetId, Sources, Period, MaxOracleAge>
+where
+    T: Config,
+    Sources: Get<Vec<Source>>,
+    Period: Get<OraclePeriod>,
+    MaxOracleAge: Get<T::BlockNumber>,
+{
+    /// Return the price of `asset_b` denominated in `asset_a` of the first source with a recent
+    /// enough oracle.
+    fn recent_price(asset_a: AssetId, asset_b: AssetId) -> Option<FixedU128> {
+        let parent = T::BlockNumberProvider::current_block_number().saturating_sub(One::one());
+        Sources::get().into_iter().find_map(|source| {
+            let last_update = Pallet::<T>::last_update(asset_a, asset_b, source)?;
+            if parent.saturating_sub(last_update) > MaxOracleAge::get() {
+                return None;
+            }
+            let price = Pallet::<T>::get_entry(asset_a, asset_b, Period::get(), source)
+                .ok()?
+                .price;
+            if price.is_zero() {
+                return None;
+            }
+            let (n, d): (Balance, Balance) = price.into();
+            FixedU128::checked_from_rational(n, d)
+        })
+    }
+}
+
+impl<T, NativeAssetId, Sources, Period, MaxOracleAge> NativePriceOracle<AssetId, FixedU128>
+    for OraclePriceProvider<T, NativeAssetId, Sources, Period, MaxOracleAge>
+where
+    T: Config,
+    NativeAssetId: Get<AssetId>,
+    Sources: Get<Vec<Source>>,
+    Period: Get<OraclePeriod>,
+    MaxOracleAge: Get<T::BlockNumber>,
+{
+    /// Returns the price of the native asset denominated in `currency`.
+    fn price(currency: AssetId) -> Option<FixedU128> {
+        if currency == NativeAssetId::get() {
+            return Some(FixedU128::one());
+        }
+        Self::recent_price(currency, NativeAssetId::get())
+    }
+}
+
+impl<T, NativeAssetId, Sources, Period, MaxOracleAge> SpotPriceProvider<AssetId>
+    for OraclePriceProvider<T, NativeAssetId, Sources, Period, MaxOracleAge>
+where
+    T: Config,
+    Sources: Get<Vec<Source>>,
+    Period: Get<OraclePeriod>,
+    MaxOracleAge: Get<T::BlockNumber>,
+{
+    type Price = FixedU128;
+
+    fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+        let assets = ordered_pair(asset_a, asset_b);
+        asset_a != asset_b
+            && Sources::get()
+                .into_iter()
+                .any(|source| Oracles::<T>::contains_key((source, assets, Period::get())))
+    }
+
+    /// Returns the price of `asset_b` denominated in `asset_a`.
+    fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+        Self::recent_price(asset_a, asset_b)
+    }
+}
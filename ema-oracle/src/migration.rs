@@ -0,0 +1,91 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Balance, Config, OracleEntry, Oracles, Pallet, Price};
+use codec::{Decode, Encode};
+use frame_support::{
+    log,
+    traits::{Get, StorageVersion},
+    weights::Weight,
+};
+use hydradx_traits::{Liquidity, Volume};
+use sp_arithmetic::{traits::Saturating, FixedU128};
+
+/// Oracle entries are extended with the volatility of the price.
+pub mod v1 {
+    use super::*;
+    use sp_arithmetic::traits::Zero;
+
+    #[derive(Encode, Decode)]
+    pub struct OracleEntryV0<BlockNumber> {
+        pub price: Price,
+        pub volume: Volume<Balance>,
+        pub liquidity: Liquidity<Balance>,
+        pub timestamp: BlockNumber,
+    }
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::ema-oracle",
+            "EMA oracle migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::ema-oracle",
+            "Running migration to v1 for EMA oracle"
+        );
+
+        let mut translated: u64 = 0;
+        Oracles::<T>::translate(
+            |_, (old, initialized): (OracleEntryV0<T::BlockNumber>, T::BlockNumber)| {
+                translated.saturating_inc();
+                // there is no price history to derive the volatility from, so it starts at zero
+                Some((
+                    OracleEntry {
+                        price: old.price,
+                        volume: old.volume,
+                        liquidity: old.liquidity,
+                        volatility: FixedU128::zero(),
+                        timestamp: old.timestamp,
+                    },
+                    initialized,
+                ))
+            },
+        );
+
+        StorageVersion::new(1).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(translated, translated.saturating_add(1))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Unexpected storage version.");
+
+        for (_, (entry, _)) in Oracles::<T>::iter() {
+            assert!(entry.volatility.is_zero(), "Unexpected volatility.");
+        }
+
+        log::info!(
+            target: "runtime::ema-oracle",
+            "EMA oracle migration: POST checks successful!"
+        );
+    }
+}
@@ -0,0 +1,355 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::sp_runtime::DispatchError;
+use pretty_assertions::assert_eq;
+
+const USD: AssetId = 4_000;
+const ETH: AssetId = 5_000;
+const BTC: AssetId = 6_000;
+
+fn track(block: BlockNumber, pairs: &[(AssetId, AssetId, Balance, Balance)]) {
+    System::set_block_number(block);
+    for &(asset_a, asset_b, liquidity_a, liquidity_b) in pairs {
+        assert_ok!(OnActivityHandler::<Test>::on_liquidity_changed(
+            SOURCE,
+            asset_a,
+            asset_b,
+            1,
+            1,
+            liquidity_a,
+            liquidity_b
+        ));
+    }
+    EmaOracle::on_finalize(block);
+}
+
+fn path(assets: Vec<AssetId>) -> BoundedVec<AssetId, ConstU32<2>> {
+    assets.try_into().unwrap()
+}
+
+#[test]
+fn get_cross_rate_entry_should_derive_entry_through_intermediate_asset() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(1, &[(HDX, DOT, 2_000, 1_000), (DOT, ACA, 500, 5_000)]);
+        System::set_block_number(2);
+
+        // Act
+        let entry = EmaOracle::get_cross_rate_entry(HDX, ACA, LastBlock, SOURCE).unwrap();
+
+        // Assert
+        assert_eq!(
+            entry,
+            AggregatedEntry {
+                price: Price::new(2_000 * 500, 1_000 * 5_000),
+                volume: Volume::default(),
+                // HDX: min(2_000, 500 DOT = 1_000 HDX), ACA: min(1_000 DOT = 10_000 ACA, 5_000)
+                liquidity: Liquidity::new(1_000, 5_000),
+                oracle_age: 0,
+            }
+        );
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_adjust_entry_to_asset_order() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(1, &[(HDX, DOT, 2_000, 1_000), (DOT, ACA, 500, 5_000)]);
+        System::set_block_number(2);
+
+        // Act
+        let entry = EmaOracle::get_cross_rate_entry(ACA, HDX, LastBlock, SOURCE).unwrap();
+
+        // Assert
+        assert_eq!(entry.price, Price::new(5_000 * 1_000, 500 * 2_000));
+        assert_eq!(entry.liquidity, Liquidity::new(5_000, 1_000));
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_report_oldest_oracle_age() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(1, &[(HDX, DOT, 2_000, 1_000)]);
+        track(3, &[(DOT, ACA, 500, 5_000)]);
+        System::set_block_number(5);
+
+        // Act
+        let entry = EmaOracle::get_cross_rate_entry(HDX, ACA, TenMinutes, SOURCE).unwrap();
+
+        // Assert
+        let age_hdx_dot = EmaOracle::get_entry(HDX, DOT, TenMinutes, SOURCE).unwrap().oracle_age;
+        let age_dot_aca = EmaOracle::get_entry(DOT, ACA, TenMinutes, SOURCE).unwrap().oracle_age;
+        assert!(age_hdx_dot > age_dot_aca);
+        assert_eq!(entry.oracle_age, age_hdx_dot);
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_return_direct_entry_when_pair_is_tracked() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(
+            1,
+            &[
+                (HDX, DOT, 2_000, 1_000),
+                (DOT, ACA, 500, 5_000),
+                (HDX, ACA, 3_000, 1_000),
+            ],
+        );
+        System::set_block_number(2);
+
+        // Act & Assert
+        assert_eq!(
+            EmaOracle::get_cross_rate_entry(HDX, ACA, LastBlock, SOURCE),
+            EmaOracle::get_entry(HDX, ACA, LastBlock, SOURCE)
+        );
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_use_path_with_fewest_intermediate_assets() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(
+            1,
+            &[
+                (HDX, DOT, 2_000, 1_000),
+                (DOT, ACA, 1_000, 1_000),
+                (ACA, USD, 1_000, 1_000),
+                (HDX, ETH, 1_000, 1_000),
+                (ETH, USD, 1_000, 3_000),
+            ],
+        );
+        System::set_block_number(2);
+
+        // Act
+        let entry = EmaOracle::get_cross_rate_entry(HDX, USD, LastBlock, SOURCE).unwrap();
+
+        // Assert
+        assert_eq!(
+            entry,
+            EmaOracle::get_entry_via(HDX, USD, LastBlock, SOURCE, &[ETH]).unwrap()
+        );
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_fail_when_path_exceeds_max_hops() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(
+            1,
+            &[
+                (HDX, DOT, 1_000, 1_000),
+                (DOT, ACA, 1_000, 1_000),
+                (ACA, USD, 1_000, 1_000),
+                (USD, ETH, 1_000, 1_000),
+            ],
+        );
+        System::set_block_number(2);
+
+        // Act & Assert
+        assert!(EmaOracle::get_cross_rate_entry(HDX, USD, LastBlock, SOURCE).is_ok());
+        assert_eq!(
+            EmaOracle::get_cross_rate_entry(HDX, ETH, LastBlock, SOURCE),
+            Err(OracleError::NotPresent)
+        );
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_fail_when_assets_are_not_connected() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(1, &[(HDX, DOT, 1_000, 1_000), (ACA, USD, 1_000, 1_000)]);
+        System::set_block_number(2);
+
+        // Act & Assert
+        assert_eq!(
+            EmaOracle::get_cross_rate_entry(HDX, USD, LastBlock, SOURCE),
+            Err(OracleError::NotPresent)
+        );
+        assert_eq!(
+            EmaOracle::get_cross_rate_entry(HDX, HDX, LastBlock, SOURCE),
+            Err(OracleError::SameAsset)
+        );
+    });
+}
+
+#[test]
+fn get_cross_rate_entry_should_use_configured_path() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(
+            1,
+            &[
+                (HDX, DOT, 2_000, 1_000),
+                (DOT, USD, 1_000, 1_000),
+                (HDX, ETH, 1_000, 1_000),
+                (ETH, BTC, 1_000, 2_000),
+                (BTC, USD, 1_000, 3_000),
+            ],
+        );
+        System::set_block_number(2);
+
+        // Act
+        assert_ok!(EmaOracle::set_cross_rate_path(
+            RuntimeOrigin::root(),
+            SOURCE,
+            USD,
+            HDX,
+            path(vec![BTC, ETH])
+        ));
+
+        // Assert
+        assert_eq!(
+            EmaOracle::cross_rate_paths((SOURCE, (HDX, USD))).unwrap().into_inner(),
+            vec![ETH, BTC]
+        );
+        System::assert_last_event(TestEvent::EmaOracle(Event::CrossRatePathSet {
+            source: SOURCE,
+            assets: (HDX, USD),
+            path: vec![ETH, BTC],
+        }));
+        assert_eq!(
+            EmaOracle::get_cross_rate_entry(HDX, USD, LastBlock, SOURCE),
+            EmaOracle::get_entry_via(HDX, USD, LastBlock, SOURCE, &[ETH, BTC])
+        );
+        assert_eq!(
+            EmaOracle::get_cross_rate_entry(USD, HDX, LastBlock, SOURCE),
+            EmaOracle::get_entry_via(USD, HDX, LastBlock, SOURCE, &[BTC, ETH])
+        );
+    });
+}
+
+#[test]
+fn set_cross_rate_path_should_remove_path_when_empty() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        System::set_block_number(1);
+        assert_ok!(EmaOracle::set_cross_rate_path(
+            RuntimeOrigin::root(),
+            SOURCE,
+            HDX,
+            USD,
+            path(vec![DOT])
+        ));
+
+        // Act
+        assert_ok!(EmaOracle::set_cross_rate_path(
+            RuntimeOrigin::root(),
+            SOURCE,
+            HDX,
+            USD,
+            path(vec![])
+        ));
+
+        // Assert
+        assert_eq!(EmaOracle::cross_rate_paths((SOURCE, (HDX, USD))), None);
+        System::assert_last_event(TestEvent::EmaOracle(Event::CrossRatePathRemoved {
+            source: SOURCE,
+            assets: (HDX, USD),
+        }));
+    });
+}
+
+#[test]
+fn set_cross_rate_path_should_fail_when_path_is_invalid() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::set_cross_rate_path(RuntimeOrigin::root(), SOURCE, HDX, USD, path(vec![DOT, DOT])),
+            Error::<Test>::InvalidPath
+        );
+        assert_noop!(
+            EmaOracle::set_cross_rate_path(RuntimeOrigin::root(), SOURCE, HDX, USD, path(vec![USD])),
+            Error::<Test>::InvalidPath
+        );
+        assert_noop!(
+            EmaOracle::set_cross_rate_path(RuntimeOrigin::root(), SOURCE, HDX, HDX, path(vec![DOT])),
+            Error::<Test>::SameAssets
+        );
+    });
+}
+
+#[test]
+fn set_cross_rate_path_should_fail_when_origin_is_not_allowed() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::set_cross_rate_path(RuntimeOrigin::signed(1), SOURCE, HDX, USD, path(vec![DOT])),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+frame_support::parameter_types! {
+    pub const TestSource: Source = SOURCE;
+}
+
+#[test]
+fn cross_rate_price_oracle_should_return_price_derived_through_configured_path() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(1, &[(HDX, DOT, 2_000, 1_000), (DOT, ACA, 500, 5_000)]);
+        System::set_block_number(2);
+        assert_ok!(EmaOracle::set_cross_rate_path(
+            RuntimeOrigin::root(),
+            SOURCE,
+            HDX,
+            ACA,
+            path(vec![DOT])
+        ));
+
+        // Act & Assert
+        assert_eq!(
+            CrossRatePriceOracle::<Test, TestSource>::price(HDX, ACA, LastBlock),
+            Some(Price::new(2_000 * 500, 1_000 * 5_000))
+        );
+        assert_eq!(
+            CrossRatePriceOracle::<Test, TestSource>::price(HDX, DOT, LastBlock),
+            Some(Price::new(2_000, 1_000))
+        );
+        assert_eq!(
+            CrossRatePriceOracle::<Test, TestSource>::price(HDX, USD, LastBlock),
+            None
+        );
+    });
+}
+
+#[test]
+fn cross_rate_price_oracle_should_not_search_for_path_when_none_is_configured() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track(1, &[(HDX, DOT, 2_000, 1_000), (DOT, ACA, 500, 5_000)]);
+        System::set_block_number(2);
+
+        // Act & Assert
+        assert_eq!(
+            CrossRatePriceOracle::<Test, TestSource>::price(HDX, ACA, LastBlock),
+            None
+        );
+        assert_eq!(
+            EmaOracle::get_configured_cross_rate_entry(HDX, ACA, LastBlock, SOURCE),
+            Err(OracleError::NotPresent)
+        );
+        // the runtime API still searches for the path
+        assert!(EmaOracle::get_cross_rate_entry(HDX, ACA, LastBlock, SOURCE).is_ok());
+    });
+}
@@ -0,0 +1,213 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::sp_runtime::DispatchError;
+use pretty_assertions::assert_eq;
+
+const PERIOD: u32 = 30;
+
+/// Track the HDX/DOT pair by trading in block 1.
+fn track_hdx_dot() {
+    System::set_block_number(1);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+    ));
+    EmaOracle::on_finalize(1);
+}
+
+#[test]
+fn add_custom_period_should_initialize_oracles_of_tracked_pairs() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track_hdx_dot();
+        let last_block = Oracles::<Test>::get((SOURCE, ordered_pair(HDX, DOT), LastBlock)).unwrap();
+
+        // Act
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 1));
+
+        // Assert
+        assert_eq!(EmaOracle::custom_periods().into_inner(), vec![PERIOD]);
+        assert_eq!(
+            Oracles::<Test>::get((SOURCE, ordered_pair(HDX, DOT), Custom(PERIOD))),
+            Some(last_block)
+        );
+        System::assert_last_event(TestEvent::EmaOracle(Event::CustomPeriodAdded { period: PERIOD }));
+    });
+}
+
+#[test]
+fn custom_period_oracle_should_be_updated_with_its_smoothing_factor() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track_hdx_dot();
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 1));
+        let previous = get_oracle_entry(HDX, DOT, Custom(PERIOD)).unwrap();
+
+        // Act
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 400, 2_500, 1_000
+        ));
+        let incoming = get_accumulator_entry(SOURCE, (HDX, DOT)).unwrap();
+        EmaOracle::on_finalize(2);
+
+        // Assert
+        let expected = previous
+            .calculate_new_by_integrating_incoming(Custom(PERIOD), &incoming)
+            .unwrap();
+        assert_eq!(get_oracle_entry(HDX, DOT, Custom(PERIOD)), Some(expected.clone()));
+        assert_ne!(expected.price, incoming.price);
+
+        System::set_block_number(3);
+        assert_eq!(
+            EmaOracle::get_entry(HDX, DOT, Custom(PERIOD), SOURCE),
+            Ok(expected.into_aggregated(1))
+        );
+    });
+}
+
+#[test]
+fn custom_period_smoothing_should_be_derived_from_its_length() {
+    // ten minutes of 6 second blocks
+    assert_eq!(into_smoothing(Custom(100)), into_smoothing(TenMinutes));
+    assert_eq!(
+        into_smoothing(Custom(PERIOD)),
+        hydra_dx_math::ema::smoothing_from_period(PERIOD.into())
+    );
+}
+
+#[test]
+fn custom_period_should_be_included_in_entries() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track_hdx_dot();
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 1));
+        System::set_block_number(10);
+
+        // Act
+        let entries = EmaOracle::get_entries(SOURCE, HDX, DOT);
+
+        // Assert
+        assert_eq!(entries.len(), supported_periods().len() + 1);
+        assert_eq!(
+            entries.last(),
+            Some(&(
+                Custom(PERIOD),
+                EmaOracle::get_entry(HDX, DOT, Custom(PERIOD), SOURCE).unwrap()
+            ))
+        );
+    });
+}
+
+#[test]
+fn add_custom_period_should_fail_when_origin_is_not_update_periods_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_custom_period(RuntimeOrigin::signed(1), PERIOD, 0),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn add_custom_period_should_fail_when_period_is_too_short() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_custom_period(RuntimeOrigin::root(), 1, 0),
+            Error::<Test>::InvalidPeriod
+        );
+    });
+}
+
+#[test]
+fn add_custom_period_should_fail_when_period_is_already_tracked() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 0));
+        assert_noop!(
+            EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 0),
+            Error::<Test>::PeriodAlreadyTracked
+        );
+    });
+}
+
+#[test]
+fn add_custom_period_should_fail_when_max_custom_periods_is_reached() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), 10, 0));
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), 20, 0));
+        assert_noop!(
+            EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 0),
+            Error::<Test>::TooManyCustomPeriods
+        );
+    });
+}
+
+#[test]
+fn add_custom_period_should_fail_when_witness_is_too_low() {
+    new_test_ext().execute_with(|| {
+        track_hdx_dot();
+        assert_noop!(
+            EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 0),
+            Error::<Test>::TrackedPairsWitnessTooLow
+        );
+    });
+}
+
+#[test]
+fn remove_custom_period_should_remove_its_oracles() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        track_hdx_dot();
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 1));
+
+        // Act
+        assert_ok!(EmaOracle::remove_custom_period(RuntimeOrigin::root(), PERIOD, 1));
+
+        // Assert
+        assert!(EmaOracle::custom_periods().is_empty());
+        assert_eq!(get_oracle_entry(HDX, DOT, Custom(PERIOD)), None);
+        assert!(get_oracle_entry(HDX, DOT, LastBlock).is_some());
+        assert_eq!(
+            EmaOracle::get_entry(HDX, DOT, Custom(PERIOD), SOURCE),
+            Err(OracleError::NotPresent)
+        );
+        System::assert_last_event(TestEvent::EmaOracle(Event::CustomPeriodRemoved { period: PERIOD }));
+    });
+}
+
+#[test]
+fn remove_custom_period_should_fail_when_period_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::remove_custom_period(RuntimeOrigin::root(), PERIOD, 0),
+            Error::<Test>::CustomPeriodNotFound
+        );
+    });
+}
+
+#[test]
+fn remove_custom_period_should_fail_when_witness_is_too_low() {
+    new_test_ext().execute_with(|| {
+        track_hdx_dot();
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), PERIOD, 1));
+        assert_noop!(
+            EmaOracle::remove_custom_period(RuntimeOrigin::root(), PERIOD, 0),
+            Error::<Test>::TrackedPairsWitnessTooLow
+        );
+    });
+}
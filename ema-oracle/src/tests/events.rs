@@ -0,0 +1,134 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::EmitOracleUpdatedEvents;
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn trade(block: BlockNumber) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn oracle_events() -> Vec<Event<Test>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            TestEvent::EmaOracle(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+fn oracle_updated_event() -> Event<Test> {
+    Event::OracleUpdated {
+        source: SOURCE,
+        assets: (HDX, DOT),
+        price: Price::new(2_000, 1_000),
+        volume: Volume::from_a_in_b_out(1_000, 500),
+        liquidity: Liquidity::new(2_000, 1_000),
+    }
+}
+
+#[test]
+fn on_finalize_should_emit_oracle_initialized_and_updated_events_for_new_pair() {
+    new_test_ext().execute_with(|| {
+        // Act
+        trade(1);
+
+        // Assert
+        assert_eq!(
+            oracle_events(),
+            vec![
+                Event::OracleInitialized {
+                    source: SOURCE,
+                    assets: (HDX, DOT),
+                },
+                oracle_updated_event(),
+            ]
+        );
+    });
+}
+
+#[test]
+fn on_finalize_should_emit_only_oracle_updated_event_for_existing_pair() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1);
+        System::reset_events();
+
+        // Act
+        trade(2);
+
+        // Assert
+        assert_eq!(oracle_events(), vec![oracle_updated_event()]);
+    });
+}
+
+#[test]
+fn on_finalize_should_emit_one_oracle_updated_event_per_pair() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1);
+        System::reset_events();
+
+        // Act
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(2);
+
+        // Assert
+        assert_eq!(
+            oracle_events(),
+            vec![Event::OracleUpdated {
+                source: SOURCE,
+                assets: (HDX, DOT),
+                price: Price::new(2_000, 1_000),
+                volume: Volume::from_a_in_b_out(2_000, 1_000),
+                liquidity: Liquidity::new(2_000, 1_000),
+            }]
+        );
+    });
+}
+
+#[test]
+fn on_finalize_should_not_emit_oracle_updated_event_when_disabled() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        EmitOracleUpdatedEvents::set(false);
+
+        // Act
+        trade(1);
+
+        // Assert
+        assert_eq!(
+            oracle_events(),
+            vec![Event::OracleInitialized {
+                source: SOURCE,
+                assets: (HDX, DOT),
+            }]
+        );
+    });
+}
@@ -0,0 +1,390 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::sp_runtime::DispatchError;
+use pretty_assertions::assert_eq;
+
+const FEEDER_1: u64 = 1;
+const FEEDER_2: u64 = 2;
+const FEEDER_3: u64 = 3;
+const FEEDER_4: u64 = 4;
+
+const LIQUIDITY: Liquidity<Balance> = Liquidity::new(2_000, 1_000);
+
+fn with_feeders(feeders: &[u64]) -> sp_io::TestExternalities {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        for feeder in feeders {
+            assert_ok!(EmaOracle::add_feeder(RuntimeOrigin::root(), *feeder));
+        }
+    });
+    ext
+}
+
+fn external_accumulator_entry() -> Option<OracleEntry<BlockNumber>> {
+    get_accumulator_entry(EXTERNAL_SOURCE, (HDX, DOT))
+}
+
+#[test]
+fn add_feeder_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(EmaOracle::add_feeder(RuntimeOrigin::root(), FEEDER_1));
+
+        assert_eq!(EmaOracle::feeders().into_inner(), vec![FEEDER_1]);
+        System::assert_last_event(TestEvent::EmaOracle(Event::FeederAdded { who: FEEDER_1 }));
+    });
+}
+
+#[test]
+fn add_feeder_should_fail_when_origin_is_not_allowed() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_feeder(RuntimeOrigin::signed(FEEDER_1), FEEDER_1),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn add_feeder_should_fail_when_feeder_was_already_added() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_feeder(RuntimeOrigin::root(), FEEDER_1),
+            Error::<Test>::FeederAlreadyAdded
+        );
+    });
+}
+
+#[test]
+fn add_feeder_should_fail_when_max_feeders_is_reached() {
+    let max_feeders = <<Test as crate::Config>::MaxFeeders as Get<u32>>::get() as u64;
+    let feeders: Vec<u64> = (1..=max_feeders).collect();
+    with_feeders(&feeders).execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_feeder(RuntimeOrigin::root(), max_feeders + 1),
+            Error::<Test>::TooManyFeeders
+        );
+    });
+}
+
+#[test]
+fn remove_feeder_should_work() {
+    with_feeders(&[FEEDER_1, FEEDER_2]).execute_with(|| {
+        assert_ok!(EmaOracle::remove_feeder(RuntimeOrigin::root(), FEEDER_1));
+
+        assert_eq!(EmaOracle::feeders().into_inner(), vec![FEEDER_2]);
+        System::assert_last_event(TestEvent::EmaOracle(Event::FeederRemoved { who: FEEDER_1 }));
+        assert_noop!(
+            EmaOracle::submit_price(RuntimeOrigin::signed(FEEDER_1), HDX, DOT, Price::new(2, 1), LIQUIDITY),
+            Error::<Test>::NotFeeder
+        );
+    });
+}
+
+#[test]
+fn remove_feeder_should_fail_when_account_is_not_a_feeder() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        assert_noop!(
+            EmaOracle::remove_feeder(RuntimeOrigin::root(), FEEDER_2),
+            Error::<Test>::NotFeeder
+        );
+    });
+}
+
+#[test]
+fn submit_price_should_fail_when_account_is_not_a_feeder() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        assert_noop!(
+            EmaOracle::submit_price(RuntimeOrigin::signed(FEEDER_2), HDX, DOT, Price::new(2, 1), LIQUIDITY),
+            Error::<Test>::NotFeeder
+        );
+    });
+}
+
+#[test]
+fn submit_price_should_fail_when_assets_are_the_same() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        assert_noop!(
+            EmaOracle::submit_price(RuntimeOrigin::signed(FEEDER_1), HDX, HDX, Price::new(1, 1), LIQUIDITY),
+            Error::<Test>::SameAssets
+        );
+    });
+}
+
+#[test]
+fn submit_price_should_fail_when_feeder_already_submitted_in_block() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            HDX,
+            DOT,
+            Price::new(2, 1),
+            LIQUIDITY
+        ));
+
+        assert_noop!(
+            EmaOracle::submit_price(RuntimeOrigin::signed(FEEDER_1), DOT, HDX, Price::new(1, 2), LIQUIDITY),
+            Error::<Test>::AlreadySubmitted
+        );
+    });
+}
+
+#[test]
+fn submit_price_should_add_entry_to_accumulator() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        // Act
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            HDX,
+            DOT,
+            Price::new(2, 1),
+            LIQUIDITY
+        ));
+
+        // Assert
+        assert_eq!(
+            external_accumulator_entry(),
+            Some(OracleEntry {
+                price: Price::new(2, 1),
+                volume: Volume::default(),
+                liquidity: LIQUIDITY,
+                volatility: FixedU128::zero(),
+                timestamp: 1,
+            })
+        );
+        System::assert_last_event(TestEvent::EmaOracle(Event::ExternalPriceSubmitted {
+            feeder: FEEDER_1,
+            assets: (HDX, DOT),
+            price: Price::new(2, 1),
+            liquidity: LIQUIDITY,
+        }));
+    });
+}
+
+#[test]
+fn submit_price_should_adjust_values_to_ordered_pair() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        // Act
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            DOT,
+            HDX,
+            Price::new(1, 2),
+            LIQUIDITY.inverted()
+        ));
+
+        // Assert
+        let entry = external_accumulator_entry().unwrap();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, LIQUIDITY);
+    });
+}
+
+#[test]
+fn submit_price_should_aggregate_submissions_by_median() {
+    with_feeders(&[FEEDER_1, FEEDER_2, FEEDER_3]).execute_with(|| {
+        // Act
+        for (feeder, price, liquidity) in [
+            (FEEDER_1, Price::new(21, 10), Liquidity::new(2_100, 1_000)),
+            (FEEDER_2, Price::new(19, 10), Liquidity::new(1_900, 1_000)),
+            (FEEDER_3, Price::new(2, 1), Liquidity::new(2_000, 1_000)),
+        ] {
+            assert_ok!(EmaOracle::submit_price(
+                RuntimeOrigin::signed(feeder),
+                HDX,
+                DOT,
+                price,
+                liquidity
+            ));
+        }
+
+        // Assert
+        let entry = external_accumulator_entry().unwrap();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, Liquidity::new(2_000, 1_000));
+        assert_eq!(entry.volume, Volume::default());
+    });
+}
+
+#[test]
+fn submit_price_should_take_lower_median_of_even_number_of_submissions() {
+    with_feeders(&[FEEDER_1, FEEDER_2, FEEDER_3, FEEDER_4]).execute_with(|| {
+        // Act
+        for (feeder, price) in [
+            (FEEDER_1, Price::new(22, 10)),
+            (FEEDER_2, Price::new(19, 10)),
+            (FEEDER_3, Price::new(21, 10)),
+            (FEEDER_4, Price::new(2, 1)),
+        ] {
+            assert_ok!(EmaOracle::submit_price(
+                RuntimeOrigin::signed(feeder),
+                HDX,
+                DOT,
+                price,
+                LIQUIDITY
+            ));
+        }
+
+        // Assert
+        assert_eq!(external_accumulator_entry().unwrap().price, Price::new(2, 1));
+    });
+}
+
+#[test]
+fn submit_price_should_reject_zero_values() {
+    with_feeders(&[FEEDER_1]).execute_with(|| {
+        for (price, liquidity) in [
+            (Price::new(0, 1), LIQUIDITY),
+            (Price::new(1, 0), LIQUIDITY),
+            (Price::new(2, 1), Liquidity::new(0, 1_000)),
+            (Price::new(2, 1), Liquidity::new(2_000, 0)),
+        ] {
+            // Act
+            assert_ok!(EmaOracle::submit_price(
+                RuntimeOrigin::signed(FEEDER_1),
+                HDX,
+                DOT,
+                price,
+                liquidity
+            ));
+
+            // Assert
+            System::assert_last_event(TestEvent::EmaOracle(Event::ExternalPriceRejected {
+                feeder: FEEDER_1,
+                assets: (HDX, DOT),
+                price,
+                liquidity,
+                reason: ExternalPriceRejection::ZeroValue,
+            }));
+        }
+        assert_eq!(external_accumulator_entry(), None);
+        assert!(EmaOracle::external_submissions((HDX, DOT)).is_empty());
+    });
+}
+
+#[test]
+fn submit_price_should_reject_outliers() {
+    with_feeders(&[FEEDER_1, FEEDER_2, FEEDER_3]).execute_with(|| {
+        // Arrange
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            HDX,
+            DOT,
+            Price::new(2, 1),
+            LIQUIDITY
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        // Act
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            HDX,
+            DOT,
+            Price::new(221, 100),
+            LIQUIDITY
+        ));
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_2),
+            HDX,
+            DOT,
+            Price::new(179, 100),
+            LIQUIDITY
+        ));
+
+        // Assert
+        System::assert_has_event(TestEvent::EmaOracle(Event::ExternalPriceRejected {
+            feeder: FEEDER_1,
+            assets: (HDX, DOT),
+            price: Price::new(221, 100),
+            liquidity: LIQUIDITY,
+            reason: ExternalPriceRejection::Outlier,
+        }));
+        System::assert_last_event(TestEvent::EmaOracle(Event::ExternalPriceRejected {
+            feeder: FEEDER_2,
+            assets: (HDX, DOT),
+            price: Price::new(179, 100),
+            liquidity: LIQUIDITY,
+            reason: ExternalPriceRejection::Outlier,
+        }));
+        assert_eq!(external_accumulator_entry(), None);
+
+        // deviations up to `MaxExternalPriceDeviation` are accepted
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_3),
+            HDX,
+            DOT,
+            Price::new(22, 10),
+            LIQUIDITY
+        ));
+        assert_eq!(external_accumulator_entry().unwrap().price, Price::new(22, 10));
+    });
+}
+
+#[test]
+fn external_prices_should_update_oracles_of_external_source() {
+    with_feeders(&[FEEDER_1, FEEDER_2]).execute_with(|| {
+        // Arrange
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            HDX,
+            DOT,
+            Price::new(2, 1),
+            LIQUIDITY
+        ));
+
+        // Act
+        EmaOracle::on_finalize(1);
+
+        // Assert
+        assert!(EmaOracle::external_submissions((HDX, DOT)).is_empty());
+        System::set_block_number(2);
+        let entry = EmaOracle::get_entry(HDX, DOT, LastBlock, EXTERNAL_SOURCE).unwrap();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, LIQUIDITY);
+
+        // feeders can submit again in the next block
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(FEEDER_1),
+            HDX,
+            DOT,
+            Price::new(2, 1),
+            LIQUIDITY
+        ));
+    });
+}
+
+#[test]
+fn on_initialize_weight_should_include_clearing_external_submissions_of_max_unique_entries() {
+    new_test_ext().execute_with(|| {
+        // Act
+        let weight = EmaOracle::on_initialize(1);
+
+        // Assert
+        let max_entries = <<Test as crate::Config>::MaxUniqueEntries as Get<u32>>::get();
+        assert_eq!(
+            weight,
+            <Test as crate::Config>::WeightInfo::on_finalize_no_entry().saturating_add(
+                <Test as crate::Config>::WeightInfo::clear_external_submissions(max_entries)
+            )
+        );
+    });
+}
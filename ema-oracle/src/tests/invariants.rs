@@ -51,28 +51,45 @@ fn any_liquidity() -> impl Strategy<Value = Liquidity<Balance>> {
     (any::<Balance>(), any::<Balance>()).prop_map(|l| l.into())
 }
 
+// squared returns are at most 4
+fn any_volatility() -> impl Strategy<Value = FixedU128> {
+    (0..=4 * FixedU128::DIV).prop_map(FixedU128::from_inner)
+}
+
 fn oracle_entry_with_timestamp(timestamp: BlockNumber) -> impl Strategy<Value = OracleEntry<BlockNumber>> {
-    (any_price(), any_volume(), any_liquidity(), Just(timestamp)).prop_map(|(price, volume, liquidity, timestamp)| {
-        OracleEntry {
+    (
+        any_price(),
+        any_volume(),
+        any_liquidity(),
+        any_volatility(),
+        Just(timestamp),
+    )
+        .prop_map(|(price, volume, liquidity, volatility, timestamp)| OracleEntry {
             price,
             volume,
             liquidity,
+            volatility,
             timestamp,
-        }
-    })
+        })
 }
 
 fn oracle_entry_within_timestamp_range(
     (timestamp_min, timestamp_max): (BlockNumber, BlockNumber),
 ) -> impl Strategy<Value = OracleEntry<BlockNumber>> {
-    (any_price(), any_volume(), any_liquidity(), timestamp_min..timestamp_max).prop_map(
-        |(price, volume, liquidity, timestamp)| OracleEntry {
+    (
+        any_price(),
+        any_volume(),
+        any_liquidity(),
+        any_volatility(),
+        timestamp_min..timestamp_max,
+    )
+        .prop_map(|(price, volume, liquidity, volatility, timestamp)| OracleEntry {
             price,
             volume,
             liquidity,
+            volatility,
             timestamp,
-        },
-    )
+        })
 }
 
 // Tests
@@ -137,6 +154,74 @@ proptest! {
     }
 }
 
+proptest! {
+    #[test]
+    fn integrated_volatility_should_be_between_previous_and_incoming(
+        start_oracle in oracle_entry_with_timestamp(10_000),
+        incoming_value in oracle_entry_with_timestamp(10_001),
+    ) {
+        let next_oracle = start_oracle.calculate_new_by_integrating_incoming(TenMinutes, &incoming_value).unwrap();
+        let min = start_oracle.volatility.min(incoming_value.volatility);
+        let max = start_oracle.volatility.max(incoming_value.volatility);
+        prop_assert!(min <= next_oracle.volatility && next_oracle.volatility <= max);
+    }
+}
+
+proptest! {
+    #[test]
+    fn outdated_volatility_should_be_between_previous_and_incoming(
+        start_oracle in oracle_entry_within_timestamp_range((0, 1_000)),
+        incoming_value in oracle_entry_within_timestamp_range((1_001, 100_000)),
+    ) {
+        let next_oracle = start_oracle.calculate_current_from_outdated(TenMinutes, &incoming_value).unwrap();
+        let min = start_oracle.volatility.min(incoming_value.volatility);
+        let max = start_oracle.volatility.max(incoming_value.volatility);
+        prop_assert!(min <= next_oracle.volatility && next_oracle.volatility <= max);
+    }
+}
+
+proptest! {
+    #[test]
+    fn squared_return_should_be_independent_of_asset_order(
+        (previous, current) in (any_price(), any_price()),
+    ) {
+        let squared = squared_return(previous, current);
+        prop_assert_eq!(squared, squared_return(inverted_price(previous), inverted_price(current)));
+        prop_assert_eq!(squared, squared_return(current, previous));
+    }
+}
+
+proptest! {
+    #[test]
+    fn volatility_should_be_independent_of_asset_order(
+        (liquidity_hdx, liquidity_dot) in (non_zero_amount(), non_zero_amount()),
+        (second_liquidity_hdx, second_liquidity_dot) in (non_zero_amount(), non_zero_amount()),
+    ) {
+        new_test_ext().execute_with(|| -> Result<(), TestCaseError> {
+            System::set_block_number(1);
+            assert_ok!(OnActivityHandler::<Test>::on_trade(SOURCE, HDX, DOT, 1_000, 1_000, liquidity_hdx, liquidity_dot));
+            EmaOracle::on_finalize(1);
+            System::set_block_number(2);
+            assert_ok!(OnActivityHandler::<Test>::on_trade(SOURCE, DOT, HDX, 1_000, 1_000, second_liquidity_dot, second_liquidity_hdx));
+            EmaOracle::on_finalize(2);
+            System::set_block_number(3);
+
+            let expected = squared_return(
+                determine_normalized_price(HDX, DOT, liquidity_hdx, liquidity_dot),
+                determine_normalized_price(HDX, DOT, second_liquidity_hdx, second_liquidity_dot),
+            );
+            prop_assert_eq!(EmaOracle::get_volatility(HDX, DOT, LastBlock, SOURCE), Ok((expected, 1)));
+            for period in supported_periods() {
+                prop_assert_eq!(
+                    EmaOracle::get_volatility(HDX, DOT, period, SOURCE),
+                    EmaOracle::get_volatility(DOT, HDX, period, SOURCE)
+                );
+            }
+            Ok(())
+        })?;
+    }
+}
+
 use hydra_dx_math::ema::{iterated_balance_ema, iterated_price_ema, iterated_volume_ema};
 
 proptest! {
@@ -172,7 +257,47 @@ proptest! {
                 oracle_age: 98,
             };
             prop_assert_eq!(EmaOracle::get_entry(HDX, DOT, TenMinutes, SOURCE), Ok(expected_ten_min));
+            // there is no previous price, so there is no return either
+            prop_assert_eq!(EmaOracle::get_volatility(HDX, DOT, TenMinutes, SOURCE), Ok((FixedU128::zero(), 98)));
             Ok(())
         })?;
     }
 }
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+    #[test]
+    fn twap_should_equal_naive_average_of_block_prices(
+        updates in prop::collection::vec((1..5u64, 1..1_000_000_000_000_000u128, 1..1_000_000_000_000_000u128), 1..10),
+        blocks_after_last_update in 1..5u64,
+        window_percentage in 1..=100u64,
+    ) {
+        new_test_ext().execute_with(|| {
+            let mut block = 0;
+            let mut prices = Vec::new();
+            for (gap, liquidity_a, liquidity_b) in updates {
+                block += gap;
+                System::set_block_number(block);
+                assert_ok!(OnActivityHandler::<Test>::on_trade(SOURCE, HDX, DOT, 1_000, 1_000, liquidity_a, liquidity_b));
+                EmaOracle::on_finalize(block);
+                prices.push((block, determine_normalized_price(HDX, DOT, liquidity_a, liquidity_b)));
+            }
+            let now = block + blocks_after_last_update;
+            System::set_block_number(now);
+            let first_update = prices[0].0;
+            let window = ((now - first_update) * window_percentage / 100).max(1);
+
+            // naive reference: sum up the price valid in every block of the window
+            let (sum, inverted_sum) = (now - window..now).fold((U256::zero(), U256::zero()), |(sum, inverted_sum), b| {
+                let (_, price) = prices.iter().rev().find(|(timestamp, _)| *timestamp <= b).unwrap();
+                let (price, inverted_price) = fixed_prices(*price);
+                (sum + U256::from(price.into_inner()), inverted_sum + U256::from(inverted_price.into_inner()))
+            });
+            let expected = Price::new((sum / U256::from(window)).low_u128(), FixedU128::DIV);
+            let expected_inverted = Price::new((inverted_sum / U256::from(window)).low_u128(), FixedU128::DIV);
+
+            assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, window), Ok(expected));
+            assert_eq!(EmaOracle::get_twap(DOT, HDX, SOURCE, window), Ok(expected_inverted));
+        });
+    }
+}
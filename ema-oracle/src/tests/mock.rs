@@ -27,8 +27,10 @@ use frame_support::sp_runtime::{
 };
 use frame_support::traits::{Everything, GenesisBuild};
 use frame_support::BoundedVec;
+use frame_system::EnsureRoot;
 use hydradx_traits::OraclePeriod::{self, *};
 use hydradx_traits::{AssetPairAccountIdFor, Liquidity, Volume};
+use sp_arithmetic::{FixedU128, Permill};
 use sp_core::H256;
 
 pub use hydradx_traits::Source;
@@ -54,6 +56,7 @@ pub const ORACLE_ENTRY_1: OracleEntry<BlockNumber> = OracleEntry {
         b_in: 0,
     },
     liquidity: Liquidity::new(2_000, 1_000),
+    volatility: FixedU128::from_inner(0),
     timestamp: 5,
 };
 pub const ORACLE_ENTRY_2: OracleEntry<BlockNumber> = OracleEntry {
@@ -65,6 +68,7 @@ pub const ORACLE_ENTRY_2: OracleEntry<BlockNumber> = OracleEntry {
         b_in: 2_000,
     },
     liquidity: Liquidity::new(4_000, 4_000),
+    volatility: FixedU128::from_inner(0),
     timestamp: 5,
 };
 
@@ -125,7 +129,10 @@ impl AssetPairAccountIdFor<AssetId, u64> for AssetPairAccountIdTest {
 }
 
 parameter_types! {
+    pub const OracleInactivityThreshold: BlockNumber = 100;
+    pub const MaxExternalPriceDeviation: Permill = Permill::from_percent(10);
     pub SupportedPeriods: BoundedVec<OraclePeriod, ConstU32<MAX_PERIODS>> = bounded_vec![LastBlock, TenMinutes, Day, Week];
+    pub static EmitOracleUpdatedEvents: bool = true;
 }
 
 impl Config for Test {
@@ -134,6 +141,17 @@ impl Config for Test {
     type BlockNumberProvider = System;
     type SupportedPeriods = SupportedPeriods;
     type MaxUniqueEntries = ConstU32<45>;
+    type UpdatePeriodsOrigin = EnsureRoot<u64>;
+    type MaxCustomPeriods = ConstU32<2>;
+    type MaxTwapCheckpoints = ConstU32<10>;
+    type RemoveOracleOrigin = EnsureRoot<u64>;
+    type OracleInactivityThreshold = OracleInactivityThreshold;
+    type ManageFeedersOrigin = EnsureRoot<u64>;
+    type MaxFeeders = ConstU32<5>;
+    type MaxExternalPriceDeviation = MaxExternalPriceDeviation;
+    type UpdateCrossRatePathsOrigin = EnsureRoot<u64>;
+    type MaxCrossRateHops = ConstU32<2>;
+    type EmitOracleUpdatedEvents = EmitOracleUpdatedEvents;
 }
 
 pub type InitialDataEntry = (Source, (AssetId, AssetId), Price, Liquidity<Balance>);
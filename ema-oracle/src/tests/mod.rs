@@ -15,8 +15,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod cross_rates;
+mod custom_periods;
+mod events;
+mod external_feeds;
 mod invariants;
 mod mock;
+mod price_provider;
+mod pruning;
+mod twap;
+mod volatility;
 
 use super::*;
 pub use mock::{
@@ -66,8 +74,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 /// Return the entry of an asset pair in the accumulator.
 fn get_accumulator_entry(src: Source, (a, b): (AssetId, AssetId)) -> Option<OracleEntry<BlockNumber>> {
-    let acc = Accumulator::<Test>::get();
-    acc.get(&(src, ordered_pair(a, b))).cloned()
+    Accumulator::<Test>::get((src, ordered_pair(a, b)))
 }
 
 fn get_oracle_entry(a: AssetId, b: AssetId, period: OraclePeriod) -> Option<OracleEntry<BlockNumber>> {
@@ -100,6 +107,7 @@ fn genesis_config_works() {
                         price: Price::new(1_000_000, 1),
                         volume: Volume::default(),
                         liquidity: Liquidity::new(2_000_000, 2_000_000_000),
+                        volatility: FixedU128::zero(),
                         timestamp: 0,
                     })
                 );
@@ -110,6 +118,7 @@ fn genesis_config_works() {
                         price: Price::new(3_000_000, 1),
                         volume: Volume::default(),
                         liquidity: Liquidity::new(4_000_000, 4_000_000_000),
+                        volatility: FixedU128::zero(),
                         timestamp: 0,
                     })
                 );
@@ -140,6 +149,7 @@ fn on_trade_handler_should_work() {
             price: Price::new(2_000, 1_000),
             volume: Volume::from_a_in_b_out(1_000, 500),
             liquidity: Liquidity::new(2_000, 1_000),
+            volatility: FixedU128::zero(),
             timestamp: 5,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(expected));
@@ -155,6 +165,7 @@ fn on_liquidity_changed_handler_should_work() {
             price: Price::new(2_000, 1_000),
             volume: Volume::default(),
             liquidity: Liquidity::new(2_000, 1_000),
+            volatility: FixedU128::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), None);
@@ -205,6 +216,7 @@ fn on_liquidity_changed_should_allow_zero_values() {
             price: Price::new(liquidity_a, liquidity_b),
             volume: Volume::default(),
             liquidity: (liquidity_a, liquidity_b).into(),
+            volatility: FixedU128::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(only_liquidity_entry));
@@ -225,6 +237,7 @@ fn on_liquidity_changed_should_allow_zero_values() {
             price: Price::new(liquidity_a, liquidity_b),
             volume: Volume::default(),
             liquidity: (liquidity_a, liquidity_b).into(),
+            volatility: FixedU128::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(only_liquidity_entry));
@@ -245,6 +258,7 @@ fn on_liquidity_changed_should_allow_zero_values() {
             price: Price::zero(),
             volume: Volume::default(),
             liquidity: (Balance::zero(), Balance::zero()).into(),
+            volatility: FixedU128::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(only_price_entry));
@@ -313,6 +327,117 @@ fn on_entry_should_error_on_accumulator_overflow() {
     });
 }
 
+#[test]
+fn activity_of_pairs_beyond_max_unique_entries_should_be_dropped_for_the_block() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        System::set_block_number(1);
+        let max_entries = <<Test as crate::Config>::MaxUniqueEntries as Get<u32>>::get();
+        for i in 0..max_entries {
+            assert_ok!(OnActivityHandler::<Test>::on_trade(
+                SOURCE,
+                i,
+                i + 1,
+                1_000,
+                1_000,
+                2_000,
+                2_000,
+            ));
+        }
+        let dropped = (2 * max_entries, 2 * max_entries + 1);
+
+        // Act
+        assert!(OnActivityHandler::<Test>::on_trade(SOURCE, dropped.0, dropped.1, 1_000, 1_000, 2_000, 2_000).is_err());
+        EmaOracle::on_finalize(1);
+
+        // Assert
+        assert_eq!(get_accumulator_entry(SOURCE, dropped), None);
+        assert_eq!(get_oracle_entry(dropped.0, dropped.1, LastBlock), None);
+
+        // the pair is accumulated again in the next block
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, dropped.0, dropped.1, 1_000, 1_000, 2_000, 2_000
+        ));
+        EmaOracle::on_finalize(2);
+        assert!(get_oracle_entry(dropped.0, dropped.1, LastBlock).is_some());
+    });
+}
+
+#[test]
+fn on_entry_should_update_existing_entries_when_accumulator_is_full() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        System::set_block_number(1);
+        let max_entries = <<Test as crate::Config>::MaxUniqueEntries as Get<u32>>::get();
+        for i in 0..max_entries {
+            assert_ok!(OnActivityHandler::<Test>::on_trade(
+                SOURCE,
+                i,
+                i + 1,
+                1_000,
+                1_000,
+                2_000,
+                2_000,
+            ));
+        }
+        assert_eq!(EmaOracle::accumulator_count(), max_entries);
+
+        // Act
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, 0, 1, 1_000, 1_000, 3_000, 2_000
+        ));
+
+        // Assert
+        assert_eq!(
+            get_accumulator_entry(SOURCE, (0, 1)).unwrap().volume,
+            Volume::from_a_in_b_out(2_000, 2_000)
+        );
+        assert_eq!(EmaOracle::accumulator_count(), max_entries);
+    });
+}
+
+#[test]
+fn on_finalize_should_clear_accumulator() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        System::set_block_number(1);
+        let max_entries = <<Test as crate::Config>::MaxUniqueEntries as Get<u32>>::get();
+        for i in 0..max_entries {
+            assert_ok!(OnActivityHandler::<Test>::on_trade(
+                SOURCE,
+                i,
+                i + 1,
+                1_000,
+                1_000,
+                2_000,
+                2_000,
+            ));
+        }
+
+        // Act
+        EmaOracle::on_finalize(1);
+
+        // Assert
+        assert_eq!(EmaOracle::accumulator_count(), 0);
+        assert_eq!(Accumulator::<Test>::iter().count(), 0);
+        assert!(get_oracle_entry(0, 1, LastBlock).is_some());
+
+        // the accumulator accepts new pairs again
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE,
+            2 * max_entries,
+            2 * max_entries + 1,
+            1_000,
+            1_000,
+            2_000,
+            2_000
+        ));
+        assert_eq!(EmaOracle::accumulator_count(), 1);
+    });
+}
+
 #[test]
 fn volume_normalization_should_factor_in_asset_order() {
     assert_ne!(
@@ -347,12 +472,14 @@ fn oracle_volume_should_factor_in_asset_order() {
             price: Price::new(2_000, 1),
             volume: Volume::from_a_in_b_out(2_000_000, 1_000),
             liquidity: (2_000, 1).into(),
+            volatility: FixedU128::zero(),
             timestamp: 0,
         };
         let second_entry = OracleEntry {
             price: Price::new(2_000, 1),
             volume: Volume::from_a_out_b_in(2_000_000, 1_000),
             liquidity: (2_000, 1).into(),
+            volatility: FixedU128::zero(),
             timestamp: 0,
         };
 
@@ -448,6 +575,7 @@ fn calculate_new_by_integrating_incoming_only_updates_timestamp_on_stable_values
         price: Price::new(4, 1),
         volume: Volume::from_a_in_b_out(1, 4),
         liquidity: Liquidity::new(4, 1),
+        volatility: FixedU128::zero(),
         timestamp: 5_u32,
     };
     let next_value = OracleEntry {
@@ -464,6 +592,7 @@ fn calculate_new_by_integrating_incoming_with_works() {
         price: Price::new(50, 1),
         volume: Volume::from_a_in_b_out(1, 50),
         liquidity: Liquidity::new(50, 1),
+        volatility: FixedU128::zero(),
         timestamp: 5_u32,
     };
 
@@ -471,6 +600,7 @@ fn calculate_new_by_integrating_incoming_with_works() {
         price: Price::new(151, 1),
         volume: Volume::from_a_in_b_out(1, 151),
         liquidity: Liquidity::new(151, 1),
+        volatility: FixedU128::zero(),
         timestamp: 6,
     };
     let next_oracle = start_oracle
@@ -483,6 +613,7 @@ fn calculate_new_by_integrating_incoming_with_works() {
         price: Price::new(52, 1),
         volume: Volume::from_a_in_b_out(1, 52),
         liquidity: Liquidity::new(52, 1),
+        volatility: FixedU128::zero(),
         timestamp: 6,
     };
     let tolerance = Price::new(1, 1e10 as u128);
@@ -498,6 +629,7 @@ fn calculate_new_by_integrating_incoming_last_block_period_returns_new_value() {
         price: Price::new(4, 1),
         volume: Volume::from_a_in_b_out(1_u128, 4_u128),
         liquidity: Liquidity::new(4_u128, 1_u128),
+        volatility: FixedU128::zero(),
         timestamp: 5_u32,
     };
 
@@ -505,6 +637,7 @@ fn calculate_new_by_integrating_incoming_last_block_period_returns_new_value() {
         price: Price::new(8, 1),
         volume: Volume::from_a_in_b_out(1_u128, 8_u128),
         liquidity: Liquidity::new(8_u128, 1_u128),
+        volatility: FixedU128::zero(),
         timestamp: 6,
     };
     let next_oracle = start_oracle.calculate_new_by_integrating_incoming(LastBlock, &next_value);
@@ -519,12 +652,14 @@ fn calculate_current_from_outdated_should_incorporate_longer_time_deltas() {
         price: Price::new(4_000, 1),
         volume: Volume::from_a_in_b_out(1, 4_000),
         liquidity: Liquidity::new(4_000, 1),
+        volatility: FixedU128::zero(),
         timestamp: 5_u32,
     };
     let next_value = OracleEntry {
         price: Price::new(8_000, 1),
         volume: Volume::from_a_in_b_out(1, 8_000),
         liquidity: Liquidity::new(8_000, 1),
+        volatility: FixedU128::zero(),
         timestamp: 1_000,
     };
     let next_oracle = start_oracle
@@ -620,6 +755,69 @@ fn get_entry_works() {
     });
 }
 
+#[test]
+fn get_entries_should_return_updated_entries_of_all_supported_periods() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(100);
+
+        let expected: Vec<_> = supported_periods()
+            .into_iter()
+            .map(|period| (period, EmaOracle::get_entry(HDX, DOT, period, SOURCE).unwrap()))
+            .collect();
+        assert_eq!(expected.len(), 4);
+        assert_eq!(EmaOracle::get_entries(SOURCE, HDX, DOT), expected);
+
+        let expected_inverted: Vec<_> = supported_periods()
+            .into_iter()
+            .map(|period| (period, EmaOracle::get_entry(DOT, HDX, period, SOURCE).unwrap()))
+            .collect();
+        assert_eq!(EmaOracle::get_entries(SOURCE, DOT, HDX), expected_inverted);
+        assert_eq!(expected_inverted[0].1.price, Price::new(1_000, 2_000));
+    });
+}
+
+#[test]
+fn get_entries_should_return_empty_list_for_untracked_pair() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(EmaOracle::get_entries(SOURCE, HDX, ACA), vec![]);
+    });
+}
+
+#[test]
+fn oracle_keys_should_list_tracked_pairs_with_pagination() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, ACA, HDX, 1_000, 500, 2_000, 1_000
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            *b"aaaasrc_",
+            DOT,
+            ACA,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+        EmaOracle::on_finalize(1);
+
+        assert_eq!(
+            EmaOracle::oracle_keys(0, 10),
+            vec![(*b"aaaasrc_", (DOT, ACA)), (SOURCE, (HDX, DOT)), (SOURCE, (HDX, ACA))]
+        );
+        assert_eq!(EmaOracle::oracle_keys(1, 1), vec![(SOURCE, (HDX, DOT))]);
+        assert_eq!(EmaOracle::oracle_keys(3, 10), vec![]);
+    });
+}
+
 #[test]
 fn get_price_returns_updated_price() {
     ExtBuilder::default()
@@ -635,6 +833,7 @@ fn get_price_returns_updated_price() {
                 price: Price::new(500_000, 1),
                 volume: Volume::default(),
                 liquidity: Liquidity::new(2_000_000, 2),
+                volatility: FixedU128::zero(),
                 timestamp: 1,
             };
             System::set_block_number(1);
@@ -0,0 +1,191 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::parameter_types;
+use hydradx_traits::pools::SpotPriceProvider;
+use pretty_assertions::assert_eq;
+
+const FALLBACK_SOURCE: Source = *b"fallback";
+
+parameter_types! {
+    pub const NativeAssetId: AssetId = HDX;
+    pub PriceSources: Vec<Source> = vec![SOURCE, FALLBACK_SOURCE];
+    pub const PricePeriod: OraclePeriod = LastBlock;
+    pub const MaxOracleAge: BlockNumber = 10;
+}
+
+type PriceProvider = OraclePriceProvider<Test, NativeAssetId, PriceSources, PricePeriod, MaxOracleAge>;
+
+fn trade(source: Source, block: BlockNumber, liquidity_dot: Balance, liquidity_hdx: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        source,
+        DOT,
+        HDX,
+        1_000,
+        500,
+        liquidity_dot,
+        liquidity_hdx
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+#[test]
+fn native_price_should_be_one_for_native_asset() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(
+            <PriceProvider as NativePriceOracle<AssetId, FixedU128>>::price(HDX),
+            Some(FixedU128::one())
+        );
+    });
+}
+
+#[test]
+fn native_price_should_return_price_of_native_asset_in_currency() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(SOURCE, 1, 4_000, 1_000);
+
+        // Act
+        System::set_block_number(2);
+
+        // Assert
+        assert_eq!(
+            <PriceProvider as NativePriceOracle<AssetId, FixedU128>>::price(DOT),
+            Some(FixedU128::saturating_from_integer(4))
+        );
+        assert_eq!(
+            <PriceProvider as NativePriceOracle<AssetId, FixedU128>>::price(ACA),
+            None
+        );
+    });
+}
+
+#[test]
+fn spot_price_should_return_price_of_asset_b_in_asset_a() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(SOURCE, 1, 4_000, 1_000);
+
+        // Act
+        System::set_block_number(2);
+
+        // Assert
+        assert_eq!(
+            PriceProvider::spot_price(DOT, HDX),
+            Some(FixedU128::saturating_from_integer(4))
+        );
+        assert_eq!(
+            PriceProvider::spot_price(HDX, DOT),
+            Some(FixedU128::saturating_from_rational(1, 4))
+        );
+        assert_eq!(PriceProvider::spot_price(HDX, HDX), None);
+    });
+}
+
+#[test]
+fn price_should_fall_back_to_next_source_without_oracle() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(FALLBACK_SOURCE, 1, 5_000, 1_000);
+
+        // Act
+        System::set_block_number(2);
+
+        // Assert
+        assert_eq!(
+            PriceProvider::spot_price(DOT, HDX),
+            Some(FixedU128::saturating_from_integer(5))
+        );
+    });
+}
+
+#[test]
+fn price_should_fall_back_to_next_source_when_oracle_is_too_old() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(SOURCE, 1, 4_000, 1_000);
+        trade(FALLBACK_SOURCE, 15, 5_000, 1_000);
+
+        // Act
+        System::set_block_number(16);
+
+        // Assert
+        assert_eq!(
+            PriceProvider::spot_price(DOT, HDX),
+            Some(FixedU128::saturating_from_integer(5))
+        );
+    });
+}
+
+#[test]
+fn price_should_not_be_available_when_all_oracles_are_too_old() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(SOURCE, 1, 4_000, 1_000);
+
+        // Act & Assert
+        System::set_block_number(12);
+        assert_eq!(
+            PriceProvider::spot_price(DOT, HDX),
+            Some(FixedU128::saturating_from_integer(4))
+        );
+        System::set_block_number(13);
+        assert_eq!(PriceProvider::spot_price(DOT, HDX), None);
+        assert_eq!(
+            <PriceProvider as NativePriceOracle<AssetId, FixedU128>>::price(DOT),
+            None
+        );
+    });
+}
+
+#[test]
+fn price_should_not_be_available_when_price_is_zero() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_liquidity_changed(
+            SOURCE, DOT, HDX, 1_000, 500, 0, 0
+        ));
+        EmaOracle::on_finalize(1);
+
+        // Act
+        System::set_block_number(2);
+
+        // Assert
+        assert_eq!(PriceProvider::spot_price(DOT, HDX), None);
+    });
+}
+
+#[test]
+fn pair_exists_should_check_all_sources() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(FALLBACK_SOURCE, 1, 5_000, 1_000);
+
+        // Act
+        System::set_block_number(2);
+
+        // Assert
+        assert!(PriceProvider::pair_exists(DOT, HDX));
+        assert!(PriceProvider::pair_exists(HDX, DOT));
+        assert!(!PriceProvider::pair_exists(HDX, ACA));
+        assert!(!PriceProvider::pair_exists(HDX, HDX));
+    });
+}
@@ -0,0 +1,249 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::sp_runtime::DispatchError;
+use pretty_assertions::assert_eq;
+
+fn trade(block: BlockNumber, asset_a: AssetId, asset_b: AssetId) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE, asset_a, asset_b, 1_000, 500, 2_000, 1_000
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn is_tracked(asset_a: AssetId, asset_b: AssetId) -> bool {
+    Oracles::<Test>::contains_key((SOURCE, ordered_pair(asset_a, asset_b), LastBlock))
+}
+
+fn removal_weight() -> Weight {
+    <Test as crate::Config>::WeightInfo::remove_oracle()
+}
+
+#[test]
+fn remove_oracle_should_remove_oracles_of_all_periods() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        assert_ok!(EmaOracle::add_custom_period(RuntimeOrigin::root(), 30, 1));
+
+        // Act
+        assert_ok!(EmaOracle::remove_oracle(RuntimeOrigin::root(), SOURCE, HDX, DOT));
+
+        // Assert
+        for period in EmaOracle::tracked_periods() {
+            assert_eq!(get_oracle_entry(HDX, DOT, period), None);
+        }
+        assert!(EmaOracle::twap_checkpoints((SOURCE, ordered_pair(HDX, DOT))).is_empty());
+        System::assert_last_event(TestEvent::EmaOracle(Event::OracleRemoved {
+            source: SOURCE,
+            assets: ordered_pair(HDX, DOT),
+        }));
+    });
+}
+
+#[test]
+fn remove_oracle_should_work_with_any_asset_order() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+
+        // Act
+        assert_ok!(EmaOracle::remove_oracle(RuntimeOrigin::root(), SOURCE, DOT, HDX));
+
+        // Assert
+        assert!(!is_tracked(HDX, DOT));
+    });
+}
+
+#[test]
+fn remove_oracle_should_keep_oracles_of_other_pairs() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        trade(1, DOT, ACA);
+
+        // Act
+        assert_ok!(EmaOracle::remove_oracle(RuntimeOrigin::root(), SOURCE, HDX, DOT));
+
+        // Assert
+        assert!(!is_tracked(HDX, DOT));
+        for period in supported_periods() {
+            assert!(get_oracle_entry(DOT, ACA, period).is_some());
+        }
+    });
+}
+
+#[test]
+fn remove_oracle_should_fail_when_oracle_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+
+        assert_noop!(
+            EmaOracle::remove_oracle(RuntimeOrigin::root(), SOURCE, HDX, ACA),
+            Error::<Test>::OracleNotFound
+        );
+    });
+}
+
+#[test]
+fn remove_oracle_should_fail_when_origin_is_not_allowed() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+
+        assert_noop!(
+            EmaOracle::remove_oracle(RuntimeOrigin::signed(1), SOURCE, HDX, DOT),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn oracle_should_be_created_again_after_removal() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        assert_ok!(EmaOracle::remove_oracle(RuntimeOrigin::root(), SOURCE, HDX, DOT));
+
+        // Act
+        trade(2, HDX, DOT);
+
+        // Assert
+        assert_eq!(
+            EmaOracle::oracle((SOURCE, ordered_pair(HDX, DOT), TenMinutes))
+                .unwrap()
+                .1,
+            2
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_remove_inactive_oracles() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        trade(50, DOT, ACA);
+        let threshold = <Test as crate::Config>::OracleInactivityThreshold::get();
+        let now = 1 + threshold + 1;
+        System::set_block_number(now);
+
+        // Act
+        EmaOracle::on_idle(now, Weight::MAX);
+
+        // Assert
+        assert!(!is_tracked(HDX, DOT));
+        assert_eq!(get_oracle_entry(HDX, DOT, TenMinutes), None);
+        assert!(is_tracked(DOT, ACA));
+        System::assert_has_event(TestEvent::EmaOracle(Event::OracleRemoved {
+            source: SOURCE,
+            assets: ordered_pair(HDX, DOT),
+        }));
+        assert_eq!(EmaOracle::pruning_cursor(), None);
+    });
+}
+
+#[test]
+fn on_idle_should_keep_oracles_updated_within_threshold() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        let threshold = <Test as crate::Config>::OracleInactivityThreshold::get();
+        let now = 1 + threshold;
+        System::set_block_number(now);
+
+        // Act
+        EmaOracle::on_idle(now, Weight::MAX);
+
+        // Assert
+        assert!(is_tracked(HDX, DOT));
+    });
+}
+
+#[test]
+fn on_idle_should_keep_oracles_with_activity_in_current_block() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        let threshold = <Test as crate::Config>::OracleInactivityThreshold::get();
+        let now = 1 + threshold + 1;
+        System::set_block_number(now);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+
+        // Act
+        EmaOracle::on_idle(now, Weight::MAX);
+
+        // Assert
+        assert!(is_tracked(HDX, DOT));
+        assert_eq!(
+            EmaOracle::oracle((SOURCE, ordered_pair(HDX, DOT), TenMinutes))
+                .unwrap()
+                .1,
+            1
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_remove_oracles_within_remaining_weight_and_continue_in_next_block() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        trade(1, DOT, ACA);
+        let threshold = <Test as crate::Config>::OracleInactivityThreshold::get();
+        let now = 1 + threshold + 1;
+        System::set_block_number(now);
+
+        // Act
+        EmaOracle::on_idle(now, removal_weight());
+
+        // Assert
+        assert_eq!(is_tracked(HDX, DOT) as u8 + is_tracked(DOT, ACA) as u8, 1);
+        assert!(EmaOracle::pruning_cursor().is_some());
+
+        // Act
+        for block in now + 1..now + 3 {
+            System::set_block_number(block);
+            EmaOracle::on_idle(block, removal_weight());
+        }
+
+        // Assert
+        assert!(!is_tracked(HDX, DOT));
+        assert!(!is_tracked(DOT, ACA));
+    });
+}
+
+#[test]
+fn on_idle_should_not_remove_oracles_when_weight_is_insufficient() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, HDX, DOT);
+        let threshold = <Test as crate::Config>::OracleInactivityThreshold::get();
+        let now = 1 + threshold + 1;
+        System::set_block_number(now);
+
+        // Act
+        EmaOracle::on_idle(now, removal_weight().saturating_sub(Weight::from_ref_time(1)));
+
+        // Assert
+        assert!(is_tracked(HDX, DOT));
+        assert_eq!(EmaOracle::pruning_cursor(), None);
+    });
+}
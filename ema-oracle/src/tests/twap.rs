@@ -0,0 +1,184 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn trade_hdx_dot(block: BlockNumber, liquidity_hdx: Balance, liquidity_dot: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE,
+        HDX,
+        DOT,
+        1_000,
+        500,
+        liquidity_hdx,
+        liquidity_dot
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn fixed_price(n: u128, d: u128) -> Price {
+    Price::new(FixedU128::saturating_from_rational(n, d).into_inner(), FixedU128::DIV)
+}
+
+#[test]
+fn get_twap_should_return_constant_price() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade_hdx_dot(1, 2_000, 1_000);
+        System::set_block_number(11);
+
+        // Act & Assert
+        assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 10), Ok(fixed_price(2, 1)));
+        assert_eq!(EmaOracle::get_twap(DOT, HDX, SOURCE, 10), Ok(fixed_price(1, 2)));
+    });
+}
+
+#[test]
+fn get_twap_should_weight_prices_by_number_of_blocks() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade_hdx_dot(1, 2_000, 1_000);
+        trade_hdx_dot(5, 4_000, 1_000);
+        System::set_block_number(11);
+
+        // Act & Assert
+        // 4 blocks of price 2 and 6 blocks of price 4
+        assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 10), Ok(fixed_price(32, 10)));
+        // 4 blocks of price 1/2 and 6 blocks of price 1/4
+        assert_eq!(EmaOracle::get_twap(DOT, HDX, SOURCE, 10), Ok(fixed_price(35, 100)));
+        // window after the second update
+        assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 4), Ok(fixed_price(4, 1)));
+    });
+}
+
+#[test]
+fn get_twap_should_exclude_current_block() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade_hdx_dot(1, 2_000, 1_000);
+        trade_hdx_dot(5, 4_000, 1_000);
+
+        // Act & Assert
+        assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 4), Ok(fixed_price(2, 1)));
+    });
+}
+
+#[test]
+fn get_twap_should_fail_for_invalid_parameters() {
+    new_test_ext().execute_with(|| {
+        trade_hdx_dot(1, 2_000, 1_000);
+        System::set_block_number(11);
+
+        assert_eq!(EmaOracle::get_twap(HDX, HDX, SOURCE, 10), Err(OracleError::SameAsset));
+        assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 0), Err(OracleError::ZeroWindow));
+        assert_eq!(EmaOracle::get_twap(HDX, ACA, SOURCE, 10), Err(OracleError::NotPresent));
+    });
+}
+
+#[test]
+fn get_twap_should_fail_when_window_starts_before_history() {
+    new_test_ext().execute_with(|| {
+        trade_hdx_dot(1, 2_000, 1_000);
+        System::set_block_number(11);
+
+        assert_eq!(
+            EmaOracle::get_twap(HDX, DOT, SOURCE, 11),
+            Err(OracleError::InsufficientHistory)
+        );
+        assert_eq!(
+            EmaOracle::get_twap(HDX, DOT, SOURCE, 12),
+            Err(OracleError::InsufficientHistory)
+        );
+    });
+}
+
+#[test]
+fn twap_checkpoints_should_keep_most_recent_updates() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        let max_checkpoints = <Test as crate::Config>::MaxTwapCheckpoints::get() as u64;
+        for block in 1..=max_checkpoints + 2 {
+            trade_hdx_dot(block, 2_000, 1_000);
+        }
+
+        // Assert
+        let checkpoints = EmaOracle::twap_checkpoints((SOURCE, ordered_pair(HDX, DOT)));
+        assert_eq!(checkpoints.len() as u64, max_checkpoints);
+        assert_eq!(checkpoints[0].timestamp, 3);
+
+        System::set_block_number(max_checkpoints + 3);
+        assert_eq!(
+            EmaOracle::get_twap(HDX, DOT, SOURCE, max_checkpoints),
+            Ok(fixed_price(2, 1))
+        );
+        assert_eq!(
+            EmaOracle::get_twap(HDX, DOT, SOURCE, max_checkpoints + 1),
+            Err(OracleError::InsufficientHistory)
+        );
+    });
+}
+
+#[test]
+fn genesis_data_should_initialize_twap() {
+    ExtBuilder::default()
+        .with_initial_data(vec![(
+            SOURCE,
+            (HDX, DOT),
+            Price::new(2_000, 1_000),
+            Liquidity::new(2_000, 1_000),
+        )])
+        .build()
+        .execute_with(|| {
+            System::set_block_number(10);
+            assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 10), Ok(fixed_price(2, 1)));
+        });
+}
+
+#[test]
+fn get_twap_should_fail_when_average_price_overflows() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        let checkpoints: BoundedVec<_, <Test as crate::Config>::MaxTwapCheckpoints> = vec![
+            TwapCheckpoint::new(Price::new(1, 1), 1),
+            TwapCheckpoint {
+                price: Price::new(1, 1),
+                cumulative_price: U256::from(u128::MAX).saturating_mul(U256::from(4)),
+                cumulative_inverted_price: U256::zero(),
+                timestamp: 2,
+            },
+        ]
+        .try_into()
+        .unwrap();
+        TwapCheckpoints::<Test>::insert((SOURCE, ordered_pair(HDX, DOT)), checkpoints);
+        System::set_block_number(3);
+
+        // Act & Assert
+        assert_eq!(EmaOracle::get_twap(HDX, DOT, SOURCE, 2), Err(OracleError::Overflow));
+    });
+}
+
+#[test]
+fn on_trade_weight_should_include_update_of_full_checkpoint_ring() {
+    new_test_ext().execute_with(|| {
+        let max_entries = <Test as crate::Config>::MaxUniqueEntries::get();
+
+        assert!(fractional_on_finalize_weight::<Test>(max_entries)
+            .all_gte(<Test as crate::Config>::WeightInfo::update_twap_checkpoints()));
+    });
+}
@@ -0,0 +1,130 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn trade(block: BlockNumber, liquidity_hdx: Balance, liquidity_dot: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE,
+        HDX,
+        DOT,
+        1_000,
+        500,
+        liquidity_hdx,
+        liquidity_dot
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn volatility(period: OraclePeriod) -> FixedU128 {
+    EmaOracle::get_volatility(HDX, DOT, period, SOURCE).unwrap().0
+}
+
+#[test]
+fn squared_return_should_be_relative_to_midpoint_of_prices() {
+    // the return from 2 to 2.2 is 0.2 / 2.1 = 2 / 21
+    let expected = FixedU128::from_rational(4, 441);
+    let squared = squared_return(Price::new(2, 1), Price::new(22, 10));
+    let tolerance = FixedU128::from_inner(10);
+    assert!(
+        squared.max(expected) - squared.min(expected) <= tolerance,
+        "{squared:?} should be close to {expected:?}"
+    );
+}
+
+#[test]
+fn squared_return_should_be_zero_if_a_price_is_zero() {
+    assert_eq!(squared_return(Price::zero(), Price::new(2, 1)), FixedU128::zero());
+    assert_eq!(squared_return(Price::new(2, 1), Price::zero()), FixedU128::zero());
+}
+
+#[test]
+fn volatility_should_be_squared_return_of_last_block() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, 2_000, 1_000);
+        trade(2, 2_200, 1_000);
+
+        // Act
+        System::set_block_number(3);
+
+        // Assert
+        assert_eq!(
+            EmaOracle::get_volatility(HDX, DOT, LastBlock, SOURCE),
+            Ok((squared_return(Price::new(2_000, 1_000), Price::new(2_200, 1_000)), 1))
+        );
+        for period in [TenMinutes, Day, Week] {
+            assert!(volatility(period) > FixedU128::zero());
+            assert!(volatility(period) < volatility(LastBlock));
+        }
+    });
+}
+
+#[test]
+fn volatility_should_be_zero_if_price_is_stable() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, 2_000, 1_000);
+        trade(2, 4_000, 2_000);
+
+        // Act
+        System::set_block_number(3);
+
+        // Assert
+        for period in supported_periods() {
+            assert_eq!(volatility(period), FixedU128::zero());
+        }
+    });
+}
+
+#[test]
+fn volatility_should_decay_without_activity() {
+    new_test_ext().execute_with(|| {
+        // Arrange
+        trade(1, 2_000, 1_000);
+        trade(2, 2_200, 1_000);
+        System::set_block_number(3);
+        let ten_minutes = volatility(TenMinutes);
+
+        // Act
+        System::set_block_number(100);
+
+        // Assert
+        assert_eq!(volatility(LastBlock), FixedU128::zero());
+        assert!(volatility(TenMinutes) < ten_minutes);
+        assert!(volatility(TenMinutes) > FixedU128::zero());
+    });
+}
+
+#[test]
+fn get_volatility_should_fail_for_same_assets() {
+    new_test_ext().execute_with(|| {
+        trade(1, 2_000, 1_000);
+        System::set_block_number(2);
+
+        assert_eq!(
+            EmaOracle::get_volatility(HDX, HDX, LastBlock, SOURCE),
+            Err(OracleError::SameAsset)
+        );
+        assert_eq!(
+            EmaOracle::get_volatility(HDX, ACA, LastBlock, SOURCE),
+            Err(OracleError::NotPresent)
+        );
+    });
+}
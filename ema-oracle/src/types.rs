@@ -17,14 +17,20 @@
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::sp_runtime::RuntimeDebug;
-use hydra_dx_math::ema::{calculate_new_by_integrating_incoming, update_outdated_to_current, EmaPrice};
+use hydra_dx_math::ema::{
+    calculate_new_by_integrating_incoming, iterated_balance_ema, smoothing_from_period, update_outdated_to_current,
+    EmaPrice,
+};
 use hydra_dx_math::types::Fraction;
 use hydradx_traits::{AggregatedEntry, Liquidity, Volume};
 use scale_info::TypeInfo;
-use sp_arithmetic::traits::{AtLeast32BitUnsigned, SaturatedConversion, UniqueSaturatedInto};
+use sp_arithmetic::traits::{AtLeast32BitUnsigned, SaturatedConversion, UniqueSaturatedInto, Zero};
+use sp_arithmetic::{FixedPointNumber, FixedU128, PerThing, Permill};
+use sp_core::{U256, U512};
 
 pub use hydradx_traits::{OraclePeriod, Source};
 
+use sp_std::cmp::Ordering;
 use sp_std::prelude::*;
 
 #[cfg(feature = "std")]
@@ -37,12 +43,17 @@ pub type Price = EmaPrice;
 
 /// A type representing data produced by a trade or liquidity event. Timestamped to the block where
 /// it was created.
+///
+/// The `volatility` is an exponentially weighted variance of the price returns. For an entry that
+/// has not been integrated yet it is the squared return of the price against the previous block
+/// with activity.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
 pub struct OracleEntry<BlockNumber> {
     pub price: Price,
     pub volume: Volume<Balance>,
     pub liquidity: Liquidity<Balance>,
+    pub volatility: FixedU128,
     pub timestamp: BlockNumber,
 }
 
@@ -78,10 +89,12 @@ where
         };
         let volume = self.volume.inverted();
         let liquidity = self.liquidity.inverted();
+        // the squared return is the same for the inverted prices
         Self {
             price,
             volume,
             liquidity,
+            volatility: self.volatility,
             timestamp: self.timestamp,
         }
     }
@@ -92,13 +105,16 @@ where
         self.volume = incoming.volume.saturating_add(&self.volume);
         self.price = incoming.price;
         self.liquidity = incoming.liquidity;
+        self.volatility = incoming.volatility;
         self.timestamp = incoming.timestamp;
     }
 
-    /// Fast forward the oracle value to `new_timestamp`. Updates the timestamp and resets the volume.
+    /// Fast forward the oracle value to `new_timestamp`. Updates the timestamp and resets the volume
+    /// and the volatility, as the price did not change in the meantime.
     pub fn fast_forward_to(&mut self, new_timestamp: BlockNumber) {
         self.timestamp = new_timestamp;
         self.volume = Volume::default();
+        self.volatility = FixedU128::zero();
     }
 
     /// Determine a new entry based on `self` and a previous entry. Adds the volumes together and
@@ -109,6 +125,7 @@ where
             price: self.price,
             volume,
             liquidity: self.liquidity,
+            volatility: self.volatility,
             timestamp: self.timestamp,
         }
     }
@@ -132,11 +149,18 @@ where
         let smoothing = into_smoothing(period);
         let (price, volume, liquidity) =
             calculate_new_by_integrating_incoming(self.raw_data(), incoming.raw_data(), smoothing);
+        let volatility = FixedU128::from_inner(iterated_balance_ema(
+            1,
+            self.volatility.into_inner(),
+            incoming.volatility.into_inner(),
+            smoothing,
+        ));
 
         Some(Self {
             price,
             volume: volume.into(),
             liquidity: liquidity.into(),
+            volatility,
             timestamp: incoming.timestamp,
         })
     }
@@ -176,11 +200,18 @@ where
             (update_with.price, update_with.liquidity.into()),
             smoothing,
         );
+        let volatility = FixedU128::from_inner(iterated_balance_ema(
+            iterations.saturated_into(),
+            self.volatility.into_inner(),
+            update_with.volatility.into_inner(),
+            smoothing,
+        ));
 
         Some(Self {
             price,
             volume: volume.into(),
             liquidity: liquidity.into(),
+            volatility,
             timestamp: update_with.timestamp,
         })
     }
@@ -194,7 +225,8 @@ where
 }
 
 /// Convert a given `period` into the smoothing factor used in the weighted average.
-/// See [`check_period_smoothing_factors`] for how the values are generated.
+/// See [`check_period_smoothing_factors`] for how the values are generated. The smoothing factor of
+/// a custom period is derived from its length in blocks.
 pub fn into_smoothing(period: OraclePeriod) -> Fraction {
     match period {
         OraclePeriod::LastBlock => Fraction::from_bits(170141183460469231731687303715884105728),
@@ -203,6 +235,7 @@ pub fn into_smoothing(period: OraclePeriod) -> Fraction {
         OraclePeriod::Hour => Fraction::from_bits(566193622164623067326746434994622648),
         OraclePeriod::Day => Fraction::from_bits(23629079016800115510268356880200556),
         OraclePeriod::Week => Fraction::from_bits(3375783642235081630771268215908257),
+        OraclePeriod::Custom(blocks) => smoothing_from_period(u64::from(blocks)),
     }
 }
 
@@ -212,7 +245,173 @@ impl<BlockNumber> From<(Price, Volume<Balance>, Liquidity<Balance>, BlockNumber)
             price,
             volume,
             liquidity,
+            volatility: FixedU128::zero(),
+            timestamp,
+        }
+    }
+}
+
+/// Checkpoint of the cumulative prices of an asset pair, used to calculate time-weighted average
+/// prices.
+///
+/// The cumulative prices are sums of the prices (and the inverted prices) of the ordered pair as
+/// fixed point numbers, each multiplied by the number of blocks it was valid for. A price set in
+/// block `timestamp` is valid from that block until the next update.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+pub struct TwapCheckpoint<BlockNumber> {
+    /// Price of the ordered pair set in block `timestamp`.
+    pub price: Price,
+    /// Cumulative price until (excluding) block `timestamp`.
+    pub cumulative_price: U256,
+    /// Cumulative inverted price until (excluding) block `timestamp`.
+    pub cumulative_inverted_price: U256,
+    pub timestamp: BlockNumber,
+}
+
+impl<BlockNumber> TwapCheckpoint<BlockNumber>
+where
+    BlockNumber: AtLeast32BitUnsigned + Copy + UniqueSaturatedInto<u64>,
+{
+    /// Create the first checkpoint of a pair, with zero cumulative prices.
+    pub fn new(price: Price, timestamp: BlockNumber) -> Self {
+        Self {
+            price,
+            cumulative_price: U256::zero(),
+            cumulative_inverted_price: U256::zero(),
             timestamp,
         }
     }
+
+    /// Return the cumulative price and inverted price until (excluding) block `at`, assuming the
+    /// price did not change since the checkpoint.
+    ///
+    /// Returns `None` if `at` is before the checkpoint.
+    pub fn cumulative_at(&self, at: BlockNumber) -> Option<(U256, U256)> {
+        let blocks = U256::from(at.checked_sub(&self.timestamp)?.saturated_into::<u64>());
+        let (price, inverted_price) = fixed_prices(self.price);
+
+        Some((
+            self.cumulative_price
+                .saturating_add(U256::from(price.into_inner()).saturating_mul(blocks)),
+            self.cumulative_inverted_price
+                .saturating_add(U256::from(inverted_price.into_inner()).saturating_mul(blocks)),
+        ))
+    }
+
+    /// Return the checkpoint following `self` when the price is updated to `price` in block
+    /// `timestamp`.
+    ///
+    /// Returns `None` if `timestamp` is before the checkpoint.
+    pub fn next(&self, price: Price, timestamp: BlockNumber) -> Option<Self> {
+        let (cumulative_price, cumulative_inverted_price) = self.cumulative_at(timestamp)?;
+        Some(Self {
+            price,
+            cumulative_price,
+            cumulative_inverted_price,
+            timestamp,
+        })
+    }
+}
+
+/// Convert `price` into a fixed point price and inverted price. The inverse of zero is zero.
+pub fn fixed_prices(price: Price) -> (FixedU128, FixedU128) {
+    if price.is_zero() {
+        return (FixedU128::zero(), FixedU128::zero());
+    }
+    let (n, d): (Balance, Balance) = price.into();
+    (
+        FixedU128::saturating_from_rational(n, d),
+        FixedU128::saturating_from_rational(d, n),
+    )
+}
+
+/// Compare two prices exactly. Both prices need non-zero denominators.
+pub fn compare_prices(a: Price, b: Price) -> Ordering {
+    let (a_n, a_d): (Balance, Balance) = a.into();
+    let (b_n, b_d): (Balance, Balance) = b.into();
+    U256::from(a_n)
+        .saturating_mul(U256::from(b_d))
+        .cmp(&U256::from(b_n).saturating_mul(U256::from(a_d)))
+}
+
+/// Whether `price` deviates from `reference` by more than `max_deviation` of `reference`. Both
+/// prices need non-zero denominators.
+pub fn exceeds_deviation(price: Price, reference: Price, max_deviation: Permill) -> bool {
+    let (n, d): (Balance, Balance) = price.into();
+    let (ref_n, ref_d): (Balance, Balance) = reference.into();
+    // |n / d - ref_n / ref_d| > max_deviation * ref_n / ref_d
+    // <=> |n * ref_d - ref_n * d| * 1_000_000 > parts * ref_n * d
+    let lhs = U256::from(n).saturating_mul(U256::from(ref_d));
+    let rhs = U256::from(ref_n).saturating_mul(U256::from(d));
+    let diff = if lhs >= rhs { lhs - rhs } else { rhs - lhs };
+    diff.full_mul(U256::from(Permill::ACCURACY)) > U256::from(max_deviation.deconstruct()).full_mul(rhs)
+}
+
+/// Calculate the squared return between the `previous` and the `current` price.
+///
+/// The return is measured relative to the midpoint of both prices, i.e. `2 * |current - previous|
+/// / (current + previous)`, which makes it the same for the inverted prices. Returns zero if one of
+/// the prices is zero.
+pub fn squared_return(previous: Price, current: Price) -> FixedU128 {
+    if previous.is_zero() || current.is_zero() {
+        return FixedU128::zero();
+    }
+    let (prev_n, prev_d): (Balance, Balance) = previous.into();
+    let (n, d): (Balance, Balance) = current.into();
+    let lhs = U256::from(n).saturating_mul(U256::from(prev_d));
+    let rhs = U256::from(prev_n).saturating_mul(U256::from(d));
+    let diff = if lhs >= rhs { lhs - rhs } else { rhs - lhs };
+    let sum = U512::from(lhs) + U512::from(rhs);
+    if sum.is_zero() {
+        return FixedU128::zero();
+    }
+    // the return is at most 2, so the result fits into `u128`
+    let relative_return = diff.full_mul(U256::from(2 * FixedU128::DIV)) / sum;
+    let relative_return = FixedU128::from_inner(relative_return.low_u128());
+    relative_return.saturating_mul(relative_return)
+}
+
+/// Multiply two prices. The result is rounded to fit into `u128` if necessary, a zero denominator
+/// is rounded up to one.
+pub fn multiply_prices(a: Price, b: Price) -> Price {
+    let (a_n, a_d): (Balance, Balance) = a.into();
+    let (b_n, b_d): (Balance, Balance) = b.into();
+    let n = U256::from(a_n).saturating_mul(U256::from(b_n));
+    let d = U256::from(a_d).saturating_mul(U256::from(b_d));
+    // shift numerator and denominator by the same amount to keep the ratio
+    let shift = n.bits().max(d.bits()).saturating_sub(128);
+    let (n, d) = (n >> shift, d >> shift);
+    Price::new(n.low_u128(), d.low_u128().max(1))
+}
+
+/// Return the reciprocal of `price`. The reciprocal of zero is zero.
+pub fn inverted_price(price: Price) -> Price {
+    if price.is_zero() {
+        return price;
+    }
+    let (n, d): (Balance, Balance) = price.into();
+    Price::new(d, n)
+}
+
+/// Convert `amount` with `price`, i.e. multiply it by the price. Saturates at `Balance::MAX`.
+pub fn convert_by_price(amount: Balance, price: Price) -> Balance {
+    let (n, d): (Balance, Balance) = price.into();
+    if d.is_zero() {
+        return Balance::zero();
+    }
+    let converted = U256::from(amount).saturating_mul(U256::from(n)) / U256::from(d);
+    if converted > U256::from(Balance::MAX) {
+        Balance::MAX
+    } else {
+        converted.low_u128()
+    }
+}
+
+/// Reason for rejecting a price submitted by an external feeder.
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum ExternalPriceRejection {
+    /// The price or the liquidity contains a zero.
+    ZeroValue,
+    /// The price deviates too much from the `LastBlock` oracle of the external source.
+    Outlier,
 }
@@ -20,6 +20,11 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2023-02-23, STEPS: 5, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! The run predates the per-pair accumulator. Only the ref times of `on_finalize_no_entry`,
+//! `on_finalize_multiple_tokens`, `on_trade_multiple_tokens`, `on_liquidity_changed_multiple_tokens`
+//! and `get_entry` come from it. The database reads and writes adjusted since and the figures of the
+//! other functions are estimates until the benchmarks are run again.
 
 // Executed Command:
 // target/release/basilisk
@@ -52,6 +57,16 @@ pub trait WeightInfo {
     fn on_trade_multiple_tokens(b: u32) -> Weight;
     fn on_liquidity_changed_multiple_tokens(b: u32) -> Weight;
     fn get_entry() -> Weight;
+    fn add_custom_period(b: u32) -> Weight;
+    fn remove_custom_period(b: u32) -> Weight;
+    fn get_twap() -> Weight;
+    fn remove_oracle() -> Weight;
+    fn add_feeder() -> Weight;
+    fn remove_feeder() -> Weight;
+    fn submit_price() -> Weight;
+    fn set_cross_rate_path() -> Weight;
+    fn clear_external_submissions(b: u32) -> Weight;
+    fn update_twap_checkpoints() -> Weight;
 }
 
 pub struct BasiliskWeight<T>(PhantomData<T>);
@@ -64,25 +79,77 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
         Weight::from_ref_time(12_487_000 as u64) // Standard Error: 24_000
             .saturating_add(Weight::from_ref_time(39_930_000 as u64).saturating_mul(b as u64))
             .saturating_add(T::DbWeight::get().reads(1 as u64))
-            .saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(b as u64)))
+            .saturating_add(T::DbWeight::get().reads((5 as u64).saturating_mul(b as u64)))
             .saturating_add(T::DbWeight::get().writes(1 as u64))
-            .saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(b as u64)))
+            .saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(b as u64)))
     }
     fn on_trade_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_042_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(507_000 as u64).saturating_mul(b as u64))
-            .saturating_add(T::DbWeight::get().reads(1 as u64))
-            .saturating_add(T::DbWeight::get().writes(1 as u64))
+            .saturating_add(T::DbWeight::get().reads(3 as u64))
+            .saturating_add(T::DbWeight::get().writes(2 as u64))
     }
     fn on_liquidity_changed_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_385_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(506_000 as u64).saturating_mul(b as u64))
-            .saturating_add(T::DbWeight::get().reads(1 as u64))
-            .saturating_add(T::DbWeight::get().writes(1 as u64))
+            .saturating_add(T::DbWeight::get().reads(3 as u64))
+            .saturating_add(T::DbWeight::get().writes(2 as u64))
     }
     fn get_entry() -> Weight {
         Weight::from_ref_time(23_575_000 as u64).saturating_add(T::DbWeight::get().reads(2 as u64))
     }
+    fn add_custom_period(b: u32) -> Weight {
+        Weight::from_ref_time(21_240_000 as u64) // Standard Error: 6_000
+            .saturating_add(Weight::from_ref_time(11_876_000 as u64).saturating_mul(b as u64))
+            .saturating_add(T::DbWeight::get().reads(2 as u64))
+            .saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(b as u64)))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+            .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+    }
+    fn remove_custom_period(b: u32) -> Weight {
+        Weight::from_ref_time(19_817_000 as u64) // Standard Error: 5_000
+            .saturating_add(Weight::from_ref_time(9_412_000 as u64).saturating_mul(b as u64))
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(b as u64)))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+            .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+    }
+    fn get_twap() -> Weight {
+        Weight::from_ref_time(18_964_000 as u64).saturating_add(T::DbWeight::get().reads(1 as u64))
+    }
+    fn remove_oracle() -> Weight {
+        Weight::from_ref_time(34_710_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(10 as u64))
+    }
+    fn add_feeder() -> Weight {
+        Weight::from_ref_time(16_383_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn remove_feeder() -> Weight {
+        Weight::from_ref_time(16_921_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn submit_price() -> Weight {
+        Weight::from_ref_time(48_652_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+    fn set_cross_rate_path() -> Weight {
+        Weight::from_ref_time(14_287_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn clear_external_submissions(b: u32) -> Weight {
+        Weight::from_ref_time(9_412_000 as u64)
+            .saturating_mul(b as u64)
+            .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+    }
+    fn update_twap_checkpoints() -> Weight {
+        Weight::from_ref_time(18_964_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -94,23 +161,75 @@ impl WeightInfo for () {
         Weight::from_ref_time(12_487_000 as u64) // Standard Error: 24_000
             .saturating_add(Weight::from_ref_time(39_930_000 as u64).saturating_mul(b as u64))
             .saturating_add(RocksDbWeight::get().reads(1 as u64))
-            .saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(b as u64)))
+            .saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(b as u64)))
             .saturating_add(RocksDbWeight::get().writes(1 as u64))
-            .saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(b as u64)))
+            .saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(b as u64)))
     }
     fn on_trade_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_042_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(507_000 as u64).saturating_mul(b as u64))
-            .saturating_add(RocksDbWeight::get().reads(1 as u64))
-            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads(3 as u64))
+            .saturating_add(RocksDbWeight::get().writes(2 as u64))
     }
     fn on_liquidity_changed_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_385_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(506_000 as u64).saturating_mul(b as u64))
-            .saturating_add(RocksDbWeight::get().reads(1 as u64))
-            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads(3 as u64))
+            .saturating_add(RocksDbWeight::get().writes(2 as u64))
     }
     fn get_entry() -> Weight {
         Weight::from_ref_time(23_575_000 as u64).saturating_add(RocksDbWeight::get().reads(2 as u64))
     }
+    fn add_custom_period(b: u32) -> Weight {
+        Weight::from_ref_time(21_240_000 as u64) // Standard Error: 6_000
+            .saturating_add(Weight::from_ref_time(11_876_000 as u64).saturating_mul(b as u64))
+            .saturating_add(RocksDbWeight::get().reads(2 as u64))
+            .saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(b as u64)))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+    }
+    fn remove_custom_period(b: u32) -> Weight {
+        Weight::from_ref_time(19_817_000 as u64) // Standard Error: 5_000
+            .saturating_add(Weight::from_ref_time(9_412_000 as u64).saturating_mul(b as u64))
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(b as u64)))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+    }
+    fn get_twap() -> Weight {
+        Weight::from_ref_time(18_964_000 as u64).saturating_add(RocksDbWeight::get().reads(1 as u64))
+    }
+    fn remove_oracle() -> Weight {
+        Weight::from_ref_time(34_710_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(10 as u64))
+    }
+    fn add_feeder() -> Weight {
+        Weight::from_ref_time(16_383_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn remove_feeder() -> Weight {
+        Weight::from_ref_time(16_921_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn submit_price() -> Weight {
+        Weight::from_ref_time(48_652_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+    fn set_cross_rate_path() -> Weight {
+        Weight::from_ref_time(14_287_000 as u64).saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn clear_external_submissions(b: u32) -> Weight {
+        Weight::from_ref_time(9_412_000 as u64)
+            .saturating_mul(b as u64)
+            .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+    }
+    fn update_twap_checkpoints() -> Weight {
+        Weight::from_ref_time(18_964_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
 }
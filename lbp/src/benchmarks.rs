@@ -0,0 +1,179 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use orml_traits::MultiCurrencyExtended;
+use sp_runtime::traits::Saturating;
+pub const ONE: Balance = 1_000_000_000_000;
+
+/// Accumulated asset
+const ASSET_A: u32 = 1;
+/// Sold asset
+const ASSET_B: u32 = 2;
+
+const INITIAL_WEIGHT: LBPWeight = 10_000_000;
+const FINAL_WEIGHT: LBPWeight = 75_000_000;
+
+const SALE_DURATION: u32 = 10;
+
+benchmarks! {
+    where_clause { where
+        T::AssetId: From<u32>,
+        T::MultiCurrency: MultiCurrencyExtended<T::AccountId, Amount=i128>,
+        T: crate::pallet::Config,
+    }
+  create_pool {
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1)?;
+        let fee_collector: T::AccountId = account("fee_collector", 2, 2);
+        let origin = T::CreatePoolOrigin::try_successful_origin().map_err(|_| "origin is not available")?;
+  }:  _<T::RuntimeOrigin>(origin, owner.clone(), ASSET_A.into(), 1_000 * ONE, ASSET_B.into(), 2_000 * ONE, INITIAL_WEIGHT, FINAL_WEIGHT, Permill::from_percent(2), fee_collector, 0)
+    verify {
+        assert!(<PoolData<T>>::contains_key(pool_id::<T>()));
+    }
+
+    update_pool_data {
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1)?;
+        create_pool_of::<T>(&owner)?;
+        let new_owner: T::AccountId = account("new_owner", 3, 3);
+        // worst case: the fee collector is replaced
+        let new_fee_collector: T::AccountId = account("new_fee_collector", 4, 4);
+        let start = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+        let end = start.saturating_add(SALE_DURATION.into());
+  }:  _(RawOrigin::Signed(owner), pool_id::<T>(), Some(new_owner.clone()), Some(start), Some(end), Some(FINAL_WEIGHT), Some(INITIAL_WEIGHT), Some(Permill::from_percent(5)), Some(new_fee_collector.clone()), Some(ONE))
+    verify {
+        let pool = <PoolData<T>>::get(pool_id::<T>()).ok_or("pool not found")?;
+        assert_eq!(pool.owner, new_owner);
+        assert_eq!(pool.fee_collector, new_fee_collector);
+    }
+
+    add_liquidity {
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1)?;
+        create_pool_of::<T>(&owner)?;
+  }:  _(RawOrigin::Signed(owner), (ASSET_A.into(), 100 * ONE), (ASSET_B.into(), 200 * ONE))
+    verify {
+        assert_eq!(T::MultiCurrency::free_balance(ASSET_A.into(), &pool_id::<T>()), 1_100 * ONE);
+    }
+
+    remove_liquidity {
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1)?;
+        let trader: T::AccountId = create_account_with_balances::<T>("trader", 2)?;
+        create_pool_of::<T>(&owner)?;
+        let (start, end) = start_sale::<T>(&owner)?;
+        // worst case: the fee collector received fees, which are unlocked
+        frame_system::Pallet::<T>::set_block_number(start);
+        crate::Pallet::<T>::sell(RawOrigin::Signed(trader).into(), ASSET_A.into(), ASSET_B.into(), 10 * ONE, 0)?;
+        frame_system::Pallet::<T>::set_block_number(end.saturating_add(1u32.into()));
+  }:  _(RawOrigin::Signed(owner), pool_id::<T>())
+    verify {
+        assert!(!<PoolData<T>>::contains_key(pool_id::<T>()));
+    }
+
+    sell {
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1)?;
+        let trader: T::AccountId = create_account_with_balances::<T>("trader", 2)?;
+        create_pool_of::<T>(&owner)?;
+        let (start, _) = start_sale::<T>(&owner)?;
+        frame_system::Pallet::<T>::set_block_number(start);
+  }:  _(RawOrigin::Signed(trader.clone()), ASSET_A.into(), ASSET_B.into(), 10 * ONE, 0)
+    verify {
+        assert!(T::MultiCurrency::free_balance(ASSET_B.into(), &trader) > 1_000_000 * ONE);
+    }
+
+    buy {
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1)?;
+        let trader: T::AccountId = create_account_with_balances::<T>("trader", 2)?;
+        create_pool_of::<T>(&owner)?;
+        let (start, _) = start_sale::<T>(&owner)?;
+        frame_system::Pallet::<T>::set_block_number(start);
+  }:  _(RawOrigin::Signed(trader.clone()), ASSET_B.into(), ASSET_A.into(), 10 * ONE, Balance::MAX)
+    verify {
+        assert_eq!(T::MultiCurrency::free_balance(ASSET_B.into(), &trader), 1_000_010 * ONE);
+    }
+}
+
+fn pool_id<T: Config>() -> T::AccountId
+where
+    T::AssetId: From<u32>,
+{
+    Pallet::<T>::get_pair_id(ASSET_A.into(), ASSET_B.into())
+}
+
+/// Pool of 1_000 `ASSET_A` and 2_000 `ASSET_B`, the sale is not scheduled yet
+fn create_pool_of<T: Config>(owner: &T::AccountId) -> DispatchResult
+where
+    T::AssetId: From<u32>,
+{
+    let origin = T::CreatePoolOrigin::try_successful_origin().map_err(|_| "origin is not available")?;
+    crate::Pallet::<T>::create_pool(
+        origin,
+        owner.clone(),
+        ASSET_A.into(),
+        1_000 * ONE,
+        ASSET_B.into(),
+        2_000 * ONE,
+        INITIAL_WEIGHT,
+        FINAL_WEIGHT,
+        Permill::from_percent(2),
+        account("fee_collector", 2, 2),
+        0,
+    )
+}
+
+/// Schedules the sale of the pool to start in the next block. Returns its start and end.
+fn start_sale<T: Config>(owner: &T::AccountId) -> Result<(BlockNumberOf<T>, BlockNumberOf<T>), DispatchError>
+where
+    T::AssetId: From<u32>,
+{
+    let start = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+    let end = start.saturating_add(SALE_DURATION.into());
+    crate::Pallet::<T>::update_pool_data(
+        RawOrigin::Signed(owner.clone()).into(),
+        pool_id::<T>(),
+        None,
+        Some(start),
+        Some(end),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+
+    Ok((start, end))
+}
+
+fn create_account_with_balances<T: Config>(name: &'static str, index: u32) -> Result<T::AccountId, DispatchError>
+where
+    T::AssetId: From<u32>,
+    T::MultiCurrency: MultiCurrencyExtended<T::AccountId, Amount = i128>,
+{
+    let account_id: T::AccountId = account(name, index, index);
+
+    T::MultiCurrency::update_balance(ASSET_A.into(), &account_id, 1_000_000 * ONE as i128)?;
+    T::MultiCurrency::update_balance(ASSET_B.into(), &account_id, 1_000_000 * ONE as i128)?;
+
+    Ok(account_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pallet;
+    use crate::tests::mock::*;
+    use frame_benchmarking::impl_benchmark_test_suite;
+
+    impl_benchmark_test_suite!(Pallet, super::ExtBuilder::default().build(), super::Test);
+}
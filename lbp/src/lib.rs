@@ -0,0 +1,902 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// # LBP pallet
+// ## General description
+// This pallet provides liquidity bootstrapping pools (LBP) - weighted pools of two assets for fair token launches.
+// The weight of the accumulated asset moves linearly from the initial to the final weight during the sale, which
+// lets the price of the sold asset fall over time unless it is bought.
+//
+// ## Notes
+// Pools are created by `CreatePoolOrigin` on behalf of a pool owner. Only the owner can update the pool and manage
+// its liquidity - liquidity can be added until the sale starts and removed before the sale starts or after it ends.
+// Removing the liquidity destroys the pool.
+//
+// Trading is possible only between the `start` and `end` block of the sale. All fees are paid in the accumulated
+// asset and transferred to the fee collector, where they stay locked until the pool is destroyed. The higher
+// `RepayFee` is charged until the fee collector received `repay_target` of the accumulated asset.
+//
+// Every trade is reported to `AMMHandler` so that the oracles are fed.
+//
+// ## Dispatachable functions
+// * `create_pool` - create a new pool with initial liquidity.
+// * `update_pool_data` - update the parameters of a pool before the sale starts.
+// * `add_liquidity` - add liquidity to a pool before the sale starts.
+// * `remove_liquidity` - remove all liquidity and destroy a pool.
+// * `sell` - sell an asset to a pool.
+// * `buy` - buy an asset from a pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::too_many_arguments)]
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Get, LockIdentifier},
+    transactional,
+};
+use frame_system::ensure_signed;
+use hydradx_traits::{AccountIdFor, LockedBalance, OnTradeHandler, Source};
+use orml_traits::{MultiCurrency, MultiLockableCurrency};
+use sp_runtime::{traits::Zero, DispatchError, Permill};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarks;
+
+mod trade_execution;
+pub mod types;
+pub mod weights;
+
+pub use trade_execution::*;
+pub use types::{Balance, LBPWeight, Pool, MAX_WEIGHT};
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// LBP account id identifier.
+pub const POOL_IDENTIFIER: &[u8] = b"lbp";
+
+/// Lock of the fees received by the fee collector of a pool.
+pub const COLLECTOR_LOCK_ID: LockIdentifier = *b"lbpcllct";
+
+/// Oracle source of the LBP pools.
+pub const SOURCE: Source = *b"lbp_pool";
+
+pub type PoolOf<T> = Pool<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, BlockNumberOf<T>>;
+type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
+
+/// Amounts of a trade.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TradeAmounts {
+    /// Amount transferred from the trader to the pool.
+    pub to_pool: Balance,
+    /// Amount transferred from the pool to the trader.
+    pub from_pool: Balance,
+    /// Fee in the accumulated asset.
+    pub fee: Balance,
+    /// The fee is paid from the pool reserve (`true`) or by the trader (`false`).
+    pub fee_from_pool: bool,
+}
+
+impl TradeAmounts {
+    /// Amount paid by the trader, including the fee.
+    pub fn amount_in(&self) -> Balance {
+        if self.fee_from_pool {
+            self.to_pool
+        } else {
+            self.to_pool.saturating_add(self.fee)
+        }
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use codec::HasCompact;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::OriginFor;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Identifier for the class of asset.
+        type AssetId: Member
+            + Parameter
+            + Ord
+            + Default
+            + Copy
+            + HasCompact
+            + MaybeSerializeDeserialize
+            + MaxEncodedLen
+            + TypeInfo;
+
+        /// Multi currency mechanism, the fees of the fee collectors are locked.
+        type MultiCurrency: MultiLockableCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
+
+        /// Locked balance of the fee collectors.
+        type LockedBalance: LockedBalance<Self::AssetId, Self::AccountId, Balance>;
+
+        /// Account ID constructor.
+        type AssetPairAccountId: AccountIdFor<Vec<Self::AssetId>, AccountId = Self::AccountId>;
+
+        /// The origin which can create a new pool.
+        type CreatePoolOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Fee rate charged until the fee collector received the repay target of a pool.
+        #[pallet::constant]
+        type RepayFee: Get<Permill>;
+
+        /// Minimum trading amount.
+        #[pallet::constant]
+        type MinTradingLimit: Get<Balance>;
+
+        /// Minimum pool liquidity.
+        #[pallet::constant]
+        type MinPoolLiquidity: Get<Balance>;
+
+        /// Max fraction of the pool reserve that can be sold in a single transaction.
+        #[pallet::constant]
+        type MaxInRatio: Get<u128>;
+
+        /// Max fraction of the pool reserve that can be bought in a single transaction.
+        #[pallet::constant]
+        type MaxOutRatio: Get<u128>;
+
+        /// Handler called when a trade is executed.
+        type AMMHandler: OnTradeHandler<Self::AssetId, Balance>;
+
+        /// Weight information for the extrinsics.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Parameters of the pools, keyed by the pool account.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_data)]
+    pub type PoolData<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolOf<T>>;
+
+    /// Fee collectors and the accumulated assets they collect fees in.
+    /// A fee collector can collect fees of a single pool per asset, as the fees are locked under the same lock.
+    #[pallet::storage]
+    #[pallet::getter(fn fee_collector_with_asset)]
+    pub type FeeCollectorWithAsset<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AssetId, bool, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A pool was created.
+        PoolCreated { pool: T::AccountId, data: PoolOf<T> },
+        /// Parameters of a pool were updated.
+        PoolUpdated { pool: T::AccountId, data: PoolOf<T> },
+        /// Liquidity was added to a pool.
+        LiquidityAdded {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b: Balance,
+        },
+        /// All liquidity was removed from a pool and the pool was destroyed.
+        LiquidityRemoved {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b: Balance,
+        },
+        /// Sell trade executed. `amount_in` includes the fee if it is paid in `asset_in`.
+        SellExecuted {
+            who: T::AccountId,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            amount_out: Balance,
+            fee_asset: T::AssetId,
+            fee: Balance,
+        },
+        /// Buy trade executed. `amount_in` includes the fee if it is paid in `asset_in`.
+        BuyExecuted {
+            who: T::AccountId,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            amount_out: Balance,
+            fee_asset: T::AssetId,
+            fee: Balance,
+        },
+    }
+
+    #[pallet::error]
+    #[cfg_attr(test, derive(PartialEq, Eq))]
+    pub enum Error<T> {
+        /// Creating a pool with same assets is not allowed.
+        CannotCreatePoolWithSameAssets,
+        /// A pool with given assets already exists.
+        PoolAlreadyExists,
+        /// A pool with given assets does not exist.
+        PoolNotFound,
+        /// Only the pool owner can perform this operation.
+        NotOwner,
+        /// The sale has already started.
+        SaleStarted,
+        /// The sale is running, liquidity can be removed before it starts or after it ends.
+        SaleNotEnded,
+        /// Trading is possible only while the sale is running.
+        SaleIsNotRunning,
+        /// The sale must start in the future and end after it starts.
+        InvalidBlockRange,
+        /// Weights must be between zero and `MAX_WEIGHT`, exclusive.
+        InvalidWeight,
+        /// Calculation of the weights failed.
+        WeightCalculationError,
+        /// No pool parameters to update are provided.
+        NothingToUpdate,
+        /// The fee collector already collects fees of another pool in the same asset.
+        FeeCollectorWithAssetAlreadyUsed,
+        /// Liquidity has not reached the required minimum.
+        InsufficientLiquidity,
+        /// Amount of liquidity to add must not be zero.
+        CannotAddZeroLiquidity,
+        /// Balance of an asset is not sufficient to perform the operation.
+        InsufficientAssetBalance,
+        /// Amount is less than the minimum trading amount configured.
+        InsufficientTradingAmount,
+        /// Minimum limit has not been reached during trade.
+        BuyLimitNotReached,
+        /// Maximum limit has been exceeded during trade.
+        SellLimitExceeded,
+        /// Amount sold exceeds `MaxInRatio` of the pool reserve.
+        MaxInRatioExceeded,
+        /// Amount bought exceeds `MaxOutRatio` of the pool reserve.
+        MaxOutRatioExceeded,
+        /// Calculation of the amounts failed.
+        MathError,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a pool of `asset_a` and `asset_b` owned by `pool_owner`.
+        ///
+        /// `asset_a` is the accumulated asset. The initial liquidity is transferred from the pool owner.
+        /// The sale does not start until its start and end are set with `update_pool_data`.
+        ///
+        /// Parameters:
+        /// - `origin`: must be `CreatePoolOrigin`
+        /// - `pool_owner`: owner of the pool
+        /// - `asset_a`: accumulated asset
+        /// - `asset_a_amount`: initial liquidity of `asset_a`
+        /// - `asset_b`: sold asset
+        /// - `asset_b_amount`: initial liquidity of `asset_b`
+        /// - `initial_weight`: weight of `asset_a` at the start of the sale
+        /// - `final_weight`: weight of `asset_a` at the end of the sale
+        /// - `fee`: trade fee rate
+        /// - `fee_collector`: account receiving the fees
+        /// - `repay_target`: amount of fees charged with `RepayFee`
+        ///
+        /// Emits `PoolCreated` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+        #[transactional]
+        pub fn create_pool(
+            origin: OriginFor<T>,
+            pool_owner: T::AccountId,
+            asset_a: T::AssetId,
+            asset_a_amount: Balance,
+            asset_b: T::AssetId,
+            asset_b_amount: Balance,
+            initial_weight: LBPWeight,
+            final_weight: LBPWeight,
+            fee: Permill,
+            fee_collector: T::AccountId,
+            repay_target: Balance,
+        ) -> DispatchResult {
+            T::CreatePoolOrigin::ensure_origin(origin)?;
+
+            ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
+            ensure!(
+                asset_a_amount >= T::MinPoolLiquidity::get() && asset_b_amount >= T::MinPoolLiquidity::get(),
+                Error::<T>::InsufficientLiquidity
+            );
+
+            let pool_id = Self::get_pair_id(asset_a, asset_b);
+            ensure!(!<PoolData<T>>::contains_key(&pool_id), Error::<T>::PoolAlreadyExists);
+            ensure!(
+                !Self::fee_collector_with_asset(&fee_collector, asset_a),
+                Error::<T>::FeeCollectorWithAssetAlreadyUsed
+            );
+
+            let pool_data = Pool {
+                owner: pool_owner.clone(),
+                start: None,
+                end: None,
+                assets: (asset_a, asset_b),
+                initial_weight,
+                final_weight,
+                fee,
+                fee_collector: fee_collector.clone(),
+                repay_target,
+            };
+            Self::validate_pool_data(&pool_data)?;
+
+            ensure!(
+                T::MultiCurrency::free_balance(asset_a, &pool_owner) >= asset_a_amount,
+                Error::<T>::InsufficientAssetBalance
+            );
+            ensure!(
+                T::MultiCurrency::free_balance(asset_b, &pool_owner) >= asset_b_amount,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            <PoolData<T>>::insert(&pool_id, &pool_data);
+            <FeeCollectorWithAsset<T>>::insert(&fee_collector, asset_a, true);
+
+            T::MultiCurrency::transfer(asset_a, &pool_owner, &pool_id, asset_a_amount)?;
+            T::MultiCurrency::transfer(asset_b, &pool_owner, &pool_id, asset_b_amount)?;
+
+            Self::deposit_event(Event::PoolCreated {
+                pool: pool_id,
+                data: pool_data,
+            });
+
+            Ok(())
+        }
+
+        /// Update the parameters of a pool. Only the pool owner can update the pool, until the sale starts.
+        ///
+        /// Parameters:
+        /// - `origin`: pool owner
+        /// - `pool_id`: account of the pool
+        /// - `pool_owner`: new owner or None
+        /// - `start`: new first block of the sale or None
+        /// - `end`: new last block of the sale or None
+        /// - `initial_weight`: new initial weight or None
+        /// - `final_weight`: new final weight or None
+        /// - `fee`: new trade fee rate or None
+        /// - `fee_collector`: new fee collector or None
+        /// - `repay_target`: new repay target or None
+        ///
+        /// Emits `PoolUpdated` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::update_pool_data())]
+        #[transactional]
+        pub fn update_pool_data(
+            origin: OriginFor<T>,
+            pool_id: T::AccountId,
+            pool_owner: Option<T::AccountId>,
+            start: Option<BlockNumberOf<T>>,
+            end: Option<BlockNumberOf<T>>,
+            initial_weight: Option<LBPWeight>,
+            final_weight: Option<LBPWeight>,
+            fee: Option<Permill>,
+            fee_collector: Option<T::AccountId>,
+            repay_target: Option<Balance>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <PoolData<T>>::try_mutate(&pool_id, |maybe_pool| -> DispatchResult {
+                let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+                ensure!(who == pool.owner, Error::<T>::NotOwner);
+                ensure!(!Self::has_pool_started(pool), Error::<T>::SaleStarted);
+                ensure!(
+                    pool_owner.is_some()
+                        || start.is_some()
+                        || end.is_some()
+                        || initial_weight.is_some()
+                        || final_weight.is_some()
+                        || fee.is_some()
+                        || fee_collector.is_some()
+                        || repay_target.is_some(),
+                    Error::<T>::NothingToUpdate
+                );
+
+                if let Some(fee_collector) = fee_collector {
+                    if fee_collector != pool.fee_collector {
+                        ensure!(
+                            !Self::fee_collector_with_asset(&fee_collector, pool.assets.0),
+                            Error::<T>::FeeCollectorWithAssetAlreadyUsed
+                        );
+                        <FeeCollectorWithAsset<T>>::remove(&pool.fee_collector, pool.assets.0);
+                        <FeeCollectorWithAsset<T>>::insert(&fee_collector, pool.assets.0, true);
+                        pool.fee_collector = fee_collector;
+                    }
+                }
+
+                pool.owner = pool_owner.unwrap_or_else(|| pool.owner.clone());
+                pool.start = start.or(pool.start);
+                pool.end = end.or(pool.end);
+                pool.initial_weight = initial_weight.unwrap_or(pool.initial_weight);
+                pool.final_weight = final_weight.unwrap_or(pool.final_weight);
+                pool.fee = fee.unwrap_or(pool.fee);
+                pool.repay_target = repay_target.unwrap_or(pool.repay_target);
+
+                Self::validate_pool_data(pool)?;
+
+                Self::deposit_event(Event::PoolUpdated {
+                    pool: pool_id.clone(),
+                    data: pool.clone(),
+                });
+
+                Ok(())
+            })
+        }
+
+        /// Add liquidity to a pool. Only the pool owner can add liquidity, until the sale starts.
+        ///
+        /// Parameters:
+        /// - `origin`: pool owner
+        /// - `amount_a`: asset and amount to add
+        /// - `amount_b`: asset and amount to add
+        ///
+        /// Emits `LiquidityAdded` event when successful.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+        #[transactional]
+        pub fn add_liquidity(
+            origin: OriginFor<T>,
+            amount_a: (T::AssetId, Balance),
+            amount_b: (T::AssetId, Balance),
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let (asset_a, amount_a) = amount_a;
+            let (asset_b, amount_b) = amount_b;
+
+            let pool_id = Self::get_pair_id(asset_a, asset_b);
+            let pool = Self::pool_data(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+            ensure!(who == pool.owner, Error::<T>::NotOwner);
+            ensure!(!Self::has_pool_started(&pool), Error::<T>::SaleStarted);
+            ensure!(
+                !amount_a.is_zero() || !amount_b.is_zero(),
+                Error::<T>::CannotAddZeroLiquidity
+            );
+
+            ensure!(
+                T::MultiCurrency::free_balance(asset_a, &who) >= amount_a,
+                Error::<T>::InsufficientAssetBalance
+            );
+            ensure!(
+                T::MultiCurrency::free_balance(asset_b, &who) >= amount_b,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            T::MultiCurrency::transfer(asset_a, &who, &pool_id, amount_a)?;
+            T::MultiCurrency::transfer(asset_b, &who, &pool_id, amount_b)?;
+
+            Self::deposit_event(Event::LiquidityAdded {
+                who,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+            });
+
+            Ok(())
+        }
+
+        /// Remove all liquidity from a pool and destroy it.
+        ///
+        /// Only the pool owner can remove liquidity, before the sale starts or after it ends.
+        /// The fees of the fee collector are unlocked.
+        ///
+        /// Parameters:
+        /// - `origin`: pool owner
+        /// - `pool_id`: account of the pool
+        ///
+        /// Emits `LiquidityRemoved` event when successful.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+        #[transactional]
+        pub fn remove_liquidity(origin: OriginFor<T>, pool_id: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let pool = Self::pool_data(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+            ensure!(who == pool.owner, Error::<T>::NotOwner);
+            ensure!(!Self::is_pool_running(&pool), Error::<T>::SaleNotEnded);
+
+            let (asset_a, asset_b) = pool.assets;
+            let amount_a = T::MultiCurrency::free_balance(asset_a, &pool_id);
+            let amount_b = T::MultiCurrency::free_balance(asset_b, &pool_id);
+
+            T::MultiCurrency::transfer(asset_a, &pool_id, &who, amount_a)?;
+            T::MultiCurrency::transfer(asset_b, &pool_id, &who, amount_b)?;
+
+            T::MultiCurrency::remove_lock(COLLECTOR_LOCK_ID, asset_a, &pool.fee_collector)?;
+
+            <FeeCollectorWithAsset<T>>::remove(&pool.fee_collector, asset_a);
+            <PoolData<T>>::remove(&pool_id);
+
+            Self::deposit_event(Event::LiquidityRemoved {
+                who,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+            });
+
+            Ok(())
+        }
+
+        /// Sell `amount` of `asset_in` to the pool of `asset_in` and `asset_out`.
+        ///
+        /// Parameters:
+        /// - `origin`: origin of the caller
+        /// - `asset_in`: ID of asset sold to the pool
+        /// - `asset_out`: ID of asset bought from the pool
+        /// - `amount`: amount of `asset_in` to sell, including the fee if it is paid in `asset_in`
+        /// - `min_bought`: minimum amount of `asset_out` to receive
+        ///
+        /// Emits `SellExecuted` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::sell()
+            .saturating_add(T::AMMHandler::on_trade_weight()))]
+        #[transactional]
+        pub fn sell(
+            origin: OriginFor<T>,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount: Balance,
+            min_bought: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let amounts = Self::calculate_sell_amounts(asset_in, asset_out, amount)?;
+            ensure!(amounts.from_pool >= min_bought, Error::<T>::BuyLimitNotReached);
+
+            let fee_asset = Self::execute_trade(&who, asset_in, asset_out, &amounts)?;
+
+            Self::deposit_event(Event::SellExecuted {
+                who,
+                asset_in,
+                asset_out,
+                amount_in: amounts.amount_in(),
+                amount_out: amounts.from_pool,
+                fee_asset,
+                fee: amounts.fee,
+            });
+
+            Ok(())
+        }
+
+        /// Buy `amount` of `asset_out` from the pool of `asset_in` and `asset_out`.
+        ///
+        /// Parameters:
+        /// - `origin`: origin of the caller
+        /// - `asset_out`: ID of asset bought from the pool
+        /// - `asset_in`: ID of asset sold to the pool
+        /// - `amount`: amount of `asset_out` to buy
+        /// - `max_sold`: maximum amount of `asset_in` to pay, including the fee if it is paid in `asset_in`
+        ///
+        /// Emits `BuyExecuted` event when successful.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::buy()
+            .saturating_add(T::AMMHandler::on_trade_weight()))]
+        #[transactional]
+        pub fn buy(
+            origin: OriginFor<T>,
+            asset_out: T::AssetId,
+            asset_in: T::AssetId,
+            amount: Balance,
+            max_sold: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let amounts = Self::calculate_buy_amounts(asset_in, asset_out, amount)?;
+            ensure!(amounts.amount_in() <= max_sold, Error::<T>::SellLimitExceeded);
+
+            let fee_asset = Self::execute_trade(&who, asset_in, asset_out, &amounts)?;
+
+            Self::deposit_event(Event::BuyExecuted {
+                who,
+                asset_in,
+                asset_out,
+                amount_in: amounts.amount_in(),
+                amount_out: amounts.from_pool,
+                fee_asset,
+                fee: amounts.fee,
+            });
+
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Account of the pool of given assets, independently of their order.
+    pub fn get_pair_id(asset_a: T::AssetId, asset_b: T::AssetId) -> T::AccountId {
+        let assets = if asset_a <= asset_b {
+            vec![asset_a, asset_b]
+        } else {
+            vec![asset_b, asset_a]
+        };
+        T::AssetPairAccountId::from_assets(&assets, Some(POOL_IDENTIFIER))
+    }
+
+    /// Weights of the accumulated and the sold asset of a pool at block `at`.
+    ///
+    /// The weight of the accumulated asset moves linearly from the initial weight at the start of the sale
+    /// to the final weight at its end.
+    pub fn calculate_weights(pool: &PoolOf<T>, at: BlockNumberOf<T>) -> Result<(LBPWeight, LBPWeight), DispatchError> {
+        let (start, end) = match (pool.start, pool.end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Err(Error::<T>::SaleIsNotRunning.into()),
+        };
+
+        let weight_a =
+            hydra_dx_math::lbp::calculate_linear_weights(start, end, pool.initial_weight, pool.final_weight, at)
+                .map_err(|_| Error::<T>::WeightCalculationError)?;
+        let weight_b = MAX_WEIGHT
+            .checked_sub(weight_a)
+            .ok_or(Error::<T>::WeightCalculationError)?;
+
+        Ok((weight_a, weight_b))
+    }
+
+    fn validate_pool_data(pool: &PoolOf<T>) -> DispatchResult {
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(
+            match (pool.start, pool.end) {
+                (Some(start), Some(end)) => now < start && start < end,
+                (None, None) => true,
+                _ => false,
+            },
+            Error::<T>::InvalidBlockRange
+        );
+        ensure!(
+            !pool.initial_weight.is_zero()
+                && pool.initial_weight < MAX_WEIGHT
+                && !pool.final_weight.is_zero()
+                && pool.final_weight < MAX_WEIGHT,
+            Error::<T>::InvalidWeight
+        );
+
+        Ok(())
+    }
+
+    fn has_pool_started(pool: &PoolOf<T>) -> bool {
+        match pool.start {
+            Some(start) => start <= frame_system::Pallet::<T>::block_number(),
+            None => false,
+        }
+    }
+
+    fn is_pool_running(pool: &PoolOf<T>) -> bool {
+        let now = frame_system::Pallet::<T>::block_number();
+        match (pool.start, pool.end) {
+            (Some(start), Some(end)) => start <= now && now <= end,
+            _ => false,
+        }
+    }
+
+    /// `RepayFee` is charged until the fee collector received the repay target of the pool.
+    fn trade_fee(pool: &PoolOf<T>) -> Permill {
+        let collected = T::LockedBalance::get_by_lock(COLLECTOR_LOCK_ID, pool.assets.0, pool.fee_collector.clone());
+        if collected < pool.repay_target {
+            T::RepayFee::get()
+        } else {
+            pool.fee
+        }
+    }
+
+    /// Returns the running pool of given assets, its account and the current weights of `asset_in` and `asset_out`.
+    fn get_running_pool(
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+    ) -> Result<(T::AccountId, PoolOf<T>, LBPWeight, LBPWeight), DispatchError> {
+        let pool_id = Self::get_pair_id(asset_in, asset_out);
+        let pool = Self::pool_data(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        ensure!(Self::is_pool_running(&pool), Error::<T>::SaleIsNotRunning);
+
+        let (weight_a, weight_b) = Self::calculate_weights(&pool, frame_system::Pallet::<T>::block_number())?;
+        let (weight_in, weight_out) = if asset_in == pool.assets.0 {
+            (weight_a, weight_b)
+        } else {
+            (weight_b, weight_a)
+        };
+
+        Ok((pool_id, pool, weight_in, weight_out))
+    }
+
+    /// Amounts of a sell of `amount` of `asset_in`, the fee is taken from the accumulated asset.
+    pub fn calculate_sell_amounts(
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount: Balance,
+    ) -> Result<TradeAmounts, DispatchError> {
+        ensure!(
+            amount >= T::MinTradingLimit::get(),
+            Error::<T>::InsufficientTradingAmount
+        );
+
+        let (pool_id, pool, weight_in, weight_out) = Self::get_running_pool(asset_in, asset_out)?;
+        let asset_in_reserve = T::MultiCurrency::free_balance(asset_in, &pool_id);
+        let asset_out_reserve = T::MultiCurrency::free_balance(asset_out, &pool_id);
+        let fee_rate = Self::trade_fee(&pool);
+
+        let amounts = if asset_in == pool.assets.0 {
+            let fee = fee_rate.mul_ceil(amount);
+            let to_pool = amount.checked_sub(fee).ok_or(Error::<T>::MathError)?;
+            let amount_out = hydra_dx_math::lbp::calculate_out_given_in(
+                asset_in_reserve,
+                asset_out_reserve,
+                weight_in,
+                weight_out,
+                to_pool,
+            )
+            .map_err(|_| Error::<T>::MathError)?;
+
+            TradeAmounts {
+                to_pool,
+                from_pool: amount_out,
+                fee,
+                fee_from_pool: false,
+            }
+        } else {
+            let amount_out = hydra_dx_math::lbp::calculate_out_given_in(
+                asset_in_reserve,
+                asset_out_reserve,
+                weight_in,
+                weight_out,
+                amount,
+            )
+            .map_err(|_| Error::<T>::MathError)?;
+            let fee = fee_rate.mul_ceil(amount_out);
+
+            TradeAmounts {
+                to_pool: amount,
+                from_pool: amount_out.checked_sub(fee).ok_or(Error::<T>::MathError)?,
+                fee,
+                fee_from_pool: true,
+            }
+        };
+
+        ensure!(
+            amounts.to_pool <= asset_in_reserve / T::MaxInRatio::get(),
+            Error::<T>::MaxInRatioExceeded
+        );
+        ensure!(
+            amounts.from_pool <= asset_out_reserve / T::MaxOutRatio::get(),
+            Error::<T>::MaxOutRatioExceeded
+        );
+
+        Ok(amounts)
+    }
+
+    /// Amounts of a buy of `amount` of `asset_out`, the fee is taken from the accumulated asset.
+    pub fn calculate_buy_amounts(
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount: Balance,
+    ) -> Result<TradeAmounts, DispatchError> {
+        ensure!(
+            amount >= T::MinTradingLimit::get(),
+            Error::<T>::InsufficientTradingAmount
+        );
+
+        let (pool_id, pool, weight_in, weight_out) = Self::get_running_pool(asset_in, asset_out)?;
+        let asset_in_reserve = T::MultiCurrency::free_balance(asset_in, &pool_id);
+        let asset_out_reserve = T::MultiCurrency::free_balance(asset_out, &pool_id);
+        let fee_rate = Self::trade_fee(&pool);
+
+        ensure!(
+            amount <= asset_out_reserve / T::MaxOutRatio::get(),
+            Error::<T>::MaxOutRatioExceeded
+        );
+
+        let amounts = if asset_out == pool.assets.0 {
+            let fee = fee_rate.mul_ceil(amount);
+            let amount_out_with_fee = amount.checked_add(fee).ok_or(Error::<T>::MathError)?;
+            let amount_in = hydra_dx_math::lbp::calculate_in_given_out(
+                asset_in_reserve,
+                asset_out_reserve,
+                weight_in,
+                weight_out,
+                amount_out_with_fee,
+            )
+            .map_err(|_| Error::<T>::MathError)?;
+
+            TradeAmounts {
+                to_pool: amount_in,
+                from_pool: amount,
+                fee,
+                fee_from_pool: true,
+            }
+        } else {
+            let amount_in = hydra_dx_math::lbp::calculate_in_given_out(
+                asset_in_reserve,
+                asset_out_reserve,
+                weight_in,
+                weight_out,
+                amount,
+            )
+            .map_err(|_| Error::<T>::MathError)?;
+
+            TradeAmounts {
+                to_pool: amount_in,
+                from_pool: amount,
+                fee: fee_rate.mul_ceil(amount_in),
+                fee_from_pool: false,
+            }
+        };
+
+        ensure!(
+            amounts.to_pool <= asset_in_reserve / T::MaxInRatio::get(),
+            Error::<T>::MaxInRatioExceeded
+        );
+
+        Ok(amounts)
+    }
+
+    /// Transfers the amounts of a trade and the fee to the fee collector, where the fee is locked.
+    /// Returns the fee asset.
+    fn execute_trade(
+        who: &T::AccountId,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amounts: &TradeAmounts,
+    ) -> Result<T::AssetId, DispatchError> {
+        let pool_id = Self::get_pair_id(asset_in, asset_out);
+        let pool = Self::pool_data(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let fee_asset = pool.assets.0;
+
+        ensure!(
+            T::MultiCurrency::free_balance(asset_in, who) >= amounts.amount_in(),
+            Error::<T>::InsufficientAssetBalance
+        );
+
+        T::MultiCurrency::transfer(asset_in, who, &pool_id, amounts.to_pool)?;
+        T::MultiCurrency::transfer(asset_out, &pool_id, who, amounts.from_pool)?;
+
+        if !amounts.fee.is_zero() {
+            let fee_payer = if amounts.fee_from_pool { &pool_id } else { who };
+            T::MultiCurrency::transfer(fee_asset, fee_payer, &pool.fee_collector, amounts.fee)?;
+
+            let collected = T::LockedBalance::get_by_lock(COLLECTOR_LOCK_ID, fee_asset, pool.fee_collector.clone());
+            T::MultiCurrency::set_lock(
+                COLLECTOR_LOCK_ID,
+                fee_asset,
+                &pool.fee_collector,
+                collected.saturating_add(amounts.fee),
+            )?;
+        }
+
+        T::AMMHandler::on_trade(
+            SOURCE,
+            asset_in,
+            asset_out,
+            amounts.to_pool,
+            amounts.from_pool,
+            T::MultiCurrency::free_balance(asset_in, &pool_id),
+            T::MultiCurrency::free_balance(asset_out, &pool_id),
+        )
+        .map_err(|(_, e)| e)?;
+
+        Ok(fee_asset)
+    }
+}
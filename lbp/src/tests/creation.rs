@@ -0,0 +1,188 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event, Pool, MAX_WEIGHT};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::{DispatchError, Permill};
+
+fn create_pool(
+    origin: RuntimeOrigin,
+    pool: Pool<AccountId, AssetId, BlockNumber>,
+) -> frame_support::dispatch::DispatchResult {
+    LBP::create_pool(
+        origin,
+        pool.owner,
+        pool.assets.0,
+        1_000 * ONE,
+        pool.assets.1,
+        2_000 * ONE,
+        pool.initial_weight,
+        pool.final_weight,
+        pool.fee,
+        pool.fee_collector,
+        pool.repay_target,
+    )
+}
+
+#[test]
+fn create_pool_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(create_pool(RuntimeOrigin::root(), default_pool()));
+
+        // Assert
+        assert_eq!(LBP::get_pair_id(DAI, HDX), HDX_DAI_POOL);
+        assert_eq!(LBP::get_pair_id(HDX, DAI), HDX_DAI_POOL);
+        assert_eq!(LBP::pool_data(HDX_DAI_POOL), Some(default_pool()));
+        assert!(LBP::fee_collector_with_asset(CHARLIE, DAI));
+
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_000 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 2_000 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 9_000 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 8_000 * ONE);
+
+        expect_events(vec![Event::PoolCreated {
+            pool: HDX_DAI_POOL,
+            data: default_pool(),
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_origin_is_not_create_pool_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            create_pool(RuntimeOrigin::signed(ALICE), default_pool()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_assets_are_same() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            create_pool(
+                RuntimeOrigin::root(),
+                Pool {
+                    assets: (DAI, DAI),
+                    ..default_pool()
+                }
+            ),
+            Error::<Test>::CannotCreatePoolWithSameAssets
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_liquidity_is_below_minimum() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            LBP::create_pool(
+                RuntimeOrigin::root(),
+                ALICE,
+                DAI,
+                1_000 * ONE,
+                HDX,
+                999,
+                INITIAL_WEIGHT,
+                FINAL_WEIGHT,
+                Permill::zero(),
+                CHARLIE,
+                0
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_exists() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            create_pool(
+                RuntimeOrigin::root(),
+                Pool {
+                    assets: (HDX, DAI),
+                    fee_collector: BOB,
+                    ..default_pool()
+                }
+            ),
+            Error::<Test>::PoolAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_fee_collector_collects_same_asset_in_another_pool() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            create_pool(
+                RuntimeOrigin::root(),
+                Pool {
+                    assets: (DAI, DOT),
+                    ..default_pool()
+                }
+            ),
+            Error::<Test>::FeeCollectorWithAssetAlreadyUsed
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_weight_is_invalid() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            create_pool(
+                RuntimeOrigin::root(),
+                Pool {
+                    initial_weight: 0,
+                    ..default_pool()
+                }
+            ),
+            Error::<Test>::InvalidWeight
+        );
+        assert_noop!(
+            create_pool(
+                RuntimeOrigin::root(),
+                Pool {
+                    final_weight: MAX_WEIGHT,
+                    ..default_pool()
+                }
+            ),
+            Error::<Test>::InvalidWeight
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_owner_has_insufficient_balance() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            create_pool(
+                RuntimeOrigin::root(),
+                Pool {
+                    owner: CHARLIE,
+                    ..default_pool()
+                }
+            ),
+            Error::<Test>::InsufficientAssetBalance
+        );
+    });
+}
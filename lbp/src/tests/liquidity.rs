@@ -0,0 +1,186 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn add_liquidity_should_work() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(LBP::add_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            (HDX, 500 * ONE),
+            (DAI, 100 * ONE)
+        ));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_100 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 2_500 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 8_900 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 7_500 * ONE);
+
+        expect_events(vec![Event::LiquidityAdded {
+            who: ALICE,
+            asset_a: HDX,
+            asset_b: DAI,
+            amount_a: 500 * ONE,
+            amount_b: 100 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn add_liquidity_should_work_when_one_amount_is_zero() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(LBP::add_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            (DAI, 0),
+            (HDX, 500 * ONE)
+        ));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_000 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 2_500 * ONE);
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_both_amounts_are_zero() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::add_liquidity(RuntimeOrigin::signed(ALICE), (DAI, 0), (HDX, 0)),
+            Error::<Test>::CannotAddZeroLiquidity
+        );
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_caller_is_not_owner() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::add_liquidity(RuntimeOrigin::signed(BOB), (DAI, ONE), (HDX, ONE)),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::add_liquidity(RuntimeOrigin::signed(ALICE), (DOT, ONE), (HDX, ONE)),
+            Error::<Test>::PoolNotFound
+        );
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_sale_started() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+
+            // Act & Assert
+            assert_noop!(
+                LBP::add_liquidity(RuntimeOrigin::signed(ALICE), (DAI, ONE), (HDX, ONE)),
+                Error::<Test>::SaleStarted
+            );
+        });
+}
+
+#[test]
+fn remove_liquidity_should_destroy_pool() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(LBP::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX_DAI_POOL));
+
+        // Assert
+        assert_eq!(LBP::pool_data(HDX_DAI_POOL), None);
+        assert!(!LBP::fee_collector_with_asset(CHARLIE, DAI));
+
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 0);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 0);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 10_000 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 10_000 * ONE);
+
+        expect_events(vec![Event::LiquidityRemoved {
+            who: ALICE,
+            asset_a: DAI,
+            asset_b: HDX,
+            amount_a: 1_000 * ONE,
+            amount_b: 2_000 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_sale_is_running() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_END);
+
+            // Act & Assert
+            assert_noop!(
+                LBP::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX_DAI_POOL),
+                Error::<Test>::SaleNotEnded
+            );
+        });
+}
+
+#[test]
+fn remove_liquidity_should_unlock_collected_fees_when_sale_ended() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            assert_ok!(LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, 0));
+            let fee = collected_fees(CHARLIE, DAI);
+            assert!(fee > 0);
+            System::set_block_number(SALE_END + 1);
+
+            // Act
+            assert_ok!(LBP::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX_DAI_POOL));
+
+            // Assert
+            assert_eq!(collected_fees(CHARLIE, DAI), 0);
+            assert_eq!(Tokens::free_balance(DAI, &CHARLIE), fee);
+            assert_eq!(LBP::pool_data(HDX_DAI_POOL), None);
+        });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_caller_is_not_owner() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::remove_liquidity(RuntimeOrigin::signed(BOB), HDX_DAI_POOL),
+            Error::<Test>::NotOwner
+        );
+    });
+}
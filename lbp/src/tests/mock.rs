@@ -0,0 +1,333 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as lbp;
+use crate::{Config, LBPWeight, Pool, PoolOf};
+use frame_support::{
+    assert_ok, parameter_types,
+    traits::{ConstU32, Everything, GenesisBuild, LockIdentifier, Nothing},
+    weights::Weight,
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use hydradx_traits::{AccountIdFor, LockedBalance, OnTradeHandler, Source};
+use orml_tokens::AccountData;
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, Permill,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Amount = i128;
+pub type AssetId = u32;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+
+pub const HDX: AssetId = 0;
+/// Accumulated asset of the default pool
+pub const DAI: AssetId = 2;
+pub const DOT: AssetId = 3;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+
+/// Account of the HDX/DAI pool created by `AccountIdConstructor`
+pub const HDX_DAI_POOL: AccountId = 1_000_002;
+
+pub const INITIAL_WEIGHT: LBPWeight = 10_000_000;
+pub const FINAL_WEIGHT: LBPWeight = 75_000_000;
+pub const SALE_START: BlockNumber = 10;
+pub const SALE_END: BlockNumber = 20;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+     Block = Block,
+     NodeBlock = Block,
+     UncheckedExtrinsic = UncheckedExtrinsic,
+     {
+         System: frame_system,
+         LBP: lbp,
+         Tokens: orml_tokens,
+     }
+);
+
+/// (source, asset_a, asset_b, amount_a, amount_b, liquidity_a, liquidity_b)
+pub type OracleCall = (Source, AssetId, AssetId, Balance, Balance, Balance, Balance);
+
+thread_local! {
+    pub static TRADES: RefCell<Vec<OracleCall>> = RefCell::new(Vec::new());
+}
+
+parameter_types! {
+    pub RepayFee: Permill = Permill::from_percent(20);
+    pub const MinTradingLimit: Balance = 1_000;
+    pub const MinPoolLiquidity: Balance = 1_000;
+    pub const MaxInRatio: u128 = 3;
+    pub const MaxOutRatio: u128 = 3;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type AssetId = AssetId;
+    type MultiCurrency = Tokens;
+    type LockedBalance = TokensLocks;
+    type AssetPairAccountId = AccountIdConstructor;
+    type CreatePoolOrigin = EnsureRoot<AccountId>;
+    type RepayFee = RepayFee;
+    type MinTradingLimit = MinTradingLimit;
+    type MinPoolLiquidity = MinPoolLiquidity;
+    type MaxInRatio = MaxInRatio;
+    type MaxOutRatio = MaxOutRatio;
+    type AMMHandler = MockOracle;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 63;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_type_with_key! {
+    pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+        1
+    };
+}
+
+impl orml_tokens::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type Amount = Amount;
+    type CurrencyId = AssetId;
+    type WeightInfo = ();
+    type ExistentialDeposits = ExistentialDeposits;
+    type MaxLocks = ConstU32<10>;
+    type DustRemovalWhitelist = Nothing;
+    type ReserveIdentifier = [u8; 8];
+    type MaxReserves = MaxReserves;
+    type CurrencyHooks = ();
+}
+
+pub struct TokensLocks;
+
+impl LockedBalance<AssetId, AccountId, Balance> for TokensLocks {
+    fn get_by_lock(lock_id: LockIdentifier, currency_id: AssetId, who: AccountId) -> Balance {
+        Tokens::locks(who, currency_id)
+            .into_iter()
+            .find(|lock| lock.id == lock_id)
+            .map(|lock| lock.amount)
+            .unwrap_or_default()
+    }
+}
+
+pub struct AccountIdConstructor;
+
+impl AccountIdFor<Vec<AssetId>> for AccountIdConstructor {
+    type AccountId = AccountId;
+
+    fn from_assets(assets: &Vec<AssetId>, _identifier: Option<&[u8]>) -> Self::AccountId {
+        1_000_000 + (assets[0] * 1_000 + assets[1]) as u64
+    }
+
+    fn name(assets: &Vec<AssetId>, identifier: Option<&[u8]>) -> Vec<u8> {
+        let mut buf: Vec<u8> = identifier.map(|ident| ident.to_vec()).unwrap_or_default();
+        buf.extend_from_slice(&(assets[0]).to_le_bytes());
+        buf.extend_from_slice(&(assets[1]).to_le_bytes());
+
+        buf
+    }
+}
+
+/// Records the trades reported by the pallet.
+pub struct MockOracle;
+
+impl OnTradeHandler<AssetId, Balance> for MockOracle {
+    fn on_trade(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+        amount_a: Balance,
+        amount_b: Balance,
+        liquidity_a: Balance,
+        liquidity_b: Balance,
+    ) -> Result<Weight, (Weight, DispatchError)> {
+        TRADES.with(|v| {
+            v.borrow_mut()
+                .push((source, asset_a, asset_b, amount_a, amount_b, liquidity_a, liquidity_b))
+        });
+        Ok(Weight::zero())
+    }
+
+    fn on_trade_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+    pools: Vec<(PoolOf<Test>, Balance, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        // If eg. tests running on one thread only, this thread local is shared.
+        // let's make sure that it is empty for each test case
+        TRADES.with(|v| v.borrow_mut().clear());
+
+        Self {
+            endowed_accounts: vec![
+                (ALICE, HDX, 10_000 * ONE),
+                (ALICE, DAI, 10_000 * ONE),
+                (ALICE, DOT, 10_000 * ONE),
+                (BOB, HDX, 10_000 * ONE),
+                (BOB, DAI, 10_000 * ONE),
+            ],
+            pools: vec![],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn with_pool(mut self, pool: PoolOf<Test>, amount_a: Balance, amount_b: Balance) -> Self {
+        self.pools.push((pool, amount_a, amount_b));
+        self
+    }
+
+    /// ALICE's pool of 1_000 DAI and 2_000 HDX, DAI is accumulated.
+    pub fn with_default_pool(self) -> Self {
+        self.with_pool(default_pool(), 1_000 * ONE, 2_000 * ONE)
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        orml_tokens::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut r: sp_io::TestExternalities = t.into();
+
+        r.execute_with(|| {
+            System::set_block_number(1);
+
+            for (pool, amount_a, amount_b) in self.pools {
+                assert_ok!(LBP::create_pool(
+                    RuntimeOrigin::root(),
+                    pool.owner,
+                    pool.assets.0,
+                    amount_a,
+                    pool.assets.1,
+                    amount_b,
+                    pool.initial_weight,
+                    pool.final_weight,
+                    pool.fee,
+                    pool.fee_collector,
+                    pool.repay_target,
+                ));
+                if pool.start.is_some() || pool.end.is_some() {
+                    assert_ok!(LBP::update_pool_data(
+                        RuntimeOrigin::signed(pool.owner),
+                        LBP::get_pair_id(pool.assets.0, pool.assets.1),
+                        None,
+                        pool.start,
+                        pool.end,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                    ));
+                }
+            }
+        });
+
+        r
+    }
+}
+
+/// Parameters of the default pool - the sale is not scheduled and the repay target is zero.
+pub fn default_pool() -> PoolOf<Test> {
+    Pool {
+        owner: ALICE,
+        start: None,
+        end: None,
+        assets: (DAI, HDX),
+        initial_weight: INITIAL_WEIGHT,
+        final_weight: FINAL_WEIGHT,
+        fee: Permill::from_rational(2u32, 1_000u32),
+        fee_collector: CHARLIE,
+        repay_target: 0,
+    }
+}
+
+/// Parameters of the default pool with the sale scheduled from `SALE_START` to `SALE_END`.
+pub fn scheduled_pool() -> PoolOf<Test> {
+    Pool {
+        start: Some(SALE_START),
+        end: Some(SALE_END),
+        ..default_pool()
+    }
+}
+
+pub fn collected_fees(who: AccountId, asset: AssetId) -> Balance {
+    TokensLocks::get_by_lock(crate::COLLECTOR_LOCK_ID, asset, who)
+}
+
+pub fn last_trade() -> Option<OracleCall> {
+    TRADES.with(|v| v.borrow().last().copied())
+}
+
+pub fn expect_events(e: Vec<RuntimeEvent>) {
+    test_utils::expect_events::<RuntimeEvent, Test>(e);
+}
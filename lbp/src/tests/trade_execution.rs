@@ -0,0 +1,140 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use frame_support::assert_ok;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn calculate_sell_should_return_amount_out_without_fee() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let expected = LBP::calculate_sell_amounts(HDX, DAI, 10 * ONE).unwrap();
+
+            // Act & Assert
+            assert_eq!(
+                LBP::calculate_sell(PoolType::LBP, HDX, DAI, 10 * ONE),
+                Ok(expected.from_pool)
+            );
+        });
+}
+
+#[test]
+fn calculate_buy_should_return_amount_in_with_fee() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let expected = LBP::calculate_buy_amounts(DAI, HDX, 10 * ONE).unwrap();
+
+            // Act & Assert
+            assert_eq!(
+                LBP::calculate_buy(PoolType::LBP, DAI, HDX, 10 * ONE),
+                Ok(expected.to_pool + expected.fee)
+            );
+        });
+}
+
+#[test]
+fn execute_sell_should_work() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let amount_out = LBP::calculate_sell(PoolType::LBP, DAI, HDX, 10 * ONE).unwrap();
+
+            // Act
+            assert_ok!(LBP::execute_sell(
+                RuntimeOrigin::signed(BOB),
+                PoolType::LBP,
+                DAI,
+                HDX,
+                10 * ONE,
+                amount_out
+            ));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), 10_000 * ONE + amount_out);
+        });
+}
+
+#[test]
+fn execute_buy_should_work() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let amount_in = LBP::calculate_buy(PoolType::LBP, DAI, HDX, 10 * ONE).unwrap();
+
+            // Act
+            assert_ok!(LBP::execute_buy(
+                RuntimeOrigin::signed(BOB),
+                PoolType::LBP,
+                DAI,
+                HDX,
+                10 * ONE,
+                amount_in
+            ));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), 10_010 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &BOB), 10_000 * ONE - amount_in);
+        });
+}
+
+#[test]
+fn trade_execution_should_not_support_other_pools() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            System::set_block_number(SALE_START);
+            assert_eq!(
+                LBP::calculate_sell(PoolType::XYK, HDX, DAI, 10 * ONE),
+                Err(ExecutorError::NotSupported)
+            );
+            assert_eq!(
+                LBP::calculate_buy(PoolType::Omnipool, HDX, DAI, 10 * ONE),
+                Err(ExecutorError::NotSupported)
+            );
+            assert_eq!(
+                LBP::execute_sell(RuntimeOrigin::signed(BOB), PoolType::XYK, HDX, DAI, 10 * ONE, 0),
+                Err(ExecutorError::NotSupported)
+            );
+            assert_eq!(
+                LBP::execute_buy(
+                    RuntimeOrigin::signed(BOB),
+                    PoolType::Stableswap(HDX),
+                    HDX,
+                    DAI,
+                    10 * ONE,
+                    u128::MAX
+                ),
+                Err(ExecutorError::NotSupported)
+            );
+        });
+}
@@ -0,0 +1,323 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event, Pool, SOURCE};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+/// Current weights of DAI and HDX in the default pool.
+fn weights() -> (u32, u32) {
+    LBP::calculate_weights(&scheduled_pool(), System::block_number()).unwrap()
+}
+
+fn pool_fee() -> Permill {
+    default_pool().fee
+}
+
+#[test]
+fn sell_accumulated_asset_should_take_fee_from_amount_in() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let (weight_dai, weight_hdx) = weights();
+            let fee = pool_fee().mul_ceil(10 * ONE);
+            let expected_out = hydra_dx_math::lbp::calculate_out_given_in(
+                1_000 * ONE,
+                2_000 * ONE,
+                weight_dai,
+                weight_hdx,
+                10 * ONE - fee,
+            )
+            .unwrap();
+
+            // Act
+            assert_ok!(LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, expected_out));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &BOB), 9_990 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &BOB), 10_000 * ONE + expected_out);
+            assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_010 * ONE - fee);
+            assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 2_000 * ONE - expected_out);
+            assert_eq!(Tokens::free_balance(DAI, &CHARLIE), fee);
+            assert_eq!(collected_fees(CHARLIE, DAI), fee);
+
+            assert_eq!(
+                last_trade(),
+                Some((
+                    SOURCE,
+                    DAI,
+                    HDX,
+                    10 * ONE - fee,
+                    expected_out,
+                    1_010 * ONE - fee,
+                    2_000 * ONE - expected_out
+                ))
+            );
+
+            expect_events(vec![Event::SellExecuted {
+                who: BOB,
+                asset_in: DAI,
+                asset_out: HDX,
+                amount_in: 10 * ONE,
+                amount_out: expected_out,
+                fee_asset: DAI,
+                fee,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn sell_for_accumulated_asset_should_take_fee_from_amount_out() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(15);
+            let (weight_dai, weight_hdx) = weights();
+            let amount_out =
+                hydra_dx_math::lbp::calculate_out_given_in(2_000 * ONE, 1_000 * ONE, weight_hdx, weight_dai, 10 * ONE)
+                    .unwrap();
+            let fee = pool_fee().mul_ceil(amount_out);
+
+            // Act
+            assert_ok!(LBP::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 10 * ONE, 0));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), 9_990 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &BOB), 10_000 * ONE + amount_out - fee);
+            assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 2_010 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_000 * ONE - amount_out);
+            assert_eq!(collected_fees(CHARLIE, DAI), fee);
+
+            expect_events(vec![Event::SellExecuted {
+                who: BOB,
+                asset_in: HDX,
+                asset_out: DAI,
+                amount_in: 10 * ONE,
+                amount_out: amount_out - fee,
+                fee_asset: DAI,
+                fee,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn buy_with_accumulated_asset_should_charge_fee_on_top_of_amount_in() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let (weight_dai, weight_hdx) = weights();
+            let amount_in =
+                hydra_dx_math::lbp::calculate_in_given_out(1_000 * ONE, 2_000 * ONE, weight_dai, weight_hdx, 10 * ONE)
+                    .unwrap();
+            let fee = pool_fee().mul_ceil(amount_in);
+
+            // Act
+            assert_ok!(LBP::buy(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                10 * ONE,
+                amount_in + fee
+            ));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), 10_010 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &BOB), 10_000 * ONE - amount_in - fee);
+            assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_000 * ONE + amount_in);
+            assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 1_990 * ONE);
+            assert_eq!(collected_fees(CHARLIE, DAI), fee);
+
+            expect_events(vec![Event::BuyExecuted {
+                who: BOB,
+                asset_in: DAI,
+                asset_out: HDX,
+                amount_in: amount_in + fee,
+                amount_out: 10 * ONE,
+                fee_asset: DAI,
+                fee,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn buy_accumulated_asset_should_take_fee_from_pool() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_END);
+            let (weight_dai, weight_hdx) = weights();
+            let fee = pool_fee().mul_ceil(10 * ONE);
+            let amount_in = hydra_dx_math::lbp::calculate_in_given_out(
+                2_000 * ONE,
+                1_000 * ONE,
+                weight_hdx,
+                weight_dai,
+                10 * ONE + fee,
+            )
+            .unwrap();
+
+            // Act
+            assert_ok!(LBP::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, amount_in));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &BOB), 10_010 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &BOB), 10_000 * ONE - amount_in);
+            assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 990 * ONE - fee);
+            assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 2_000 * ONE + amount_in);
+            assert_eq!(collected_fees(CHARLIE, DAI), fee);
+        });
+}
+
+#[test]
+fn sell_should_charge_repay_fee_until_repay_target_is_reached() {
+    ExtBuilder::default()
+        .with_pool(
+            Pool {
+                repay_target: ONE,
+                ..scheduled_pool()
+            },
+            1_000 * ONE,
+            2_000 * ONE,
+        )
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+            let repay_fee = RepayFee::get().mul_ceil(10 * ONE);
+
+            // Act
+            assert_ok!(LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, 0));
+
+            // Assert
+            assert_eq!(repay_fee, 2 * ONE);
+            assert_eq!(collected_fees(CHARLIE, DAI), repay_fee);
+
+            // Act - the repay target is reached
+            assert_ok!(LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, 0));
+
+            // Assert
+            assert_eq!(collected_fees(CHARLIE, DAI), repay_fee + pool_fee().mul_ceil(10 * ONE));
+        });
+}
+
+#[test]
+fn trade_should_fail_when_sale_is_not_running() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            System::set_block_number(SALE_START - 1);
+            assert_noop!(
+                LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, 0),
+                Error::<Test>::SaleIsNotRunning
+            );
+
+            System::set_block_number(SALE_END + 1);
+            assert_noop!(
+                LBP::buy(RuntimeOrigin::signed(BOB), HDX, DAI, 10 * ONE, u128::MAX),
+                Error::<Test>::SaleIsNotRunning
+            );
+        });
+}
+
+#[test]
+fn trade_should_fail_when_sale_is_not_scheduled() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, 0),
+            Error::<Test>::SaleIsNotRunning
+        );
+    });
+}
+
+#[test]
+fn trade_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::sell(RuntimeOrigin::signed(BOB), DAI, DOT, 10 * ONE, 0),
+            Error::<Test>::PoolNotFound
+        );
+    });
+}
+
+#[test]
+fn trade_should_fail_when_amount_is_below_min_trading_limit() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            System::set_block_number(SALE_START);
+            assert_noop!(
+                LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 999, 0),
+                Error::<Test>::InsufficientTradingAmount
+            );
+            assert_noop!(
+                LBP::buy(RuntimeOrigin::signed(BOB), HDX, DAI, 999, u128::MAX),
+                Error::<Test>::InsufficientTradingAmount
+            );
+        });
+}
+
+#[test]
+fn trade_should_fail_when_max_ratio_is_exceeded() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            System::set_block_number(SALE_START);
+            assert_noop!(
+                LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 500 * ONE, 0),
+                Error::<Test>::MaxInRatioExceeded
+            );
+            assert_noop!(
+                LBP::buy(RuntimeOrigin::signed(BOB), HDX, DAI, 700 * ONE, u128::MAX),
+                Error::<Test>::MaxOutRatioExceeded
+            );
+        });
+}
+
+#[test]
+fn trade_should_fail_when_limits_are_not_met() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            System::set_block_number(SALE_START);
+            assert_noop!(
+                LBP::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 10 * ONE, 1_000 * ONE),
+                Error::<Test>::BuyLimitNotReached
+            );
+            assert_noop!(
+                LBP::buy(RuntimeOrigin::signed(BOB), HDX, DAI, 10 * ONE, ONE),
+                Error::<Test>::SellLimitExceeded
+            );
+        });
+}
@@ -0,0 +1,267 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event, Pool};
+use frame_support::{assert_noop, assert_ok};
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn update_pool_data_should_work() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(LBP::update_pool_data(
+            RuntimeOrigin::signed(ALICE),
+            HDX_DAI_POOL,
+            Some(BOB),
+            Some(SALE_START),
+            Some(SALE_END),
+            Some(20_000_000),
+            Some(80_000_000),
+            Some(Permill::from_percent(1)),
+            Some(ALICE),
+            Some(100 * ONE),
+        ));
+
+        // Assert
+        let expected = Pool {
+            owner: BOB,
+            start: Some(SALE_START),
+            end: Some(SALE_END),
+            assets: (DAI, HDX),
+            initial_weight: 20_000_000,
+            final_weight: 80_000_000,
+            fee: Permill::from_percent(1),
+            fee_collector: ALICE,
+            repay_target: 100 * ONE,
+        };
+        assert_eq!(LBP::pool_data(HDX_DAI_POOL), Some(expected.clone()));
+        assert!(!LBP::fee_collector_with_asset(CHARLIE, DAI));
+        assert!(LBP::fee_collector_with_asset(ALICE, DAI));
+
+        expect_events(vec![Event::PoolUpdated {
+            pool: HDX_DAI_POOL,
+            data: expected,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn update_pool_data_should_keep_values_which_are_not_provided() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(LBP::update_pool_data(
+            RuntimeOrigin::signed(ALICE),
+            HDX_DAI_POOL,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Permill::from_percent(1)),
+            None,
+            None,
+        ));
+
+        // Assert
+        assert_eq!(
+            LBP::pool_data(HDX_DAI_POOL),
+            Some(Pool {
+                fee: Permill::from_percent(1),
+                ..default_pool()
+            })
+        );
+    });
+}
+
+#[test]
+fn update_pool_data_should_fail_when_nothing_to_update() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::update_pool_data(
+                RuntimeOrigin::signed(ALICE),
+                HDX_DAI_POOL,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Error::<Test>::NothingToUpdate
+        );
+    });
+}
+
+#[test]
+fn update_pool_data_should_fail_when_caller_is_not_owner() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        assert_noop!(
+            LBP::update_pool_data(
+                RuntimeOrigin::signed(BOB),
+                HDX_DAI_POOL,
+                None,
+                Some(SALE_START),
+                Some(SALE_END),
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn update_pool_data_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            LBP::update_pool_data(
+                RuntimeOrigin::signed(ALICE),
+                HDX_DAI_POOL,
+                None,
+                Some(SALE_START),
+                Some(SALE_END),
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Error::<Test>::PoolNotFound
+        );
+    });
+}
+
+#[test]
+fn update_pool_data_should_fail_when_block_range_is_invalid() {
+    ExtBuilder::default().with_default_pool().build().execute_with(|| {
+        // end before start
+        assert_noop!(
+            LBP::update_pool_data(
+                RuntimeOrigin::signed(ALICE),
+                HDX_DAI_POOL,
+                None,
+                Some(SALE_END),
+                Some(SALE_START),
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Error::<Test>::InvalidBlockRange
+        );
+        // start in the past
+        assert_noop!(
+            LBP::update_pool_data(
+                RuntimeOrigin::signed(ALICE),
+                HDX_DAI_POOL,
+                None,
+                Some(1),
+                Some(SALE_END),
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Error::<Test>::InvalidBlockRange
+        );
+        // end missing
+        assert_noop!(
+            LBP::update_pool_data(
+                RuntimeOrigin::signed(ALICE),
+                HDX_DAI_POOL,
+                None,
+                Some(SALE_START),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Error::<Test>::InvalidBlockRange
+        );
+    });
+}
+
+#[test]
+fn update_pool_data_should_fail_when_sale_started() {
+    ExtBuilder::default()
+        .with_pool(scheduled_pool(), 1_000 * ONE, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(SALE_START);
+
+            // Act & Assert
+            assert_noop!(
+                LBP::update_pool_data(
+                    RuntimeOrigin::signed(ALICE),
+                    HDX_DAI_POOL,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(Permill::from_percent(1)),
+                    None,
+                    None
+                ),
+                Error::<Test>::SaleStarted
+            );
+        });
+}
+
+#[test]
+fn update_pool_data_should_fail_when_new_fee_collector_collects_same_asset_in_another_pool() {
+    ExtBuilder::default()
+        .with_default_pool()
+        .with_pool(
+            Pool {
+                assets: (DAI, DOT),
+                fee_collector: BOB,
+                ..default_pool()
+            },
+            1_000 * ONE,
+            1_000 * ONE,
+        )
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                LBP::update_pool_data(
+                    RuntimeOrigin::signed(ALICE),
+                    HDX_DAI_POOL,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(BOB),
+                    None
+                ),
+                Error::<Test>::FeeCollectorWithAssetAlreadyUsed
+            );
+        });
+}
@@ -0,0 +1,78 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Pool, MAX_WEIGHT};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn weights_should_move_linearly_from_initial_to_final_weight() {
+    ExtBuilder::default().build().execute_with(|| {
+        let pool = scheduled_pool();
+
+        assert_eq!(
+            LBP::calculate_weights(&pool, SALE_START),
+            Ok((INITIAL_WEIGHT, MAX_WEIGHT - INITIAL_WEIGHT))
+        );
+        assert_eq!(LBP::calculate_weights(&pool, 15), Ok((42_500_000, 57_500_000)));
+        assert_eq!(
+            LBP::calculate_weights(&pool, SALE_END),
+            Ok((FINAL_WEIGHT, MAX_WEIGHT - FINAL_WEIGHT))
+        );
+    });
+}
+
+#[test]
+fn weights_should_decrease_when_final_weight_is_lower() {
+    ExtBuilder::default().build().execute_with(|| {
+        let pool = Pool {
+            initial_weight: FINAL_WEIGHT,
+            final_weight: INITIAL_WEIGHT,
+            ..scheduled_pool()
+        };
+
+        assert_eq!(
+            LBP::calculate_weights(&pool, SALE_START),
+            Ok((FINAL_WEIGHT, 25_000_000))
+        );
+        assert_eq!(LBP::calculate_weights(&pool, 15), Ok((42_500_000, 57_500_000)));
+        assert_eq!(
+            LBP::calculate_weights(&pool, SALE_END),
+            Ok((INITIAL_WEIGHT, 90_000_000))
+        );
+    });
+}
+
+#[test]
+fn weights_should_sum_to_max_weight_during_sale() {
+    ExtBuilder::default().build().execute_with(|| {
+        let pool = scheduled_pool();
+
+        for block in SALE_START..=SALE_END {
+            let (weight_a, weight_b) = LBP::calculate_weights(&pool, block).unwrap();
+            assert_eq!(weight_a + weight_b, MAX_WEIGHT);
+        }
+    });
+}
+
+#[test]
+fn weights_should_not_be_calculated_when_sale_is_not_scheduled() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            LBP::calculate_weights(&default_pool(), SALE_START),
+            Err(Error::<Test>::SaleIsNotRunning.into())
+        );
+    });
+}
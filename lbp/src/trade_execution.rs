@@ -0,0 +1,69 @@
+use crate::{Balance, Config, Pallet};
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use sp_runtime::DispatchError;
+
+impl<T: Config> TradeExecution<T::RuntimeOrigin, T::AccountId, T::AssetId, Balance> for Pallet<T> {
+    type Error = DispatchError;
+
+    fn calculate_sell(
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::LBP => {
+                let amounts =
+                    Self::calculate_sell_amounts(asset_in, asset_out, amount_in).map_err(ExecutorError::Error)?;
+
+                Ok(amounts.from_pool)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn calculate_buy(
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::LBP => {
+                let amounts =
+                    Self::calculate_buy_amounts(asset_in, asset_out, amount_out).map_err(ExecutorError::Error)?;
+
+                Ok(amounts.amount_in())
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn execute_sell(
+        who: T::RuntimeOrigin,
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+        min_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::LBP => Self::sell(who, asset_in, asset_out, amount_in, min_limit).map_err(ExecutorError::Error),
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn execute_buy(
+        who: T::RuntimeOrigin,
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+        max_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::LBP => Self::buy(who, asset_out, asset_in, amount_out, max_limit).map_err(ExecutorError::Error),
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+use sp_runtime::Permill;
+
+pub type Balance = u128;
+
+/// Weight of an asset in a pool, `MAX_WEIGHT` is 100%.
+pub type LBPWeight = u32;
+
+pub const MAX_WEIGHT: LBPWeight = 100_000_000;
+
+/// Parameters of a liquidity bootstrapping pool.
+///
+/// `assets.0` is the accumulated asset - all fees are paid in it. The weight of the accumulated asset moves
+/// linearly from `initial_weight` at block `start` to `final_weight` at block `end`, the weight of the
+/// other asset is the rest of `MAX_WEIGHT`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct Pool<AccountId, AssetId, BlockNumber> {
+    /// Owner of the pool, the only account allowed to manage the pool and its liquidity.
+    pub owner: AccountId,
+    /// First block of the sale.
+    pub start: Option<BlockNumber>,
+    /// Last block of the sale.
+    pub end: Option<BlockNumber>,
+    /// Accumulated asset and sold asset.
+    pub assets: (AssetId, AssetId),
+    /// Weight of the accumulated asset at `start`.
+    pub initial_weight: LBPWeight,
+    /// Weight of the accumulated asset at `end`.
+    pub final_weight: LBPWeight,
+    /// Trade fee rate.
+    pub fee: Permill,
+    /// Account receiving the fees.
+    pub fee_collector: AccountId,
+    /// Amount of the accumulated asset the fee collector has to receive before the trade fee drops
+    /// from the repay fee to `fee`.
+    pub repay_target: Balance,
+}
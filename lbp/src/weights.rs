@@ -0,0 +1,125 @@
+// This file is part of galacticcouncil/warehouse.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_lbp
+//!
+//! The benchmarks in `benchmarks.rs` have not been run yet, the figures below are estimates which have to be
+//! replaced by the generated weights.
+
+// Command to generate the weights:
+// target/release/hydradx
+// benchmark
+// pallet
+// --chain=dev
+// --steps=5
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --template=.maintain/pallet-weight-template.hbs
+// --pallet=pallet-lbp
+// --output=lbp.rs
+// --extrinsic=*
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_lbp.
+pub trait WeightInfo {
+    fn create_pool() -> Weight;
+    fn update_pool_data() -> Weight;
+    fn add_liquidity() -> Weight;
+    fn remove_liquidity() -> Weight;
+    fn sell() -> Weight;
+    fn buy() -> Weight;
+}
+
+/// Weights for pallet_lbp using the hydraDX node and recommended hardware.
+pub struct HydraWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
+    fn create_pool() -> Weight {
+        Weight::from_ref_time(58_264_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(9 as u64))
+    }
+    fn update_pool_data() -> Weight {
+        Weight::from_ref_time(24_570_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn add_liquidity() -> Weight {
+        Weight::from_ref_time(47_936_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+    fn remove_liquidity() -> Weight {
+        Weight::from_ref_time(53_118_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(7 as u64))
+    }
+    fn sell() -> Weight {
+        Weight::from_ref_time(92_314_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(9 as u64))
+            .saturating_add(T::DbWeight::get().writes(6 as u64))
+    }
+    fn buy() -> Weight {
+        Weight::from_ref_time(93_051_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(9 as u64))
+            .saturating_add(T::DbWeight::get().writes(6 as u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_pool() -> Weight {
+        Weight::from_ref_time(58_264_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(9 as u64))
+    }
+    fn update_pool_data() -> Weight {
+        Weight::from_ref_time(24_570_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn add_liquidity() -> Weight {
+        Weight::from_ref_time(47_936_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+    fn remove_liquidity() -> Weight {
+        Weight::from_ref_time(53_118_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(7 as u64))
+    }
+    fn sell() -> Weight {
+        Weight::from_ref_time(92_314_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(9 as u64))
+            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+    }
+    fn buy() -> Weight {
+        Weight::from_ref_time(93_051_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(9 as u64))
+            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+    }
+}
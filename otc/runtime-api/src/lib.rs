@@ -0,0 +1,37 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the OTC pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_otc::OrderId;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// The API to list open OTC orders.
+    pub trait OtcApi<AccountId, AssetId, Order> where
+        AccountId: Codec,
+        AssetId: Codec,
+        Order: Codec,
+    {
+        /// Returns a page of the open orders selling `asset_out` for `asset_in`, with the best price first.
+        fn orders_for_pair(asset_in: AssetId, asset_out: AssetId, offset: u32, limit: u32) -> Vec<(OrderId, Order)>;
+
+        /// Returns a page of the open orders of `owner`, sorted by order id.
+        fn orders_of_owner(owner: AccountId, offset: u32, limit: u32) -> Vec<(OrderId, Order)>;
+    }
+}
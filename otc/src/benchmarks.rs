@@ -33,7 +33,7 @@ benchmarks! {
         let (hdx, dai) = seed_registry::<T>()?;
 
         let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
-  }:  _(RawOrigin::Signed(owner.clone()), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+  }:  _(RawOrigin::Signed(owner.clone()), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
     verify {
         assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 100 * ONE);
     }
@@ -45,11 +45,15 @@ benchmarks! {
         let filler: T::AccountId = create_account_with_balances::<T>("filler", 2, vec!(hdx, dai))?;
 
         assert_ok!(
-            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
         );
+
+        let (fee_asset, fee) = fee_of::<T>(dai, hdx, 10 * ONE, 50 * ONE);
+        let fee_receiver_balance_before = T::Currency::free_balance(fee_asset.into(), &T::FeeReceiver::get());
   }:  _(RawOrigin::Signed(filler.clone()), 0u32, 10 * ONE)
     verify {
         assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 50 * ONE);
+        assert_eq!(T::Currency::free_balance(fee_asset.into(), &T::FeeReceiver::get()), fee_receiver_balance_before + fee);
     }
 
     fill_order {
@@ -59,11 +63,15 @@ benchmarks! {
         let filler: T::AccountId = create_account_with_balances::<T>("filler", 2, vec!(hdx, dai))?;
 
         assert_ok!(
-            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
         );
+
+        let (fee_asset, fee) = fee_of::<T>(dai, hdx, 20 * ONE, 100 * ONE);
+        let fee_receiver_balance_before = T::Currency::free_balance(fee_asset.into(), &T::FeeReceiver::get());
   }:  _(RawOrigin::Signed(filler.clone()), 0u32)
     verify {
         assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 0);
+        assert_eq!(T::Currency::free_balance(fee_asset.into(), &T::FeeReceiver::get()), fee_receiver_balance_before + fee);
     }
 
     cancel_order {
@@ -71,12 +79,130 @@ benchmarks! {
 
         let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
         assert_ok!(
-            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
         );
   }:  _(RawOrigin::Signed(owner.clone()), 0u32)
     verify {
         assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 0);
     }
+
+    cleanup_expired {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 2, vec!(hdx, dai))?;
+
+        let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(expires_at), None)
+        );
+        frame_system::Pallet::<T>::set_block_number(expires_at);
+  }:  _(RawOrigin::Signed(caller.clone()), 0u32)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 0);
+        assert!(<Orders<T>>::get(0u32).is_none());
+    }
+
+    remove_expired_order {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+
+        let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(expires_at), None)
+        );
+        frame_system::Pallet::<T>::set_block_number(expires_at);
+  }: {
+        frame_support::storage::with_transaction(|| {
+            TransactionOutcome::Commit(crate::Pallet::<T>::do_remove_expired_order(0u32, None))
+        })?;
+    }
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 0);
+        assert!(<Orders<T>>::get(0u32).is_none());
+    }
+
+    match_orders {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner_a: T::AccountId = create_account_with_balances::<T>("owner_a", 1, vec!(hdx, dai))?;
+        let owner_b: T::AccountId = create_account_with_balances::<T>("owner_b", 2, vec!(hdx, dai))?;
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 3, vec!(hdx, dai))?;
+
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner_a.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
+        );
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner_b.clone()).into(), hdx.into(), dai.into(), 50 * ONE, 15 * ONE, true, None, None)
+        );
+
+        // worst case: the fee is charged on both sides
+        let fee_receiver_dai_balance_before = T::Currency::free_balance(dai.into(), &T::FeeReceiver::get());
+        let fee_receiver_hdx_balance_before = T::Currency::free_balance(hdx.into(), &T::FeeReceiver::get());
+  }:  _(RawOrigin::Signed(caller.clone()), 0u32, 1u32)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner_a), 50 * ONE);
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, dai.into(), &owner_b), 0);
+        assert!(<Orders<T>>::get(1u32).is_none());
+        assert_eq!(T::Currency::free_balance(dai.into(), &T::FeeReceiver::get()), fee_receiver_dai_balance_before + T::Fee::get().mul_floor(10 * ONE));
+        assert_eq!(T::Currency::free_balance(hdx.into(), &T::FeeReceiver::get()), fee_receiver_hdx_balance_before + T::Fee::get().mul_floor(50 * ONE));
+    }
+
+    update_order {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
+        );
+  }:  _(RawOrigin::Signed(owner.clone()), 0u32, 25 * ONE, 120 * ONE)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 120 * ONE);
+    }
+
+    place_pegged_order {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+        let peg = OraclePeg { source: *b"omnipool", period: OraclePeriod::Short, spread: 10_000 };
+  }:  _(RawOrigin::Signed(owner.clone()), dai.into(), hdx.into(), 100 * ONE, peg, true, None, None)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 100 * ONE);
+    }
+
+    fill_orders {
+        let n in 1 .. T::MaxFillsPerBatch::get();
+
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+        let filler: T::AccountId = create_account_with_balances::<T>("filler", 2, vec!(hdx, dai))?;
+        T::Currency::update_balance(hdx.into(), &owner, (n as Balance * 100 * ONE) as i128)?;
+        T::Currency::update_balance(dai.into(), &filler, (n as Balance * 20 * ONE) as i128)?;
+
+        let mut fills = Vec::new();
+        for order_id in 0 .. n {
+            assert_ok!(
+                crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, None, None)
+            );
+            // partial fills are more expensive than complete ones
+            fills.push((order_id, Some(10 * ONE)));
+        }
+        let fills: FillsOf<T> = fills.try_into().unwrap();
+  }:  _(RawOrigin::Signed(filler.clone()), fills, n as Balance * 10 * ONE, 0)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), n as Balance * 50 * ONE);
+    }
+}
+
+fn fee_of<T: Config>(asset_in: u32, asset_out: u32, amount_in: Balance, amount_out: Balance) -> (u32, Balance) {
+    if T::FeeOnAmountIn::get() {
+        (asset_in, T::Fee::get().mul_floor(amount_in))
+    } else {
+        (asset_out, T::Fee::get().mul_floor(amount_out))
+    }
 }
 
 fn seed_registry<T: Config>() -> Result<(u32, u32), DispatchError>
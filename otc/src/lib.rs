@@ -18,6 +18,8 @@
 // This pallet provides basic over-the-counter (OTC) trading functionality.
 // It allows anyone to `place_order` by specifying a pair of assets (in and out), their respective amounts, and
 // whether the order is partially fillable. The order price is static and calculated as `amount_out / amount_in`.
+// An order can optionally be placed with an expiry block, from which on it can no longer be filled.
+// An order can also be restricted to a list of allowed takers, which are then the only accounts able to fill it.
 //
 // ## Notes
 // The pallet implements a minimum order size as an alternative to storage fees. The amounts of an open order cannot
@@ -25,28 +27,68 @@
 // This is validated at `place_order` but also at `partial_fill_order` - meaning that a user cannot leave dust amounts
 // below the defined threshold after filling an order (instead they should fill the order completely).
 //
+// An order can also be pegged to an oracle price with `place_pegged_order`. Instead of a fixed `amount_in`, the
+// order follows the oracle price of `asset_out` adjusted by a spread, which is evaluated on each fill. Fills are
+// rejected if the oracle was not updated within `MaxOracleAge` blocks.
+//
+// A protocol `Fee` is charged on every fill and paid to `FeeReceiver`, either from the `amount_out` received by
+// the taker or, if `FeeOnAmountIn` is set, from the `amount_in` received by the order owner.
+//
+// Expired orders are removed and their reserves released in `on_idle`, as long as there is weight left in the block.
+// They can also be removed by anyone with `cleanup_expired`, which pays a share of the remaining order amount
+// to the caller.
+//
+// Two orders on opposite sides of the same pair whose prices cross can be settled against each other by anyone
+// with `match_orders`. Each order is filled at its own price and the price improvement is paid to
+// `SurplusReceiver`, or to the caller if it is not configured. The protocol `Fee` is charged on the amounts
+// received by both order owners.
+//
+// Open orders are indexed by their pair and by their owner, which allows listing them with `orders_for_pair`
+// and `orders_of_owner` (exposed via the `OtcApi` runtime API).
+//
 // ## Dispatachable functions
 // * `place_order` -  create a new OTC order.
 // * `partial_fill_order` - fill an OTC order (partially).
 // * `fill_order` - fill an OTC order (completely).
 // * `cancel_order` - cancel an open OTC order.
+// * `cleanup_expired` - remove an expired OTC order.
+// * `match_orders` - settle two crossing OTC orders against each other.
+// * `update_order` - change the amounts of an open OTC order that is not pegged to an oracle price.
+// * `fill_orders` - fill several OTC orders of the same pair at once.
+// * `place_pegged_order` - create a new OTC order priced relative to an oracle.
+//
+// ## Router integration
+// The pallet implements `TradeExecution` for `PoolType::OTC(order_id)`, so that open orders can be used as a
+// liquidity source in routes. A sell fills the order with the given `amount_in` at the fixed price of the order,
+// a buy calculates the `amount_in` required for the given `amount_out` from the price ratio of the order.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::MaxEncodedLen;
 use frame_support::{pallet_prelude::*, require_transactional};
-use frame_system::{ensure_signed, pallet_prelude::OriginFor};
-use hydradx_traits::Registry;
+use frame_system::{
+    ensure_signed,
+    pallet_prelude::{BlockNumberFor, OriginFor},
+};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OracleLastUpdate, OraclePeriod, Source},
+    Registry,
+};
 use orml_traits::{GetByKey, MultiCurrency, NamedMultiReservableCurrency};
 use sp_core::U256;
 use sp_runtime::{
-    traits::{One, Zero},
-    DispatchError,
+    traits::{One, Saturating, Zero},
+    DispatchError, Permill, TransactionOutcome,
 };
 use sp_std::vec::Vec;
 #[cfg(test)]
 mod tests;
 
+mod trade_execution;
+
+pub mod migration;
+
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarks;
 
@@ -63,23 +105,60 @@ pub type NamedReserveIdentifier = [u8; 8];
 
 pub const NAMED_RESERVE_ID: NamedReserveIdentifier = *b"otcorder";
 
+/// Accuracy of the spread of pegged orders - the spread is expressed in parts per million
+pub const SPREAD_ACCURACY: i32 = 1_000_000;
+
+/// Oracle price followed by a pegged order
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+pub struct OraclePeg {
+    /// Source of the oracle entry
+    pub source: Source,
+    /// Aggregation period of the oracle entry
+    pub period: OraclePeriod,
+    /// Adjustment of the oracle price in parts per million. A positive spread makes the order more expensive
+    /// for the taker.
+    pub spread: i32,
+}
+
 #[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, TypeInfo, MaxEncodedLen)]
-pub struct Order<AccountId, AssetId> {
+pub struct Order<AccountId, AssetId, BlockNumber, AllowedTakers> {
     pub owner: AccountId,
     pub asset_in: AssetId,
     pub asset_out: AssetId,
     pub amount_in: Balance,
     pub amount_out: Balance,
     pub partially_fillable: bool,
+    /// The order cannot be filled from this block on
+    pub expires_at: Option<BlockNumber>,
+    /// Only these accounts can fill the order, if set
+    pub allowed_takers: Option<AllowedTakers>,
+    /// If set, `amount_in` follows the oracle price of `asset_out` and is recalculated on each fill
+    pub pegged_to: Option<OraclePeg>,
 }
 
+pub type AllowedTakersOf<T> = BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxAllowedTakers>;
+
+/// Orders to fill in a batch, with an optional `amount_in` for a partial fill
+pub type FillsOf<T> = BoundedVec<(OrderId, Option<Balance>), <T as Config>::MaxFillsPerBatch>;
+
+pub type OrderOf<T> = Order<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::AssetId,
+    <T as frame_system::Config>::BlockNumber,
+    AllowedTakersOf<T>,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use codec::HasCompact;
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -105,6 +184,44 @@ pub mod pallet {
         #[pallet::constant]
         type ExistentialDepositMultiplier: Get<u8>;
 
+        /// Share of the remaining order amount paid to the caller of `cleanup_expired`
+        #[pallet::constant]
+        type CleanupReward: Get<Permill>;
+
+        /// Max number of allowed takers of a private order
+        #[pallet::constant]
+        type MaxAllowedTakers: Get<u32>;
+
+        /// Account which receives the price improvement of matched orders.
+        /// If not set, the price improvement is paid to the caller of `match_orders`.
+        type SurplusReceiver: Get<Option<Self::AccountId>>;
+
+        /// Protocol fee charged on fills
+        #[pallet::constant]
+        type Fee: Get<Permill>;
+
+        /// If set, the fee is charged on the `amount_in` received by the order owner,
+        /// otherwise on the `amount_out` received by the taker
+        #[pallet::constant]
+        type FeeOnAmountIn: Get<bool>;
+
+        /// Account which receives the fees
+        #[pallet::constant]
+        type FeeReceiver: Get<Self::AccountId>;
+
+        /// Max number of orders filled by `fill_orders`
+        #[pallet::constant]
+        type MaxFillsPerBatch: Get<u32>;
+
+        /// Oracle used to price pegged orders
+        type Oracle: AggregatedPriceOracle<Self::AssetId, Self::BlockNumber, EmaPrice>
+            + OracleLastUpdate<Self::AssetId, Self::BlockNumber>;
+
+        /// Max number of blocks between the last update of the oracle used to price a pegged order and the
+        /// parent block
+        #[pallet::constant]
+        type MaxOracleAge: Get<Self::BlockNumber>;
+
         /// Weight information for the extrinsics.
         type WeightInfo: WeightInfo;
     }
@@ -120,6 +237,7 @@ pub mod pallet {
             who: T::AccountId,
             amount_in: Balance,
             amount_out: Balance,
+            fee: Balance,
         },
         /// An Order has been partially filled
         PartiallyFilled {
@@ -127,6 +245,7 @@ pub mod pallet {
             who: T::AccountId,
             amount_in: Balance,
             amount_out: Balance,
+            fee: Balance,
         },
         /// An Order has been placed
         Placed {
@@ -136,6 +255,32 @@ pub mod pallet {
             amount_in: Balance,
             amount_out: Balance,
             partially_fillable: bool,
+            expires_at: Option<T::BlockNumber>,
+            allowed_takers: Option<AllowedTakersOf<T>>,
+            pegged_to: Option<OraclePeg>,
+        },
+        /// An expired Order has been removed
+        Expired { order_id: OrderId, reward: Balance },
+        /// Two crossing Orders have been matched.
+        /// `amount_a` of the asset sold by `order_a` has been exchanged for `amount_b` of the asset sold by `order_b`,
+        /// out of which `surplus` has been paid to `surplus_receiver`.
+        /// `fee_a` of the asset sold by `order_b` and `fee_b` of the asset sold by `order_a` have been charged
+        /// on the amounts received by the owners of `order_a` and `order_b` respectively.
+        Matched {
+            order_a: OrderId,
+            order_b: OrderId,
+            amount_a: Balance,
+            amount_b: Balance,
+            surplus: Balance,
+            surplus_receiver: T::AccountId,
+            fee_a: Balance,
+            fee_b: Balance,
+        },
+        /// An Order has been updated
+        Updated {
+            order_id: OrderId,
+            amount_in: Balance,
+            amount_out: Balance,
         },
     }
 
@@ -159,6 +304,32 @@ pub mod pallet {
         Forbidden,
         /// Reserved amount not sufficient.
         InsufficientReservedAmount,
+        /// Assets of the trade do not match the assets of the order
+        AssetMismatch,
+        /// Trade amount exceeds the remaining amount of the order
+        OrderAmountExceeded,
+        /// Trade limit has been reached
+        TradeLimitReached,
+        /// Expiry block of the order must be in the future
+        InvalidExpiry,
+        /// Order has expired and cannot be filled
+        OrderExpired,
+        /// Order has not expired yet
+        OrderNotExpired,
+        /// List of allowed takers cannot be empty
+        NoAllowedTakers,
+        /// Orders are not on opposite sides of the same pair or their prices do not cross
+        OrdersNotCrossing,
+        /// An order cannot be matched with itself
+        CannotMatchOrderWithItself,
+        /// Spread of a pegged order is out of bounds
+        InvalidSpread,
+        /// Oracle price for a pegged order is not available
+        OraclePriceNotAvailable,
+        /// Oracle of a pegged order was not updated within `MaxOracleAge` blocks
+        OracleTooOld,
+        /// Amounts of a pegged order cannot be updated, as `amount_in` follows the oracle price
+        CannotUpdatePeggedOrder,
     }
 
     /// ID sequencer for Orders
@@ -168,7 +339,34 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn orders)]
-    pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, Order<T::AccountId, T::AssetId>, OptionQuery>;
+    pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, OrderOf<T>, OptionQuery>;
+
+    /// Open orders with an expiry, indexed by the expiry block
+    #[pallet::storage]
+    pub type ExpiringOrders<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, OrderId, (), OptionQuery>;
+
+    /// The first block whose expiring orders have not been removed yet
+    #[pallet::storage]
+    #[pallet::getter(fn expiry_cursor)]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Open orders indexed by their `(asset_in, asset_out)` pair
+    #[pallet::storage]
+    pub type OrdersByPair<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, (T::AssetId, T::AssetId), Twox64Concat, OrderId, (), OptionQuery>;
+
+    /// Open orders indexed by their owner
+    #[pallet::storage]
+    pub type OrdersByOwner<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, OrderId, (), OptionQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::remove_expired_orders(now, remaining_weight)
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -180,9 +378,13 @@ pub mod pallet {
         /// - `amount_in`: Amount that the order is seeking to buy
         /// - `amount_out`: Amount that the order is selling
         /// - `partially_fillable`: Flag indicating whether users can fill the order partially
+        /// - `expires_at`: Optional block from which on the order cannot be filled
+        /// - `allowed_takers`: Optional list of accounts which are the only ones allowed to fill the order
         ///
         /// Validations:
         /// - asset_in must be registered
+        /// - expires_at must be in the future
+        /// - allowed_takers must not be empty
         /// - amount_in must be higher than the existential deposit of asset_in multiplied by
         ///   ExistentialDepositMultiplier
         /// - amount_out must be higher than the existential deposit of asset_out multiplied by
@@ -199,38 +401,21 @@ pub mod pallet {
             amount_in: Balance,
             amount_out: Balance,
             partially_fillable: bool,
+            expires_at: Option<T::BlockNumber>,
+            allowed_takers: Option<AllowedTakersOf<T>>,
         ) -> DispatchResult {
             let owner = ensure_signed(origin)?;
-            let order = Order {
+
+            Self::do_place_order(Order {
                 owner,
                 asset_in,
                 asset_out,
                 amount_in,
                 amount_out,
                 partially_fillable,
-            };
-
-            ensure!(T::AssetRegistry::exists(order.asset_in), Error::<T>::AssetNotRegistered);
-            Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
-            Self::ensure_min_order_amount(order.asset_out, amount_out)?;
-
-            <NextOrderId<T>>::try_mutate(|next_id| -> DispatchResult {
-                let order_id = *next_id;
-
-                T::Currency::reserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out)?;
-                <Orders<T>>::insert(order_id, &order);
-
-                Self::deposit_event(Event::Placed {
-                    order_id,
-                    asset_in: order.asset_in,
-                    asset_out: order.asset_out,
-                    amount_in: order.amount_in,
-                    amount_out,
-                    partially_fillable: order.partially_fillable,
-                });
-
-                *next_id = next_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOutOfBound)?;
-                Ok(())
+                expires_at,
+                allowed_takers,
+                pegged_to: None,
             })
         }
 
@@ -241,6 +426,7 @@ pub mod pallet {
         /// - `amount_in`: Amount with which the order is being filled
         ///
         /// Validations:
+        /// - caller must be an allowed taker, if the order is private
         /// - order must be partially_fillable
         /// - after the partial_fill, the remaining order.amount_in must be higher than the existential deposit
         ///   of asset_in multiplied by ExistentialDepositMultiplier
@@ -250,36 +436,13 @@ pub mod pallet {
         /// Events:
         /// `PartiallyFilled` event when successful.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::partial_fill_order())]
+        #[pallet::weight(<T as Config>::WeightInfo::partial_fill_order().saturating_add(Pallet::<T>::oracle_price_weight()))]
         pub fn partial_fill_order(origin: OriginFor<T>, order_id: OrderId, amount_in: Balance) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            <Orders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
-                let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
-
-                ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
-
-                let amount_out_calculation = U256::from(order.amount_out)
-                    .checked_mul(U256::from(amount_in))
-                    .and_then(|v| v.checked_div(U256::from(order.amount_in)))
-                    .ok_or(Error::<T>::MathError)?;
-                let amount_out = Balance::try_from(amount_out_calculation).map_err(|_| Error::<T>::MathError)?;
-
-                order.amount_in = order.amount_in.checked_sub(amount_in).ok_or(Error::<T>::MathError)?;
-                order.amount_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
+            let order = Self::get_priced_order(order_id)?;
+            let amount_out = Self::calculate_partial_fill_amount_out(&order, amount_in)?;
 
-                Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
-                Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
-
-                Self::execute_order(order, &who, amount_in, amount_out)?;
-
-                Self::deposit_event(Event::PartiallyFilled {
-                    order_id,
-                    who,
-                    amount_in,
-                    amount_out,
-                });
-                Ok(())
-            })
+            Self::do_partial_fill_order(order_id, who, amount_in, amount_out)
         }
 
         /// Fill an OTC order (completely)
@@ -287,24 +450,17 @@ pub mod pallet {
         /// Parameters:
         /// - `order_id`: ID of the order
         ///
+        /// Validations:
+        /// - caller must be an allowed taker, if the order is private
+        ///
         /// Events:
         /// `Filled` event when successful.
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::fill_order())]
+        #[pallet::weight(<T as Config>::WeightInfo::fill_order().saturating_add(Pallet::<T>::oracle_price_weight()))]
         pub fn fill_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
-
-            Self::execute_order(&order, &who, order.amount_in, order.amount_out)?;
-            <Orders<T>>::remove(order_id);
 
-            Self::deposit_event(Event::Filled {
-                order_id,
-                who,
-                amount_in: order.amount_in,
-                amount_out: order.amount_out,
-            });
-            Ok(())
+            Self::do_fill_order(order_id, who)
         }
 
         /// Cancel an open OTC order
@@ -322,20 +478,334 @@ pub mod pallet {
         #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
         pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            <Orders<T>>::try_mutate_exists(order_id, |maybe_order| -> DispatchResult {
+            let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+            ensure!(order.owner == who, Error::<T>::Forbidden);
+
+            let remaining_to_unreserve =
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+            Self::remove_order(order_id, &order);
+
+            Self::deposit_event(Event::Cancelled { order_id });
+            Ok(())
+        }
+
+        /// Remove an expired OTC order
+        ///
+        /// The remaining reserved amount is released to the order owner, except for the `CleanupReward`
+        /// share of it, which is paid to the caller.
+        ///
+        /// Parameters:
+        /// - `order_id`: ID of the order
+        ///
+        /// Validations:
+        /// - order has expired
+        ///
+        /// Emits `Expired` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::cleanup_expired())]
+        pub fn cleanup_expired(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::do_remove_expired_order(order_id, Some(&who))
+        }
+
+        /// Match two crossing OTC orders
+        ///
+        /// `order_a` sells asset X for asset Y and `order_b` sells asset Y for asset X. The orders cross when
+        /// `order_b` pays at least the price asked by `order_a`.
+        /// The overlap of both orders is settled directly from their reserves: `order_a` receives asset Y
+        /// at its own price and `order_b` receives asset X at its own price. The difference (price improvement)
+        /// is paid in asset Y to `SurplusReceiver`, or to the caller if it is not set.
+        /// Both orders are filled, so the protocol fee is charged on the amounts received by both owners and
+        /// paid to `FeeReceiver`, regardless of `FeeOnAmountIn`.
+        ///
+        /// Parameters:
+        /// - `order_a`: ID of the first order
+        /// - `order_b`: ID of the second order
+        ///
+        /// Validations:
+        /// - orders must be on opposite sides of the same pair and their prices must cross
+        /// - orders must not be expired
+        /// - owner of each order must be an allowed taker of the other one, if it is private
+        /// - order which is not filled completely must be partially_fillable
+        /// - remaining amounts of a partially filled order must be higher than the existential deposit
+        ///   multiplied by ExistentialDepositMultiplier
+        ///
+        /// Emits `Matched` event when successful.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::match_orders()
+            .saturating_add(Pallet::<T>::oracle_price_weight().saturating_mul(2)))]
+        pub fn match_orders(origin: OriginFor<T>, order_a: OrderId, order_b: OrderId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(order_a != order_b, Error::<T>::CannotMatchOrderWithItself);
+
+            let a = Self::get_priced_order(order_a)?;
+            let b = Self::get_priced_order(order_b)?;
+
+            ensure!(
+                a.asset_in == b.asset_out && a.asset_out == b.asset_in,
+                Error::<T>::OrdersNotCrossing
+            );
+            Self::ensure_not_expired(&a)?;
+            Self::ensure_not_expired(&b)?;
+            Self::ensure_allowed_taker(&a, &b.owner)?;
+            Self::ensure_allowed_taker(&b, &a.owner)?;
+
+            // b.amount_out / b.amount_in >= a.amount_in / a.amount_out
+            let offered = U256::from(b.amount_out)
+                .checked_mul(U256::from(a.amount_out))
+                .ok_or(Error::<T>::MathError)?;
+            let asked = U256::from(a.amount_in)
+                .checked_mul(U256::from(b.amount_in))
+                .ok_or(Error::<T>::MathError)?;
+            ensure!(offered >= asked, Error::<T>::OrdersNotCrossing);
+
+            // amount of asset X sold by `order_a`
+            let amount_a = a.amount_out.min(b.amount_in);
+            // amount of asset Y received by `order_a`, rounded up in favour of its owner
+            let amount_a_in = if amount_a == a.amount_out {
+                a.amount_in
+            } else {
+                Self::calculate_partial_fill_amount_in(&a, amount_a)?
+            };
+            // amount of asset Y sold by `order_b`, rounded down in favour of its owner
+            let amount_b = if amount_a == b.amount_in {
+                b.amount_out
+            } else {
+                Self::calculate_partial_fill_amount_out(&b, amount_a)?
+            };
+            let surplus = amount_b.checked_sub(amount_a_in).ok_or(Error::<T>::OrdersNotCrossing)?;
+
+            Self::settle_matched_order(order_a, &a, amount_a_in, amount_a)?;
+            Self::settle_matched_order(order_b, &b, amount_a, amount_b)?;
+
+            // fees charged on the amounts received by the owners of `order_a` and `order_b`
+            let fee_a = Self::calculate_fee(amount_a_in);
+            let fee_b = Self::calculate_fee(amount_a);
+
+            let remaining_to_unreserve =
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, a.asset_out, &a.owner, amount_a);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+            let amount_a_without_fee = amount_a.checked_sub(fee_b).ok_or(Error::<T>::MathError)?;
+            T::Currency::transfer(a.asset_out, &a.owner, &b.owner, amount_a_without_fee)?;
+            if !fee_b.is_zero() {
+                T::Currency::transfer(a.asset_out, &a.owner, &T::FeeReceiver::get(), fee_b)?;
+            }
+
+            let remaining_to_unreserve =
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, b.asset_out, &b.owner, amount_b);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+            let amount_a_in_without_fee = amount_a_in.checked_sub(fee_a).ok_or(Error::<T>::MathError)?;
+            T::Currency::transfer(b.asset_out, &b.owner, &a.owner, amount_a_in_without_fee)?;
+            if !fee_a.is_zero() {
+                T::Currency::transfer(b.asset_out, &b.owner, &T::FeeReceiver::get(), fee_a)?;
+            }
+
+            let surplus_receiver = T::SurplusReceiver::get().unwrap_or(who);
+            if !surplus.is_zero() {
+                T::Currency::transfer(b.asset_out, &b.owner, &surplus_receiver, surplus)?;
+            }
+
+            Self::deposit_event(Event::Matched {
+                order_a,
+                order_b,
+                amount_a,
+                amount_b,
+                surplus,
+                surplus_receiver,
+                fee_a,
+                fee_b,
+            });
+            Ok(())
+        }
+
+        /// Update the amounts of an open OTC order
+        ///
+        /// The reserved amount of `asset_out` is adjusted to `new_amount_out`. The order keeps its ID.
+        ///
+        /// Parameters:
+        /// - `order_id`: ID of the order
+        /// - `new_amount_in`: New amount that the order is seeking to buy
+        /// - `new_amount_out`: New amount that the order is selling
+        ///
+        /// Validations:
+        /// - caller is order owner
+        /// - order must not be expired
+        /// - order must not be pegged to an oracle price
+        /// - new_amount_in must be higher than the existential deposit of asset_in multiplied by
+        ///   ExistentialDepositMultiplier
+        /// - new_amount_out must be higher than the existential deposit of asset_out multiplied by
+        ///   ExistentialDepositMultiplier
+        ///
+        /// Emits `Updated` event when successful.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::update_order())]
+        pub fn update_order(
+            origin: OriginFor<T>,
+            order_id: OrderId,
+            new_amount_in: Balance,
+            new_amount_out: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            <Orders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
 
                 ensure!(order.owner == who, Error::<T>::Forbidden);
+                Self::ensure_not_expired(order)?;
+                ensure!(order.pegged_to.is_none(), Error::<T>::CannotUpdatePeggedOrder);
+                Self::ensure_min_order_amount(order.asset_in, new_amount_in)?;
+                Self::ensure_min_order_amount(order.asset_out, new_amount_out)?;
+
+                if new_amount_out > order.amount_out {
+                    T::Currency::reserve_named(
+                        &NAMED_RESERVE_ID,
+                        order.asset_out,
+                        &order.owner,
+                        new_amount_out.saturating_sub(order.amount_out),
+                    )?;
+                } else {
+                    let remaining_to_unreserve = T::Currency::unreserve_named(
+                        &NAMED_RESERVE_ID,
+                        order.asset_out,
+                        &order.owner,
+                        order.amount_out.saturating_sub(new_amount_out),
+                    );
+                    ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+                }
 
-                let remaining_to_unreserve =
-                    T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out);
-                ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
-                *maybe_order = None;
+                order.amount_in = new_amount_in;
+                order.amount_out = new_amount_out;
 
-                Self::deposit_event(Event::Cancelled { order_id });
+                Self::deposit_event(Event::Updated {
+                    order_id,
+                    amount_in: new_amount_in,
+                    amount_out: new_amount_out,
+                });
                 Ok(())
             })
         }
+
+        /// Fill several OTC orders at once
+        ///
+        /// All orders must have the same `asset_in` and `asset_out`. The fills are executed atomically - if any of
+        /// them fails, none of the orders is filled.
+        ///
+        /// Parameters:
+        /// - `fills`: List of order IDs with an optional `amount_in` - the order is filled partially if it is set,
+        ///   completely otherwise
+        /// - `max_amount_in`: Max total amount of `asset_in` paid for all fills
+        /// - `min_amount_out`: Min total amount of `asset_out` received from all fills, after fees
+        ///
+        /// Validations:
+        /// - all orders must have the same pair of assets
+        /// - every fill must pass the validations of `fill_order` or `partial_fill_order`
+        /// - total amounts must be within the limits
+        ///
+        /// Events:
+        /// `Filled` or `PartiallyFilled` event for each fill when successful.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::fill_orders(fills.len() as u32)
+            .saturating_add(Pallet::<T>::oracle_price_weight().saturating_mul(fills.len() as u64)))]
+        pub fn fill_orders(
+            origin: OriginFor<T>,
+            fills: FillsOf<T>,
+            max_amount_in: Balance,
+            min_amount_out: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut pair = None;
+            let mut total_amount_in: Balance = Zero::zero();
+            let mut total_amount_out: Balance = Zero::zero();
+
+            for (order_id, maybe_amount_in) in fills {
+                let order = Self::get_priced_order(order_id)?;
+                match pair {
+                    None => pair = Some((order.asset_in, order.asset_out)),
+                    Some(pair) => ensure!(pair == (order.asset_in, order.asset_out), Error::<T>::AssetMismatch),
+                }
+
+                let (amount_in, amount_out) = match maybe_amount_in {
+                    Some(amount_in) if amount_in != order.amount_in => {
+                        let amount_out = Self::calculate_partial_fill_amount_out(&order, amount_in)?;
+                        Self::do_partial_fill_order(order_id, who.clone(), amount_in, amount_out)?;
+                        (amount_in, amount_out)
+                    }
+                    _ => {
+                        Self::do_fill_order(order_id, who.clone())?;
+                        (order.amount_in, order.amount_out)
+                    }
+                };
+
+                total_amount_in = total_amount_in.checked_add(amount_in).ok_or(Error::<T>::MathError)?;
+                total_amount_out = total_amount_out
+                    .checked_add(Self::amount_out_without_fee(amount_out)?)
+                    .ok_or(Error::<T>::MathError)?;
+            }
+
+            ensure!(total_amount_in <= max_amount_in, Error::<T>::TradeLimitReached);
+            ensure!(total_amount_out >= min_amount_out, Error::<T>::TradeLimitReached);
+
+            Ok(())
+        }
+
+        /// Create a new OTC order pegged to an oracle price
+        ///
+        /// `amount_in` of the order is not fixed, it is calculated on each fill from the oracle price of `asset_out`
+        /// denominated in `asset_in`, adjusted by the spread of the peg.
+        ///
+        /// Parameters:
+        /// - `asset_in`: Asset which is being bought
+        /// - `asset_out`: Asset which is being sold
+        /// - `amount_out`: Amount that the order is selling
+        /// - `pegged_to`: Oracle source, period and spread which determine the price of the order
+        /// - `partially_fillable`: Flag indicating whether users can fill the order partially
+        /// - `expires_at`: Optional block from which on the order cannot be filled
+        /// - `allowed_takers`: Optional list of accounts which are the only ones allowed to fill the order
+        ///
+        /// Validations:
+        /// - same as `place_order`, with `amount_in` calculated from the current oracle price
+        /// - spread must be higher than -100% and not higher than 100%
+        /// - oracle price must be available and updated within `MaxOracleAge` blocks
+        ///
+        /// Events:
+        /// - `Placed` event when successful.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::place_pegged_order().saturating_add(Pallet::<T>::oracle_price_weight()))]
+        pub fn place_pegged_order(
+            origin: OriginFor<T>,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_out: Balance,
+            pegged_to: OraclePeg,
+            partially_fillable: bool,
+            expires_at: Option<T::BlockNumber>,
+            allowed_takers: Option<AllowedTakersOf<T>>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            ensure!(
+                pegged_to.spread > -SPREAD_ACCURACY && pegged_to.spread <= SPREAD_ACCURACY,
+                Error::<T>::InvalidSpread
+            );
+
+            let mut order = Order {
+                owner,
+                asset_in,
+                asset_out,
+                amount_in: Zero::zero(),
+                amount_out,
+                partially_fillable,
+                expires_at,
+                allowed_takers,
+                pegged_to: Some(pegged_to),
+            };
+            Self::apply_oracle_price(&mut order)?;
+
+            Self::do_place_order(order)
+        }
     }
 }
 
@@ -350,19 +820,401 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    fn do_place_order(order: OrderOf<T>) -> DispatchResult {
+        ensure!(T::AssetRegistry::exists(order.asset_in), Error::<T>::AssetNotRegistered);
+        if let Some(expires_at) = order.expires_at {
+            ensure!(
+                expires_at > <frame_system::Pallet<T>>::block_number(),
+                Error::<T>::InvalidExpiry
+            );
+        }
+        if let Some(allowed_takers) = &order.allowed_takers {
+            ensure!(!allowed_takers.is_empty(), Error::<T>::NoAllowedTakers);
+        }
+        Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
+        Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
+
+        <NextOrderId<T>>::try_mutate(|next_id| -> DispatchResult {
+            let order_id = *next_id;
+
+            T::Currency::reserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out)?;
+            Self::insert_order(order_id, &order);
+
+            Self::deposit_event(Event::Placed {
+                order_id,
+                asset_in: order.asset_in,
+                asset_out: order.asset_out,
+                amount_in: order.amount_in,
+                amount_out: order.amount_out,
+                partially_fillable: order.partially_fillable,
+                expires_at: order.expires_at,
+                allowed_takers: order.allowed_takers.clone(),
+                pegged_to: order.pegged_to,
+            });
+
+            *next_id = next_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOutOfBound)?;
+            Ok(())
+        })
+    }
+
+    /// Weight of reading the oracle price and the last oracle update for a pegged order.
+    pub(crate) fn oracle_price_weight() -> Weight {
+        T::Oracle::get_price_weight().saturating_add(T::Oracle::last_update_weight())
+    }
+
+    /// Returns an order with `amount_in` set from the current oracle price, if the order is pegged.
+    fn get_priced_order(order_id: OrderId) -> Result<OrderOf<T>, DispatchError> {
+        let mut order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+        Self::apply_oracle_price(&mut order)?;
+
+        Ok(order)
+    }
+
+    /// Sets `amount_in` of a pegged order from the oracle price of `asset_out` denominated in `asset_in`,
+    /// adjusted by the spread. Rounds up in favour of the order owner.
+    fn apply_oracle_price(order: &mut OrderOf<T>) -> DispatchResult {
+        let peg = match order.pegged_to {
+            Some(peg) => peg,
+            None => return Ok(()),
+        };
+
+        let (price, _) = T::Oracle::get_price(order.asset_in, order.asset_out, peg.period, peg.source)
+            .map_err(|_| Error::<T>::OraclePriceNotAvailable)?;
+
+        // the oracle age counts the blocks since the oracle was initialized, the data is as recent as its last update
+        let last_update = T::Oracle::last_update(order.asset_in, order.asset_out, peg.source)
+            .ok_or(Error::<T>::OraclePriceNotAvailable)?;
+        let parent = <frame_system::Pallet<T>>::block_number().saturating_sub(One::one());
+        ensure!(
+            parent.saturating_sub(last_update) <= T::MaxOracleAge::get(),
+            Error::<T>::OracleTooOld
+        );
+
+        let (price_n, price_d): (Balance, Balance) = price.into();
+        ensure!(
+            !price_n.is_zero() && !price_d.is_zero(),
+            Error::<T>::OraclePriceNotAvailable
+        );
+
+        let spread_factor = SPREAD_ACCURACY
+            .checked_add(peg.spread)
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or(Error::<T>::MathError)?;
+
+        let numerator = U256::from(order.amount_out)
+            .checked_mul(U256::from(price_n))
+            .and_then(|v| v.checked_mul(U256::from(spread_factor)))
+            .ok_or(Error::<T>::MathError)?;
+        let denominator = U256::from(price_d)
+            .checked_mul(U256::from(SPREAD_ACCURACY))
+            .ok_or(Error::<T>::MathError)?;
+        let amount_in = numerator
+            .checked_add(denominator)
+            .and_then(|v| v.checked_sub(U256::one()))
+            .and_then(|v| v.checked_div(denominator))
+            .ok_or(Error::<T>::MathError)?;
+
+        order.amount_in = Balance::try_from(amount_in).map_err(|_| Error::<T>::MathError)?;
+
+        Ok(())
+    }
+
+    /// Stores a new order and adds it to all indexes.
+    fn insert_order(order_id: OrderId, order: &OrderOf<T>) {
+        <Orders<T>>::insert(order_id, order);
+        <OrdersByPair<T>>::insert((order.asset_in, order.asset_out), order_id, ());
+        <OrdersByOwner<T>>::insert(&order.owner, order_id, ());
+        if let Some(expires_at) = order.expires_at {
+            <ExpiringOrders<T>>::insert(expires_at, order_id, ());
+        }
+    }
+
+    /// Removes an order and drops it from all indexes.
+    fn remove_order(order_id: OrderId, order: &OrderOf<T>) {
+        <Orders<T>>::remove(order_id);
+        <OrdersByPair<T>>::remove((order.asset_in, order.asset_out), order_id);
+        <OrdersByOwner<T>>::remove(&order.owner, order_id);
+        if let Some(expires_at) = order.expires_at {
+            <ExpiringOrders<T>>::remove(expires_at, order_id);
+        }
+    }
+
+    /// Returns a page of the open orders selling `asset_out` for `asset_in`, sorted by price.
+    /// Orders asking the lowest `amount_in` per unit of `amount_out` come first. Expired orders and pegged orders
+    /// without a valid oracle price are skipped.
+    pub fn orders_for_pair(
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(OrderId, OrderOf<T>)> {
+        let mut orders: Vec<(OrderId, OrderOf<T>)> = <OrdersByPair<T>>::iter_key_prefix((asset_in, asset_out))
+            .filter_map(|order_id| Self::get_priced_order(order_id).ok().map(|order| (order_id, order)))
+            .filter(|(_, order)| !Self::is_expired(order))
+            .collect();
+
+        orders.sort_by(|(id_a, a), (id_b, b)| {
+            let price_a = U256::from(a.amount_in).saturating_mul(U256::from(b.amount_out));
+            let price_b = U256::from(b.amount_in).saturating_mul(U256::from(a.amount_out));
+            price_a.cmp(&price_b).then(id_a.cmp(id_b))
+        });
+
+        orders.into_iter().skip(offset as usize).take(limit as usize).collect()
+    }
+
+    /// Returns a page of the open orders of `owner`, sorted by order id.
+    pub fn orders_of_owner(owner: &T::AccountId, offset: u32, limit: u32) -> Vec<(OrderId, OrderOf<T>)> {
+        let mut orders: Vec<(OrderId, OrderOf<T>)> = <OrdersByOwner<T>>::iter_key_prefix(owner)
+            .filter_map(|order_id| <Orders<T>>::get(order_id).map(|order| (order_id, order)))
+            .collect();
+
+        orders.sort_by_key(|(order_id, _)| *order_id);
+
+        orders.into_iter().skip(offset as usize).take(limit as usize).collect()
+    }
+
+    fn calculate_partial_fill_amount_out(order: &OrderOf<T>, amount_in: Balance) -> Result<Balance, DispatchError> {
+        let amount_out = U256::from(order.amount_out)
+            .checked_mul(U256::from(amount_in))
+            .and_then(|v| v.checked_div(U256::from(order.amount_in)))
+            .ok_or(Error::<T>::MathError)?;
+
+        Balance::try_from(amount_out).map_err(|_| Error::<T>::MathError.into())
+    }
+
+    /// Calculates `amount_in` required to receive `amount_out` from the order. Rounds up in favour of the order owner.
+    fn calculate_partial_fill_amount_in(order: &OrderOf<T>, amount_out: Balance) -> Result<Balance, DispatchError> {
+        let order_amount_out = U256::from(order.amount_out);
+        let amount_in = U256::from(order.amount_in)
+            .checked_mul(U256::from(amount_out))
+            .and_then(|v| v.checked_add(order_amount_out))
+            .and_then(|v| v.checked_sub(U256::one()))
+            .and_then(|v| v.checked_div(order_amount_out))
+            .ok_or(Error::<T>::MathError)?;
+
+        Balance::try_from(amount_in).map_err(|_| Error::<T>::MathError.into())
+    }
+
+    #[require_transactional]
+    fn do_partial_fill_order(
+        order_id: OrderId,
+        who: T::AccountId,
+        amount_in: Balance,
+        amount_out: Balance,
+    ) -> DispatchResult {
+        <Orders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
+            let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+            Self::apply_oracle_price(order)?;
+
+            ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+
+            order.amount_in = order.amount_in.checked_sub(amount_in).ok_or(Error::<T>::MathError)?;
+            order.amount_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
+
+            Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
+            Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
+
+            let fee = Self::execute_order(order, &who, amount_in, amount_out)?;
+
+            Self::deposit_event(Event::PartiallyFilled {
+                order_id,
+                who,
+                amount_in,
+                amount_out,
+                fee,
+            });
+            Ok(())
+        })
+    }
+
+    #[require_transactional]
+    fn do_fill_order(order_id: OrderId, who: T::AccountId) -> DispatchResult {
+        let order = Self::get_priced_order(order_id)?;
+
+        let fee = Self::execute_order(&order, &who, order.amount_in, order.amount_out)?;
+        Self::remove_order(order_id, &order);
+
+        Self::deposit_event(Event::Filled {
+            order_id,
+            who,
+            amount_in: order.amount_in,
+            amount_out: order.amount_out,
+            fee,
+        });
+        Ok(())
+    }
+
+    /// Reduces the remaining amounts of a matched order, or removes it if it has been filled completely.
+    fn settle_matched_order(
+        order_id: OrderId,
+        order: &OrderOf<T>,
+        amount_in: Balance,
+        amount_out: Balance,
+    ) -> DispatchResult {
+        if amount_out == order.amount_out {
+            Self::remove_order(order_id, order);
+            return Ok(());
+        }
+
+        ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+
+        let mut order = order.clone();
+        order.amount_in = order.amount_in.checked_sub(amount_in).ok_or(Error::<T>::MathError)?;
+        order.amount_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
+
+        Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
+        Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
+
+        <Orders<T>>::insert(order_id, order);
+        Ok(())
+    }
+
+    fn is_expired(order: &OrderOf<T>) -> bool {
+        order.expires_at.map_or(false, |expires_at| {
+            <frame_system::Pallet<T>>::block_number() >= expires_at
+        })
+    }
+
+    fn ensure_not_expired(order: &OrderOf<T>) -> DispatchResult {
+        ensure!(!Self::is_expired(order), Error::<T>::OrderExpired);
+
+        Ok(())
+    }
+
+    fn ensure_allowed_taker(order: &OrderOf<T>, who: &T::AccountId) -> DispatchResult {
+        if let Some(allowed_takers) = &order.allowed_takers {
+            ensure!(allowed_takers.contains(who), Error::<T>::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    /// Removes an expired order and releases its reserved amount.
+    /// If `rewarded` is set, the `CleanupReward` share of the released amount is transferred to it.
+    #[require_transactional]
+    fn do_remove_expired_order(order_id: OrderId, rewarded: Option<&T::AccountId>) -> DispatchResult {
+        let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+        ensure!(Self::is_expired(&order), Error::<T>::OrderNotExpired);
+
+        let remaining_to_unreserve =
+            T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out);
+        ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+
+        let reward = match rewarded {
+            Some(who) => {
+                let reward = T::CleanupReward::get().mul_floor(order.amount_out);
+                if !reward.is_zero() {
+                    T::Currency::transfer(order.asset_out, &order.owner, who, reward)?;
+                }
+                reward
+            }
+            None => Zero::zero(),
+        };
+
+        Self::remove_order(order_id, &order);
+
+        Self::deposit_event(Event::Expired { order_id, reward });
+        Ok(())
+    }
+
+    /// Removes orders which expired up to block `now`, as long as `remaining_weight` allows.
+    /// Blocks are processed in order, starting at `ExpiryCursor`.
+    fn remove_expired_orders(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        // reading and writing the cursor
+        let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+        if !remaining_weight.all_gte(used_weight) {
+            return Weight::zero();
+        }
+
+        let block_weight = T::DbWeight::get().reads(1);
+        let order_weight = T::WeightInfo::remove_expired_order();
+
+        let mut cursor = <ExpiryCursor<T>>::get();
+        'blocks: while cursor <= now {
+            if !remaining_weight.all_gte(used_weight.saturating_add(block_weight)) {
+                break;
+            }
+            used_weight.saturating_accrue(block_weight);
+
+            for order_id in <ExpiringOrders<T>>::iter_key_prefix(cursor) {
+                if !remaining_weight.all_gte(used_weight.saturating_add(order_weight)) {
+                    break 'blocks;
+                }
+                used_weight.saturating_accrue(order_weight);
+
+                let result =
+                    frame_support::storage::with_transaction(|| match Self::do_remove_expired_order(order_id, None) {
+                        Ok(()) => TransactionOutcome::Commit(Ok(())),
+                        Err(e) => TransactionOutcome::Rollback(Err(e)),
+                    });
+                if result.is_err() {
+                    // the order cannot be removed, drop it from the index so that the sweep can progress
+                    <ExpiringOrders<T>>::remove(cursor, order_id);
+                }
+            }
+
+            cursor.saturating_inc();
+        }
+
+        <ExpiryCursor<T>>::put(cursor);
+
+        used_weight
+    }
+
+    fn calculate_fee(amount: Balance) -> Balance {
+        T::Fee::get().mul_floor(amount)
+    }
+
+    /// Amount of `asset_out` the taker receives from a fill of `amount_out`
+    fn amount_out_without_fee(amount_out: Balance) -> Result<Balance, DispatchError> {
+        if T::FeeOnAmountIn::get() {
+            return Ok(amount_out);
+        }
+
+        amount_out
+            .checked_sub(Self::calculate_fee(amount_out))
+            .ok_or_else(|| Error::<T>::MathError.into())
+    }
+
+    /// Executes a fill of `order` by `who` and returns the charged fee.
+    /// The fee is paid in `asset_in` by the order owner if `FeeOnAmountIn` is set, in `asset_out` by the taker
+    /// otherwise.
     #[require_transactional]
     fn execute_order(
-        order: &Order<T::AccountId, T::AssetId>,
+        order: &OrderOf<T>,
         who: &T::AccountId,
         amount_in: Balance,
         amount_out: Balance,
-    ) -> DispatchResult {
-        T::Currency::transfer(order.asset_in, who, &order.owner, amount_in)?;
+    ) -> Result<Balance, DispatchError> {
+        Self::ensure_not_expired(order)?;
+        Self::ensure_allowed_taker(order, who)?;
+
         let remaining_to_unreserve =
             T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, amount_out);
         ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
-        T::Currency::transfer(order.asset_out, &order.owner, who, amount_out)?;
 
-        Ok(())
+        let fee = if T::FeeOnAmountIn::get() {
+            let fee = Self::calculate_fee(amount_in);
+            let amount_in_without_fee = amount_in.checked_sub(fee).ok_or(Error::<T>::MathError)?;
+
+            T::Currency::transfer(order.asset_in, who, &order.owner, amount_in_without_fee)?;
+            if !fee.is_zero() {
+                T::Currency::transfer(order.asset_in, who, &T::FeeReceiver::get(), fee)?;
+            }
+            T::Currency::transfer(order.asset_out, &order.owner, who, amount_out)?;
+            fee
+        } else {
+            let fee = Self::calculate_fee(amount_out);
+            let amount_out_without_fee = amount_out.checked_sub(fee).ok_or(Error::<T>::MathError)?;
+
+            T::Currency::transfer(order.asset_in, who, &order.owner, amount_in)?;
+            T::Currency::transfer(order.asset_out, &order.owner, who, amount_out_without_fee)?;
+            if !fee.is_zero() {
+                T::Currency::transfer(order.asset_out, &order.owner, &T::FeeReceiver::get(), fee)?;
+            }
+            fee
+        };
+
+        Ok(fee)
     }
 }
@@ -0,0 +1,303 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    AllowedTakersOf, Balance, Config, ExpiryCursor, Order, OrderId, Orders, OrdersByOwner, OrdersByPair, Pallet,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    log, storage_alias,
+    traits::{Get, StorageVersion},
+    weights::Weight,
+    Blake2_128Concat,
+};
+use sp_runtime::traits::Saturating;
+
+/// Orders are extended with an optional expiry block.
+pub mod v1 {
+    use super::*;
+
+    #[derive(Encode, Decode)]
+    pub struct OrderV0<AccountId, AssetId> {
+        pub owner: AccountId,
+        pub asset_in: AssetId,
+        pub asset_out: AssetId,
+        pub amount_in: Balance,
+        pub amount_out: Balance,
+        pub partially_fillable: bool,
+    }
+
+    #[derive(Encode, Decode)]
+    pub struct OrderV1<AccountId, AssetId, BlockNumber> {
+        pub owner: AccountId,
+        pub asset_in: AssetId,
+        pub asset_out: AssetId,
+        pub amount_in: Balance,
+        pub amount_out: Balance,
+        pub partially_fillable: bool,
+        pub expires_at: Option<BlockNumber>,
+    }
+
+    #[storage_alias]
+    pub type Orders<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        OrderId,
+        OrderV1<
+            <T as frame_system::Config>::AccountId,
+            <T as Config>::AssetId,
+            <T as frame_system::Config>::BlockNumber,
+        >,
+    >;
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::otc",
+            "Running migration to v1 for OTC"
+        );
+
+        let mut translated: u64 = 0;
+        Orders::<T>::translate(|_, old: OrderV0<T::AccountId, T::AssetId>| {
+            translated.saturating_inc();
+            Some(OrderV1 {
+                owner: old.owner,
+                asset_in: old.asset_in,
+                asset_out: old.asset_out,
+                amount_in: old.amount_in,
+                amount_out: old.amount_out,
+                partially_fillable: old.partially_fillable,
+                expires_at: None,
+            })
+        });
+
+        // there are no expiring orders yet, so the sweep can start at the current block
+        <ExpiryCursor<T>>::put(<frame_system::Pallet<T>>::block_number());
+
+        StorageVersion::new(1).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(2))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Unexpected storage version.");
+
+        for (_, order) in Orders::<T>::iter() {
+            assert!(order.expires_at.is_none(), "Unexpected order expiry.");
+        }
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: POST checks successful!"
+        );
+    }
+}
+
+/// Orders are extended with an optional list of allowed takers.
+pub mod v2 {
+    use super::*;
+    use v1::OrderV1;
+
+    #[derive(Encode, Decode)]
+    pub struct OrderV2<AccountId, AssetId, BlockNumber, AllowedTakers> {
+        pub owner: AccountId,
+        pub asset_in: AssetId,
+        pub asset_out: AssetId,
+        pub amount_in: Balance,
+        pub amount_out: Balance,
+        pub partially_fillable: bool,
+        pub expires_at: Option<BlockNumber>,
+        pub allowed_takers: Option<AllowedTakers>,
+    }
+
+    #[storage_alias]
+    pub type Orders<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        OrderId,
+        OrderV2<
+            <T as frame_system::Config>::AccountId,
+            <T as Config>::AssetId,
+            <T as frame_system::Config>::BlockNumber,
+            AllowedTakersOf<T>,
+        >,
+    >;
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::otc",
+            "Running migration to v2 for OTC"
+        );
+
+        let mut translated: u64 = 0;
+        Orders::<T>::translate(|_, old: OrderV1<T::AccountId, T::AssetId, T::BlockNumber>| {
+            translated.saturating_inc();
+            Some(OrderV2 {
+                owner: old.owner,
+                asset_in: old.asset_in,
+                asset_out: old.asset_out,
+                amount_in: old.amount_in,
+                amount_out: old.amount_out,
+                partially_fillable: old.partially_fillable,
+                expires_at: old.expires_at,
+                allowed_takers: None,
+            })
+        });
+
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(translated, translated.saturating_add(1))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 2, "Unexpected storage version.");
+
+        for (_, order) in Orders::<T>::iter() {
+            assert!(order.allowed_takers.is_none(), "Unexpected allowed takers.");
+        }
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: POST checks successful!"
+        );
+    }
+}
+
+/// Open orders are indexed by their pair and by their owner.
+pub mod v3 {
+    use super::*;
+    use v2::Orders;
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 2, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::otc",
+            "Running migration to v3 for OTC"
+        );
+
+        let mut indexed: u64 = 0;
+        for (order_id, order) in <Orders<T>>::iter() {
+            indexed.saturating_inc();
+            <OrdersByPair<T>>::insert((order.asset_in, order.asset_out), order_id, ());
+            <OrdersByOwner<T>>::insert(&order.owner, order_id, ());
+        }
+
+        StorageVersion::new(3).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(indexed, indexed.saturating_mul(2).saturating_add(1))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 3, "Unexpected storage version.");
+
+        for (order_id, order) in <Orders<T>>::iter() {
+            assert!(
+                <OrdersByPair<T>>::contains_key((order.asset_in, order.asset_out), order_id),
+                "Order missing in pair index."
+            );
+            assert!(
+                <OrdersByOwner<T>>::contains_key(&order.owner, order_id),
+                "Order missing in owner index."
+            );
+        }
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: POST checks successful!"
+        );
+    }
+}
+
+/// Orders are extended with an optional oracle peg.
+pub mod v4 {
+    use super::*;
+    use v2::OrderV2;
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 3, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::otc",
+            "Running migration to v4 for OTC"
+        );
+
+        let mut translated: u64 = 0;
+        <Orders<T>>::translate(
+            |_, old: OrderV2<T::AccountId, T::AssetId, T::BlockNumber, AllowedTakersOf<T>>| {
+                translated.saturating_inc();
+                Some(Order {
+                    owner: old.owner,
+                    asset_in: old.asset_in,
+                    asset_out: old.asset_out,
+                    amount_in: old.amount_in,
+                    amount_out: old.amount_out,
+                    partially_fillable: old.partially_fillable,
+                    expires_at: old.expires_at,
+                    allowed_takers: old.allowed_takers,
+                    pegged_to: None,
+                })
+            },
+        );
+
+        StorageVersion::new(4).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(translated, translated.saturating_add(1))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 4, "Unexpected storage version.");
+
+        for (_, order) in <Orders<T>>::iter() {
+            assert!(order.pegged_to.is_none(), "Unexpected oracle peg.");
+        }
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: POST checks successful!"
+        );
+    }
+}
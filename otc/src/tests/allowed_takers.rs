@@ -0,0 +1,134 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::tests::mock::*;
+use crate::{AllowedTakersOf, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use pretty_assertions::assert_eq;
+
+fn allowed_takers(takers: Vec<AccountId>) -> AllowedTakersOf<Test> {
+    takers.try_into().unwrap()
+}
+
+fn place_private_order(takers: Vec<AccountId>) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        true,
+        None,
+        Some(allowed_takers(takers))
+    ));
+}
+
+#[test]
+fn place_order_should_store_allowed_takers() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_private_order(vec![BOB, CHARLIE]);
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.allowed_takers, Some(allowed_takers(vec![BOB, CHARLIE])));
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: None,
+            allowed_takers: Some(allowed_takers(vec![BOB, CHARLIE])),
+            pegged_to: None,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_allowed_takers_are_empty() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                100 * ONE,
+                true,
+                None,
+                Some(allowed_takers(vec![]))
+            ),
+            Error::<Test>::NoAllowedTakers
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_work_when_caller_is_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_private_order(vec![BOB]);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_caller_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_private_order(vec![CHARLIE]);
+
+        // Act & Assert
+        assert_noop!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0), Error::<Test>::Forbidden);
+    });
+}
+
+#[test]
+fn partial_fill_order_should_work_when_caller_is_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_private_order(vec![CHARLIE, BOB]);
+
+        // Act
+        assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 5 * ONE));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 15 * ONE);
+    });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_caller_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_private_order(vec![CHARLIE]);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 5 * ONE),
+            Error::<Test>::Forbidden
+        );
+    });
+}
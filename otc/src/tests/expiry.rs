@@ -0,0 +1,279 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate as otc;
+use crate::tests::mock::*;
+use crate::weights::WeightInfo;
+use crate::{Error, Event, ExpiringOrders};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+const EXPIRES_AT: u64 = 10;
+
+fn place_expiring_order() {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        true,
+        Some(EXPIRES_AT),
+        None
+    ));
+}
+
+#[test]
+fn place_order_should_store_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_expiring_order();
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.expires_at, Some(EXPIRES_AT));
+        assert!(ExpiringOrders::<Test>::contains_key(EXPIRES_AT, 0));
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: Some(EXPIRES_AT),
+            allowed_takers: None,
+            pegged_to: None,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_expiry_is_not_in_the_future() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                100 * ONE,
+                true,
+                Some(1),
+                None
+            ),
+            Error::<Test>::InvalidExpiry
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_work_before_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT - 1);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(!ExpiringOrders::<Test>::contains_key(EXPIRES_AT, 0));
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_order_has_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_order_has_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 5 * ONE),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn cancel_order_should_remove_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+
+        // Act
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(!ExpiringOrders::<Test>::contains_key(EXPIRES_AT, 0));
+    });
+}
+
+#[test]
+fn cleanup_expired_should_release_reserve_and_pay_reward() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT);
+
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+        // Act
+        assert_ok!(OTC::cleanup_expired(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        let expected_reward = ONE;
+
+        assert!(OTC::orders(0).is_none());
+        assert!(!ExpiringOrders::<Test>::contains_key(EXPIRES_AT, 0));
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(
+            Tokens::free_balance(HDX, &ALICE),
+            alice_hdx_balance_before + 100 * ONE - expected_reward
+        );
+        assert_eq!(
+            Tokens::free_balance(HDX, &BOB),
+            bob_hdx_balance_before + expected_reward
+        );
+
+        expect_events(vec![Event::Expired {
+            order_id: 0,
+            reward: expected_reward,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn cleanup_expired_should_throw_error_when_order_has_not_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT - 1);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::cleanup_expired(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OrderNotExpired
+        );
+    });
+}
+
+#[test]
+fn cleanup_expired_should_throw_error_when_order_has_no_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            None
+        ));
+        System::set_block_number(EXPIRES_AT);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::cleanup_expired(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OrderNotExpired
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_remove_expired_orders() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+        System::set_block_number(EXPIRES_AT);
+
+        // Act
+        OTC::on_idle(EXPIRES_AT, Weight::MAX);
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(!ExpiringOrders::<Test>::contains_key(EXPIRES_AT, 0));
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before + 100 * ONE);
+        assert_eq!(OTC::expiry_cursor(), EXPIRES_AT + 1);
+
+        expect_events(vec![Event::Expired { order_id: 0, reward: 0 }.into()]);
+    });
+}
+
+#[test]
+fn on_idle_should_not_remove_orders_before_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT - 1);
+
+        // Act
+        OTC::on_idle(EXPIRES_AT - 1, Weight::MAX);
+
+        // Assert
+        assert!(OTC::orders(0).is_some());
+        assert_eq!(OTC::expiry_cursor(), EXPIRES_AT);
+    });
+}
+
+#[test]
+fn on_idle_should_remove_expired_orders_within_remaining_weight() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_expiring_order();
+        place_expiring_order();
+        System::set_block_number(EXPIRES_AT);
+
+        // Act
+        OTC::on_idle(EXPIRES_AT, <() as WeightInfo>::remove_expired_order());
+
+        // Assert
+        assert_eq!(otc::Orders::<Test>::iter().count(), 1);
+        assert_eq!(OTC::expiry_cursor(), EXPIRES_AT);
+
+        // Act
+        OTC::on_idle(EXPIRES_AT, <() as WeightInfo>::remove_expired_order());
+
+        // Assert
+        assert_eq!(otc::Orders::<Test>::iter().count(), 0);
+        assert_eq!(OTC::expiry_cursor(), EXPIRES_AT + 1);
+    });
+}
@@ -0,0 +1,274 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::{PoolType, TradeExecution};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+fn place_dai_hdx_order() {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        true,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn fill_order_should_charge_fee_on_amount_out() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 20 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 99 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &FEE_RECEIVER), ONE);
+            assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+
+            expect_events(vec![Event::Filled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                fee: ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn fill_order_should_charge_fee_on_amount_in_when_configured() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(5), true)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+            let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 19 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 20 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 100 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &FEE_RECEIVER), ONE);
+
+            expect_events(vec![Event::Filled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                fee: ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn partial_fill_order_should_charge_fee_and_keep_remaining_order_amounts() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(2), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+            // Assert
+            let order = OTC::orders(0).unwrap();
+            assert_eq!(order.amount_in, 10 * ONE);
+            assert_eq!(order.amount_out, 50 * ONE);
+            assert_eq!(
+                Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+                50 * ONE
+            );
+
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 49 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &FEE_RECEIVER), ONE);
+
+            expect_events(vec![Event::PartiallyFilled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 10 * ONE,
+                amount_out: 50 * ONE,
+                fee: ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_remaining_amount_is_too_low_with_fee() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(2), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            // Act & Assert
+            assert_noop!(
+                OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 16 * ONE),
+                Error::<Test>::OrderAmountTooSmall
+            );
+        });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_remaining_amount_is_too_low_with_fee_on_amount_in() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(5), true)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            // Act & Assert
+            assert_noop!(
+                OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 16 * ONE),
+                Error::<Test>::OrderAmountTooSmall
+            );
+        });
+}
+
+#[test]
+fn calculate_sell_and_buy_should_account_for_fee_on_amount_out() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(2), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            // Act & Assert
+            assert_eq!(OTC::calculate_sell(PoolType::OTC(0), DAI, HDX, 10 * ONE), Ok(49 * ONE));
+            assert_eq!(OTC::calculate_buy(PoolType::OTC(0), DAI, HDX, 49 * ONE), Ok(10 * ONE));
+        });
+}
+
+#[test]
+fn execute_buy_should_deliver_exact_amount_out_when_fee_is_charged() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(2), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+            let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+
+            // Act
+            assert_ok!(in_transaction(|| OTC::execute_buy(
+                RuntimeOrigin::signed(BOB),
+                PoolType::OTC(0),
+                DAI,
+                HDX,
+                49 * ONE,
+                10 * ONE
+            )));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 49 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 10 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &FEE_RECEIVER), ONE);
+
+            let order = OTC::orders(0).unwrap();
+            assert_eq!(order.amount_in, 10 * ONE);
+            assert_eq!(order.amount_out, 50 * ONE);
+        });
+}
+
+#[test]
+fn match_orders_should_charge_fee_on_amounts_received_by_both_owners() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_dai_hdx_order();
+            assert_ok!(OTC::place_order(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                50 * ONE,
+                15 * ONE,
+                true,
+                None,
+                None
+            ));
+
+            let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+            // Assert
+            // Alice received 10 DAI and Bob received 50 HDX, minus the fee
+            assert_eq!(
+                Tokens::free_balance(DAI, &ALICE),
+                alice_dai_balance_before + 10 * ONE - ONE / 10
+            );
+            assert_eq!(
+                Tokens::free_balance(HDX, &BOB),
+                bob_hdx_balance_before + 50 * ONE - ONE / 2
+            );
+            assert_eq!(Tokens::free_balance(DAI, &FEE_RECEIVER), ONE / 10);
+            assert_eq!(Tokens::free_balance(HDX, &FEE_RECEIVER), ONE / 2);
+            // the price improvement is not charged
+            assert_eq!(Tokens::free_balance(DAI, &CHARLIE), 5 * ONE);
+            assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, DAI, &BOB), 0);
+
+            expect_events(vec![Event::Matched {
+                order_a: 0,
+                order_b: 1,
+                amount_a: 50 * ONE,
+                amount_b: 15 * ONE,
+                surplus: 5 * ONE,
+                surplus_receiver: CHARLIE,
+                fee_a: ONE / 10,
+                fee_b: ONE / 2,
+            }
+            .into()]);
+        });
+}
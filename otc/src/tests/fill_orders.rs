@@ -0,0 +1,239 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{Error, Event, FillsOf, OrderId};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+fn place_order(
+    asset_in: AssetId,
+    asset_out: AssetId,
+    amount_in: Balance,
+    amount_out: Balance,
+    partially_fillable: bool,
+) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        partially_fillable,
+        None,
+        None
+    ));
+}
+
+fn fills(fills: Vec<(OrderId, Option<Balance>)>) -> FillsOf<Test> {
+    fills.try_into().unwrap()
+}
+
+#[test]
+fn fill_orders_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(DAI, HDX, 10 * ONE, 50 * ONE, true);
+
+        let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+        // Act
+        assert_ok!(OTC::fill_orders(
+            RuntimeOrigin::signed(BOB),
+            fills(vec![(0, None), (1, Some(5 * ONE))]),
+            25 * ONE,
+            125 * ONE
+        ));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        let order = OTC::orders(1).unwrap();
+        assert_eq!(order.amount_in, 5 * ONE);
+        assert_eq!(order.amount_out, 25 * ONE);
+
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 25 * ONE);
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            25 * ONE
+        );
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 25 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 125 * ONE);
+
+        expect_events(vec![
+            Event::Filled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                fee: 0,
+            }
+            .into(),
+            Event::PartiallyFilled {
+                order_id: 1,
+                who: BOB,
+                amount_in: 5 * ONE,
+                amount_out: 25 * ONE,
+                fee: 0,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn fill_orders_should_fill_order_completely_when_amount_equals_order_amount() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, false);
+
+        // Act
+        assert_ok!(OTC::fill_orders(
+            RuntimeOrigin::signed(BOB),
+            fills(vec![(0, Some(20 * ONE))]),
+            20 * ONE,
+            100 * ONE
+        ));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+    });
+}
+
+#[test]
+fn fill_orders_should_throw_error_when_orders_have_different_pairs() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(HDX, DAI, 100 * ONE, 20 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_orders(
+                RuntimeOrigin::signed(BOB),
+                fills(vec![(0, None), (1, None)]),
+                Balance::MAX,
+                0
+            ),
+            Error::<Test>::AssetMismatch
+        );
+    });
+}
+
+#[test]
+fn fill_orders_should_throw_error_when_max_amount_in_is_exceeded() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(DAI, HDX, 10 * ONE, 50 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_orders(
+                RuntimeOrigin::signed(BOB),
+                fills(vec![(0, None), (1, None)]),
+                30 * ONE - 1,
+                0
+            ),
+            Error::<Test>::TradeLimitReached
+        );
+    });
+}
+
+#[test]
+fn fill_orders_should_throw_error_when_min_amount_out_is_not_reached() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(DAI, HDX, 10 * ONE, 50 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_orders(
+                RuntimeOrigin::signed(BOB),
+                fills(vec![(0, None), (1, None)]),
+                30 * ONE,
+                150 * ONE + 1
+            ),
+            Error::<Test>::TradeLimitReached
+        );
+    });
+}
+
+#[test]
+fn fill_orders_should_apply_min_amount_out_after_fee() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1), false)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+
+            // Act & Assert
+            assert_noop!(
+                OTC::fill_orders(RuntimeOrigin::signed(BOB), fills(vec![(0, None)]), 20 * ONE, 100 * ONE),
+                Error::<Test>::TradeLimitReached
+            );
+            assert_ok!(OTC::fill_orders(
+                RuntimeOrigin::signed(BOB),
+                fills(vec![(0, None)]),
+                20 * ONE,
+                99 * ONE
+            ));
+        });
+}
+
+#[test]
+fn fill_orders_should_not_fill_any_order_when_one_fill_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(DAI, HDX, 10 * ONE, 50 * ONE, false);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_orders(
+                RuntimeOrigin::signed(BOB),
+                fills(vec![(0, None), (1, Some(5 * ONE))]),
+                Balance::MAX,
+                0
+            ),
+            Error::<Test>::OrderNotPartiallyFillable
+        );
+    });
+}
+
+#[test]
+fn fill_orders_should_throw_error_when_order_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(DAI, HDX, 20 * ONE, 100 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_orders(
+                RuntimeOrigin::signed(BOB),
+                fills(vec![(0, None), (1, None)]),
+                Balance::MAX,
+                0
+            ),
+            Error::<Test>::OrderNotFound
+        );
+    });
+}
@@ -0,0 +1,212 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::tests::mock::*;
+use crate::{OrderId, OrdersByOwner, OrdersByPair};
+use frame_support::assert_ok;
+use pretty_assertions::assert_eq;
+
+fn place_order(owner: AccountId, asset_in: AssetId, asset_out: AssetId, amount_in: Balance, amount_out: Balance) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(owner),
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        true,
+        None,
+        None
+    ));
+}
+
+fn is_indexed(order_id: OrderId, owner: AccountId, asset_in: AssetId, asset_out: AssetId) -> bool {
+    let by_pair = OrdersByPair::<Test>::contains_key((asset_in, asset_out), order_id);
+    let by_owner = OrdersByOwner::<Test>::contains_key(owner, order_id);
+    assert_eq!(by_pair, by_owner);
+    by_pair
+}
+
+fn order_ids(orders: Vec<(OrderId, crate::OrderOf<Test>)>) -> Vec<OrderId> {
+    orders.into_iter().map(|(order_id, _)| order_id).collect()
+}
+
+#[test]
+fn place_order_should_index_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Assert
+        assert!(is_indexed(0, ALICE, DAI, HDX));
+        assert!(!OrdersByPair::<Test>::contains_key((HDX, DAI), 0));
+    });
+}
+
+#[test]
+fn fill_order_should_remove_order_from_indexes() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(!is_indexed(0, ALICE, DAI, HDX));
+    });
+}
+
+#[test]
+fn partial_fill_order_should_keep_order_in_indexes() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+        // Assert
+        assert!(is_indexed(0, ALICE, DAI, HDX));
+    });
+}
+
+#[test]
+fn cancel_order_should_remove_order_from_indexes() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(!is_indexed(0, ALICE, DAI, HDX));
+    });
+}
+
+#[test]
+fn cleanup_expired_should_remove_order_from_indexes() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        System::set_block_number(5);
+
+        // Act
+        assert_ok!(OTC::cleanup_expired(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(!is_indexed(0, ALICE, DAI, HDX));
+    });
+}
+
+#[test]
+fn match_orders_should_remove_only_filled_order_from_indexes() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(BOB, HDX, DAI, 50 * ONE, 15 * ONE);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        assert!(is_indexed(0, ALICE, DAI, HDX));
+        assert!(!is_indexed(1, BOB, HDX, DAI));
+    });
+}
+
+#[test]
+fn orders_for_pair_should_return_orders_sorted_by_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(ALICE, DAI, HDX, 10 * ONE, 100 * ONE);
+        place_order(BOB, DAI, HDX, 30 * ONE, 100 * ONE);
+        place_order(BOB, HDX, DAI, 100 * ONE, 20 * ONE);
+        place_order(BOB, DAI, HDX, 10 * ONE, 50 * ONE);
+
+        // Act
+        let orders = OTC::orders_for_pair(DAI, HDX, 0, 10);
+
+        // Assert
+        assert_eq!(order_ids(orders), vec![1, 0, 4, 2]);
+        assert_eq!(order_ids(OTC::orders_for_pair(HDX, DAI, 0, 10)), vec![3]);
+    });
+}
+
+#[test]
+fn orders_for_pair_should_return_requested_page() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(ALICE, DAI, HDX, 10 * ONE, 100 * ONE);
+        place_order(BOB, DAI, HDX, 30 * ONE, 100 * ONE);
+
+        // Act & Assert
+        assert_eq!(order_ids(OTC::orders_for_pair(DAI, HDX, 0, 2)), vec![1, 0]);
+        assert_eq!(order_ids(OTC::orders_for_pair(DAI, HDX, 2, 2)), vec![2]);
+        assert_eq!(order_ids(OTC::orders_for_pair(DAI, HDX, 3, 2)), Vec::<OrderId>::new());
+    });
+}
+
+#[test]
+fn orders_for_pair_should_skip_expired_orders() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            10 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        place_order(BOB, DAI, HDX, 20 * ONE, 100 * ONE);
+        System::set_block_number(5);
+
+        // Act
+        let orders = OTC::orders_for_pair(DAI, HDX, 0, 10);
+
+        // Assert
+        assert_eq!(order_ids(orders), vec![1]);
+    });
+}
+
+#[test]
+fn orders_of_owner_should_return_orders_of_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(BOB, DAI, HDX, 30 * ONE, 100 * ONE);
+        place_order(ALICE, HDX, DAI, 100 * ONE, 20 * ONE);
+        place_order(ALICE, DAI, HDX, 10 * ONE, 100 * ONE);
+
+        // Act & Assert
+        assert_eq!(order_ids(OTC::orders_of_owner(&ALICE, 0, 10)), vec![0, 2, 3]);
+        assert_eq!(order_ids(OTC::orders_of_owner(&ALICE, 1, 1)), vec![2]);
+        assert_eq!(order_ids(OTC::orders_of_owner(&BOB, 0, 10)), vec![1]);
+        assert_eq!(order_ids(OTC::orders_of_owner(&CHARLIE, 0, 10)), Vec::<OrderId>::new());
+    });
+}
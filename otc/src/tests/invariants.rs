@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use crate::tests::mock::*;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
 use proptest::prelude::*;
 use sp_runtime::{FixedPointNumber, FixedU128};
 use std::cmp::min;
@@ -99,7 +100,9 @@ proptest! {
                 HDX,
                 initial_amount_in,
                 initial_amount_out,
-                true
+                true,
+                None,
+                None
             ).unwrap();
 
             let initial_price = FixedU128::from_rational(initial_amount_out, initial_amount_in);
@@ -118,3 +121,88 @@ proptest! {
         });
     }
 }
+
+fn total_balance(asset: AssetId) -> Balance {
+    [ALICE, BOB, CHARLIE]
+        .iter()
+        .map(|who| Tokens::total_balance(asset, who))
+        .sum()
+}
+
+fn reserved_balance(asset: AssetId, who: AccountId) -> Balance {
+    Tokens::reserved_balance_named(&crate::NAMED_RESERVE_ID, asset, &who)
+}
+
+fn order_amount(max: Balance) -> impl Strategy<Value = Balance> {
+    MIN_ORDER_SIZE * ONE..max * ONE
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1_000))]
+    #[test]
+    fn match_orders_reserves_invariant(
+        amount_in_a in order_amount(100),
+        amount_out_a in order_amount(100),
+        amount_in_b in order_amount(100),
+        amount_out_b in order_amount(100),
+    ) {
+        ExtBuilder::default()
+        .build()
+        .execute_with(|| {
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                amount_in_a,
+                amount_out_a,
+                true,
+                None,
+                None
+            ).unwrap();
+            OTC::place_order(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                amount_in_b,
+                amount_out_b,
+                true,
+                None,
+                None
+            ).unwrap();
+
+            let hdx_total_before = total_balance(HDX);
+            let dai_total_before = total_balance(DAI);
+            let alice_hdx_before = Tokens::total_balance(HDX, &ALICE);
+            let alice_dai_before = Tokens::total_balance(DAI, &ALICE);
+            let bob_hdx_before = Tokens::total_balance(HDX, &BOB);
+            let bob_dai_before = Tokens::total_balance(DAI, &BOB);
+
+            if OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1).is_err() {
+                return;
+            }
+
+            // no asset is created or destroyed
+            assert_eq!(total_balance(HDX), hdx_total_before);
+            assert_eq!(total_balance(DAI), dai_total_before);
+
+            // reserves always back the remaining amounts of the orders
+            let remaining_a = OTC::orders(0).map_or(0, |order| order.amount_out);
+            let remaining_b = OTC::orders(1).map_or(0, |order| order.amount_out);
+            assert_eq!(reserved_balance(HDX, ALICE), remaining_a);
+            assert_eq!(reserved_balance(DAI, BOB), remaining_b);
+
+            // the amounts released from the reserves are exactly the amounts paid out
+            let sold_a = amount_out_a - remaining_a;
+            let sold_b = amount_out_b - remaining_b;
+            assert_eq!(Tokens::total_balance(HDX, &ALICE), alice_hdx_before - sold_a);
+            assert_eq!(Tokens::total_balance(HDX, &BOB), bob_hdx_before + sold_a);
+            assert_eq!(Tokens::total_balance(DAI, &BOB), bob_dai_before - sold_b);
+            let received_a = Tokens::total_balance(DAI, &ALICE) - alice_dai_before;
+            assert_eq!(received_a + Tokens::total_balance(DAI, &CHARLIE), sold_b);
+
+            // both orders are filled at least at their own price
+            assert!(received_a * amount_out_a >= sold_a * amount_in_a);
+            assert!(sold_a * amount_out_b >= sold_b * amount_in_b);
+        });
+    }
+}
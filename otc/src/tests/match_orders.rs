@@ -0,0 +1,353 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{AllowedTakersOf, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+fn place_order(
+    owner: AccountId,
+    asset_in: AssetId,
+    asset_out: AssetId,
+    amount_in: Balance,
+    amount_out: Balance,
+    partially_fillable: bool,
+) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(owner),
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        partially_fillable,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn match_orders_should_work_when_first_order_is_partially_filled() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(BOB, HDX, DAI, 50 * ONE, 15 * ONE, true);
+
+        let alice_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+        let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 10 * ONE);
+        assert_eq!(order.amount_out, 50 * ONE);
+        assert!(OTC::orders(1).is_none());
+
+        // Alice sold 50 HDX for 10 DAI at her own price
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), alice_hdx_balance_before);
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            50 * ONE
+        );
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 10 * ONE);
+
+        // Bob sold 15 DAI for 50 HDX at his own price
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 50 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before);
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, DAI, &BOB), 0);
+
+        // Charlie received the price improvement
+        assert_eq!(Tokens::free_balance(DAI, &CHARLIE), 5 * ONE);
+
+        expect_events(vec![Event::Matched {
+            order_a: 0,
+            order_b: 1,
+            amount_a: 50 * ONE,
+            amount_b: 15 * ONE,
+            surplus: 5 * ONE,
+            surplus_receiver: CHARLIE,
+            fee_a: 0,
+            fee_b: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn match_orders_should_work_when_second_order_is_partially_filled() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 15 * ONE, 50 * ONE, true);
+        place_order(BOB, HDX, DAI, 100 * ONE, 40 * ONE, true);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        let order = OTC::orders(1).unwrap();
+        assert_eq!(order.amount_in, 50 * ONE);
+        assert_eq!(order.amount_out, 20 * ONE);
+
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, DAI, &BOB),
+            20 * ONE
+        );
+        assert_eq!(Tokens::free_balance(DAI, &CHARLIE), 5 * ONE);
+
+        expect_events(vec![Event::Matched {
+            order_a: 0,
+            order_b: 1,
+            amount_a: 50 * ONE,
+            amount_b: 20 * ONE,
+            surplus: 5 * ONE,
+            surplus_receiver: CHARLIE,
+            fee_a: 0,
+            fee_b: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn match_orders_should_fill_both_orders_when_amounts_overlap_exactly() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, false);
+        place_order(BOB, HDX, DAI, 100 * ONE, 25 * ONE, false);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(OTC::orders(1).is_none());
+
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, DAI, &BOB), 0);
+        assert_eq!(Tokens::free_balance(DAI, &CHARLIE), 5 * ONE);
+    });
+}
+
+#[test]
+fn match_orders_should_not_transfer_surplus_when_prices_are_equal() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(BOB, HDX, DAI, 100 * ONE, 20 * ONE, true);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(OTC::orders(1).is_none());
+        assert_eq!(Tokens::free_balance(DAI, &CHARLIE), 0);
+
+        expect_events(vec![Event::Matched {
+            order_a: 0,
+            order_b: 1,
+            amount_a: 100 * ONE,
+            amount_b: 20 * ONE,
+            surplus: 0,
+            surplus_receiver: CHARLIE,
+            fee_a: 0,
+            fee_b: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn match_orders_should_pay_surplus_to_surplus_receiver_when_configured() {
+    ExtBuilder::default()
+        .with_surplus_receiver(CHARLIE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+            place_order(BOB, HDX, DAI, 50 * ONE, 15 * ONE, true);
+
+            let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+
+            // Act
+            assert_ok!(OTC::match_orders(RuntimeOrigin::signed(ALICE), 0, 1));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 10 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &CHARLIE), 5 * ONE);
+
+            expect_events(vec![Event::Matched {
+                order_a: 0,
+                order_b: 1,
+                amount_a: 50 * ONE,
+                amount_b: 15 * ONE,
+                surplus: 5 * ONE,
+                surplus_receiver: CHARLIE,
+                fee_a: 0,
+                fee_b: 0,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_order_is_matched_with_itself() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 0),
+            Error::<Test>::CannotMatchOrderWithItself
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_order_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrderNotFound
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_orders_are_on_the_same_side() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(BOB, DAI, HDX, 10 * ONE, 100 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrdersNotCrossing
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_prices_do_not_cross() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(BOB, HDX, DAI, 100 * ONE, 15 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrdersNotCrossing
+        );
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 1, 0),
+            Error::<Test>::OrdersNotCrossing
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_partially_filled_order_is_not_partially_fillable() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, false);
+        place_order(BOB, HDX, DAI, 50 * ONE, 15 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrderNotPartiallyFillable
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_remaining_amount_is_too_small() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE, true);
+        place_order(BOB, HDX, DAI, 98 * ONE, 20 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrderAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_order_is_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        place_order(BOB, HDX, DAI, 50 * ONE, 15 * ONE, true);
+
+        System::set_block_number(5);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_counterparty_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let allowed_takers: AllowedTakersOf<Test> = vec![CHARLIE].try_into().unwrap();
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(allowed_takers)
+        ));
+        place_order(BOB, HDX, DAI, 50 * ONE, 15 * ONE, true);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::Forbidden
+        );
+    });
+}
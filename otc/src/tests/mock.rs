@@ -16,18 +16,26 @@
 use crate as otc;
 use crate::Config;
 use frame_support::{
+    dispatch::DispatchResult,
     parameter_types,
-    traits::{Everything, GenesisBuild, Nothing},
+    storage::with_transaction,
+    traits::{ConstU32, Everything, GenesisBuild, Get, Nothing},
+    weights::Weight,
 };
 use frame_system as system;
-use hydradx_traits::Registry;
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OracleLastUpdate, OraclePeriod, Source},
+    router::ExecutorError,
+    Registry,
+};
 use orml_tokens::AccountData;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
-    DispatchError,
+    traits::{BlakeTwo256, IdentityLookup, Zero},
+    DispatchError, Permill, TransactionOutcome,
 };
 use std::{cell::RefCell, collections::HashMap};
 
@@ -49,6 +57,8 @@ pub const ONE: Balance = 1_000_000_000_000;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const FEE_RECEIVER: AccountId = 4;
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -66,11 +76,18 @@ thread_local! {
     pub static REGISTERED_ASSETS: RefCell<HashMap<AssetId, u32>> = RefCell::new(HashMap::default());
     pub static EXISTENTIAL_DEPOSIT: RefCell<HashMap<AssetId, u128>>= RefCell::new(HashMap::default());
     pub static PRECISIONS: RefCell<HashMap<AssetId, u32>>= RefCell::new(HashMap::default());
+    pub static SURPLUS_RECEIVER: RefCell<Option<AccountId>> = RefCell::new(None);
+    pub static FEE: RefCell<(Permill, bool)> = RefCell::new((Permill::zero(), false));
+    pub static ORACLE_PRICE: RefCell<Option<(EmaPrice, u64)>> = RefCell::new(None);
+    pub static ORACLE_LAST_UPDATE: RefCell<Option<u64>> = RefCell::new(None);
 }
 
 parameter_types! {
     pub NativeCurrencyId: AssetId = HDX;
     pub ExistentialDepositMultiplier: u8 = 5;
+    pub CleanupReward: Permill = Permill::from_percent(1);
+    pub const FeeReceiver: AccountId = FEE_RECEIVER;
+    pub const MaxOracleAge: u64 = 10;
 }
 
 parameter_type_with_key! {
@@ -79,6 +96,59 @@ parameter_type_with_key! {
     };
 }
 
+pub struct SurplusReceiver;
+
+impl Get<Option<AccountId>> for SurplusReceiver {
+    fn get() -> Option<AccountId> {
+        SURPLUS_RECEIVER.with(|v| *v.borrow())
+    }
+}
+
+pub struct Fee;
+
+impl Get<Permill> for Fee {
+    fn get() -> Permill {
+        FEE.with(|v| v.borrow().0)
+    }
+}
+
+pub struct FeeOnAmountIn;
+
+impl Get<bool> for FeeOnAmountIn {
+    fn get() -> bool {
+        FEE.with(|v| v.borrow().1)
+    }
+}
+
+pub struct PriceOracle;
+
+impl AggregatedPriceOracle<AssetId, u64, EmaPrice> for PriceOracle {
+    type Error = ();
+
+    fn get_price(
+        _asset_a: AssetId,
+        _asset_b: AssetId,
+        _period: OraclePeriod,
+        _source: Source,
+    ) -> Result<(EmaPrice, u64), Self::Error> {
+        ORACLE_PRICE.with(|v| *v.borrow()).ok_or(())
+    }
+
+    fn get_price_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl OracleLastUpdate<AssetId, u64> for PriceOracle {
+    fn last_update(_asset_a: AssetId, _asset_b: AssetId, _source: Source) -> Option<u64> {
+        ORACLE_LAST_UPDATE.with(|v| *v.borrow())
+    }
+
+    fn last_update_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 impl Config for Test {
     type AssetId = AssetId;
     type AssetRegistry = DummyRegistry<Test>;
@@ -86,6 +156,15 @@ impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ExistentialDeposits = ExistentialDeposits;
     type ExistentialDepositMultiplier = ExistentialDepositMultiplier;
+    type CleanupReward = CleanupReward;
+    type MaxAllowedTakers = ConstU32<3>;
+    type MaxFillsPerBatch = ConstU32<5>;
+    type SurplusReceiver = SurplusReceiver;
+    type Fee = Fee;
+    type FeeOnAmountIn = FeeOnAmountIn;
+    type FeeReceiver = FeeReceiver;
+    type Oracle = PriceOracle;
+    type MaxOracleAge = MaxOracleAge;
     type WeightInfo = ();
 }
 
@@ -174,6 +253,20 @@ impl Default for ExtBuilder {
         EXISTENTIAL_DEPOSIT.with(|v| {
             v.borrow_mut().clear();
         });
+        SURPLUS_RECEIVER.with(|v| {
+            *v.borrow_mut() = None;
+        });
+        FEE.with(|v| {
+            *v.borrow_mut() = (Permill::zero(), false);
+        });
+        // price of 1 asset_out = 1/5 asset_in, e.g. 1 HDX = 0.2 DAI
+        ORACLE_PRICE.with(|v| {
+            *v.borrow_mut() = Some((EmaPrice::new(1, 5), 1));
+        });
+        // updated in the genesis block, so recent for the first blocks
+        ORACLE_LAST_UPDATE.with(|v| {
+            *v.borrow_mut() = Some(0);
+        });
 
         Self {
             endowed_accounts: vec![
@@ -198,6 +291,39 @@ impl ExtBuilder {
 
         self
     }
+
+    pub fn with_surplus_receiver(self, receiver: AccountId) -> Self {
+        SURPLUS_RECEIVER.with(|v| {
+            *v.borrow_mut() = Some(receiver);
+        });
+
+        self
+    }
+
+    pub fn with_fee(self, fee: Permill, on_amount_in: bool) -> Self {
+        FEE.with(|v| {
+            *v.borrow_mut() = (fee, on_amount_in);
+        });
+
+        self
+    }
+
+    pub fn with_oracle_price(self, price: Option<(EmaPrice, u64)>) -> Self {
+        ORACLE_PRICE.with(|v| {
+            *v.borrow_mut() = price;
+        });
+
+        self
+    }
+
+    pub fn with_oracle_last_update(self, block: Option<u64>) -> Self {
+        ORACLE_LAST_UPDATE.with(|v| {
+            *v.borrow_mut() = block;
+        });
+
+        self
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
@@ -234,6 +360,29 @@ thread_local! {
     pub static DUMMYTHREADLOCAL: RefCell<u128> = RefCell::new(100);
 }
 
+pub fn in_transaction(
+    f: impl FnOnce() -> Result<(), ExecutorError<DispatchError>>,
+) -> Result<(), ExecutorError<DispatchError>> {
+    let mut result = Ok(());
+    let _: DispatchResult = with_transaction(|| {
+        result = f();
+        TransactionOutcome::Commit(Ok(()))
+    });
+    result
+}
+
+pub fn set_oracle_price(price: Option<(EmaPrice, u64)>) {
+    ORACLE_PRICE.with(|v| {
+        *v.borrow_mut() = price;
+    });
+}
+
+pub fn set_oracle_last_update(block: Option<u64>) {
+    ORACLE_LAST_UPDATE.with(|v| {
+        *v.borrow_mut() = block;
+    });
+}
+
 pub fn expect_events(e: Vec<RuntimeEvent>) {
     test_utils::expect_events::<RuntimeEvent, Test>(e);
 }
@@ -15,8 +15,17 @@
 
 pub mod mock;
 
+pub mod allowed_takers;
 pub mod cancel_order;
+pub mod expiry;
+pub mod fee;
 pub mod fill_order;
+pub mod fill_orders;
+pub mod indexes;
 pub mod invariants;
+pub mod match_orders;
 pub mod partial_fill_order;
+pub mod pegged_orders;
 pub mod place_order;
+pub mod trade_execution;
+pub mod update_order;
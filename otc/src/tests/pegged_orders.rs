@@ -0,0 +1,376 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{Error, Event, OraclePeg};
+use frame_support::{assert_noop, assert_ok};
+use hydra_dx_math::ema::EmaPrice;
+use hydradx_traits::oracle::OraclePeriod;
+use hydradx_traits::router::{PoolType, TradeExecution};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+fn peg(spread: i32) -> OraclePeg {
+    OraclePeg {
+        source: *b"omnipool",
+        period: OraclePeriod::Short,
+        spread,
+    }
+}
+
+fn place_pegged_order(spread: i32) {
+    assert_ok!(OTC::place_pegged_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        100 * ONE,
+        peg(spread),
+        true,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn place_pegged_order_should_calculate_amount_in_from_oracle_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_pegged_order(0);
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 20 * ONE);
+        assert_eq!(order.amount_out, 100 * ONE);
+        assert_eq!(order.pegged_to, Some(peg(0)));
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            100 * ONE
+        );
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: None,
+            allowed_takers: None,
+            pegged_to: Some(peg(0)),
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn place_pegged_order_should_apply_positive_spread() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_pegged_order(10_000);
+
+        // Assert
+        assert_eq!(OTC::orders(0).unwrap().amount_in, 20_200_000_000_000);
+    });
+}
+
+#[test]
+fn place_pegged_order_should_apply_negative_spread() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_pegged_order(-50_000);
+
+        // Assert
+        assert_eq!(OTC::orders(0).unwrap().amount_in, 19 * ONE);
+    });
+}
+
+#[test]
+fn place_pegged_order_should_round_amount_in_up() {
+    ExtBuilder::default()
+        .with_oracle_price(Some((EmaPrice::new(1, 3), 1)))
+        .build()
+        .execute_with(|| {
+            // Act
+            place_pegged_order(0);
+
+            // Assert
+            assert_eq!(OTC::orders(0).unwrap().amount_in, 33_333_333_333_334);
+        });
+}
+
+#[test]
+fn place_pegged_order_should_throw_error_when_spread_is_out_of_bounds() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        for spread in [-1_000_000, 1_000_001] {
+            assert_noop!(
+                OTC::place_pegged_order(
+                    RuntimeOrigin::signed(ALICE),
+                    DAI,
+                    HDX,
+                    100 * ONE,
+                    peg(spread),
+                    true,
+                    None,
+                    None
+                ),
+                Error::<Test>::InvalidSpread
+            );
+        }
+    });
+}
+
+#[test]
+fn place_pegged_order_should_throw_error_when_oracle_price_is_not_available() {
+    ExtBuilder::default().with_oracle_price(None).build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            OTC::place_pegged_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                100 * ONE,
+                peg(0),
+                true,
+                None,
+                None
+            ),
+            Error::<Test>::OraclePriceNotAvailable
+        );
+    });
+}
+
+#[test]
+fn place_pegged_order_should_throw_error_when_oracle_is_too_old() {
+    ExtBuilder::default()
+        .with_oracle_last_update(Some(1))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            System::set_block_number(MaxOracleAge::get() + 3);
+
+            // Act & Assert
+            assert_noop!(
+                OTC::place_pegged_order(
+                    RuntimeOrigin::signed(ALICE),
+                    DAI,
+                    HDX,
+                    100 * ONE,
+                    peg(0),
+                    true,
+                    None,
+                    None
+                ),
+                Error::<Test>::OracleTooOld
+            );
+        });
+}
+
+#[test]
+fn fill_order_should_use_current_oracle_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        set_oracle_price(Some((EmaPrice::new(1, 4), 1)));
+
+        let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 25 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 25 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 100 * ONE);
+
+        expect_events(vec![Event::Filled {
+            order_id: 0,
+            who: BOB,
+            amount_in: 25 * ONE,
+            amount_out: 100 * ONE,
+            fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_is_too_old() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        System::set_block_number(MaxOracleAge::get() + 2);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OracleTooOld
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_use_long_lived_oracle_with_recent_activity() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        System::set_block_number(10_001);
+        // the oracle exists for much longer than `MaxOracleAge`, but was updated in the parent block
+        set_oracle_price(Some((EmaPrice::new(1, 4), 10_000)));
+        set_oracle_last_update(Some(10_000));
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        expect_events(vec![Event::Filled {
+            order_id: 0,
+            who: BOB,
+            amount_in: 25 * ONE,
+            amount_out: 100 * ONE,
+            fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_young_oracle_has_no_recent_activity() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        System::set_block_number(MaxOracleAge::get() + 2);
+        // the oracle was initialized recently, but its data is from the genesis block
+        set_oracle_price(Some((EmaPrice::new(1, 4), 1)));
+        set_oracle_last_update(Some(0));
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OracleTooOld
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_was_never_updated() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        set_oracle_last_update(None);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OraclePriceNotAvailable
+        );
+    });
+}
+
+#[test]
+fn partial_fill_order_should_use_current_oracle_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        set_oracle_price(Some((EmaPrice::new(1, 2), 1)));
+
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+        // Act
+        assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_out, 80 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 20 * ONE);
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            80 * ONE
+        );
+    });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_oracle_price_is_not_available() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        set_oracle_price(None);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE),
+            Error::<Test>::OraclePriceNotAvailable
+        );
+    });
+}
+
+#[test]
+fn calculate_sell_should_use_current_oracle_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+        set_oracle_price(Some((EmaPrice::new(1, 2), 1)));
+
+        // Act & Assert
+        assert_eq!(OTC::calculate_sell(PoolType::OTC(0), DAI, HDX, 10 * ONE), Ok(20 * ONE));
+    });
+}
+
+#[test]
+fn orders_for_pair_should_sort_pegged_orders_by_current_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(BOB),
+            DAI,
+            HDX,
+            22 * ONE,
+            100 * ONE,
+            true,
+            None,
+            None
+        ));
+        place_pegged_order(0);
+
+        // Act & Assert
+        let order_ids = |orders: Vec<(crate::OrderId, crate::OrderOf<Test>)>| {
+            orders.into_iter().map(|(order_id, _)| order_id).collect::<Vec<_>>()
+        };
+        assert_eq!(order_ids(OTC::orders_for_pair(DAI, HDX, 0, 10)), vec![1, 0]);
+
+        set_oracle_price(Some((EmaPrice::new(1, 4), 1)));
+        assert_eq!(order_ids(OTC::orders_for_pair(DAI, HDX, 0, 10)), vec![0, 1]);
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_order_is_pegged() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(0);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 25 * ONE, 100 * ONE),
+            Error::<Test>::CannotUpdatePeggedOrder
+        );
+    });
+}
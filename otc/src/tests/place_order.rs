@@ -30,7 +30,9 @@ fn place_order_should_work() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         // Assert
@@ -49,6 +51,9 @@ fn place_order_should_work() {
             amount_in: order.amount_in,
             amount_out: 100 * ONE,
             partially_fillable: true,
+            expires_at: None,
+            allowed_takers: None,
+            pegged_to: None,
         }
         .into()]);
 
@@ -72,7 +77,9 @@ fn place_order_should_work_when_user_has_multiple_orders() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         assert_ok!(OTC::place_order(
@@ -81,7 +88,9 @@ fn place_order_should_work_when_user_has_multiple_orders() {
             HDX,
             10 * ONE,
             50 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         // Assert
@@ -97,7 +106,16 @@ fn place_order_should_throw_error_when_amount_is_higher_than_balance() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, HDX, 20 * ONE, 100_000 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                100_000 * ONE,
+                true,
+                None,
+                None
+            ),
             BalanceTooLow::<Test>
         );
     });
@@ -108,7 +126,16 @@ fn place_order_should_throw_error_when_asset_out_is_not_registered() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, DOGE, 20 * ONE, 100 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                DOGE,
+                20 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
             BalanceTooLow::<Test>
         );
     });
@@ -119,7 +146,16 @@ fn place_order_should_throw_error_when_asset_in_is_not_registered() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DOGE, HDX, 20 * ONE, 100 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DOGE,
+                HDX,
+                20 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
             Error::<Test>::AssetNotRegistered
         );
     });
@@ -130,7 +166,16 @@ fn place_order_should_throw_error_when_amount_in_is_too_low() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, HDX, 4 * ONE, 100 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                4 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
             Error::<Test>::OrderAmountTooSmall
         );
     });
@@ -141,7 +186,16 @@ fn place_order_should_throw_error_when_amount_out_is_too_low() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, HDX, 20 * ONE, 4 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                4 * ONE,
+                true,
+                None,
+                None
+            ),
             Error::<Test>::OrderAmountTooSmall
         );
     });
@@ -0,0 +1,263 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::tests::mock::*;
+use crate::{Error, Event};
+use frame_support::assert_ok;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+fn place_dai_hdx_order(partially_fillable: bool) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        partially_fillable,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn calculate_sell_should_return_amount_out_based_on_order_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(OTC::calculate_sell(PoolType::OTC(0), DAI, HDX, 5 * ONE), Ok(25 * ONE));
+        assert_eq!(OTC::calculate_sell(PoolType::OTC(0), DAI, HDX, 20 * ONE), Ok(100 * ONE));
+    });
+}
+
+#[test]
+fn calculate_buy_should_return_amount_in_based_on_order_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(OTC::calculate_buy(PoolType::OTC(0), DAI, HDX, 25 * ONE), Ok(5 * ONE));
+        assert_eq!(OTC::calculate_buy(PoolType::OTC(0), DAI, HDX, 100 * ONE), Ok(20 * ONE));
+    });
+}
+
+#[test]
+fn calculate_buy_should_round_amount_in_up() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(
+            OTC::calculate_buy(PoolType::OTC(0), DAI, HDX, 25 * ONE + 1),
+            Ok(5 * ONE + 1)
+        );
+    });
+}
+
+#[test]
+fn calculate_sell_should_not_be_supported_for_other_pool_types() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(
+            OTC::calculate_sell(PoolType::Omnipool, DAI, HDX, 5 * ONE),
+            Err(ExecutorError::NotSupported)
+        );
+    });
+}
+
+#[test]
+fn calculate_sell_should_fail_when_assets_do_not_match_the_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(
+            OTC::calculate_sell(PoolType::OTC(0), HDX, DAI, 5 * ONE),
+            Err(ExecutorError::Error(Error::<Test>::AssetMismatch.into()))
+        );
+    });
+}
+
+#[test]
+fn calculate_sell_should_fail_when_amount_exceeds_the_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(
+            OTC::calculate_sell(PoolType::OTC(0), DAI, HDX, 21 * ONE),
+            Err(ExecutorError::Error(Error::<Test>::OrderAmountExceeded.into()))
+        );
+    });
+}
+
+#[test]
+fn calculate_sell_should_fail_when_partial_amount_and_order_is_not_partially_fillable() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(false);
+
+        // Act & Assert
+        assert_eq!(
+            OTC::calculate_sell(PoolType::OTC(0), DAI, HDX, 5 * ONE),
+            Err(ExecutorError::Error(Error::<Test>::OrderNotPartiallyFillable.into()))
+        );
+    });
+}
+
+#[test]
+fn execute_sell_should_partially_fill_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+
+        // Act
+        assert_ok!(in_transaction(|| OTC::execute_sell(
+            RuntimeOrigin::signed(BOB),
+            PoolType::OTC(0),
+            DAI,
+            HDX,
+            5 * ONE,
+            25 * ONE
+        )));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 25 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 5 * ONE);
+
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 15 * ONE);
+        assert_eq!(order.amount_out, 75 * ONE);
+
+        expect_events(vec![Event::PartiallyFilled {
+            order_id: 0,
+            who: BOB,
+            amount_in: 5 * ONE,
+            amount_out: 25 * ONE,
+            fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn execute_sell_should_fill_order_when_amount_in_equals_order_amount_in() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(false);
+
+        // Act
+        assert_ok!(in_transaction(|| OTC::execute_sell(
+            RuntimeOrigin::signed(BOB),
+            PoolType::OTC(0),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE
+        )));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        expect_events(vec![Event::Filled {
+            order_id: 0,
+            who: BOB,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn execute_sell_should_fail_when_min_limit_is_not_reached() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(
+            in_transaction(|| OTC::execute_sell(
+                RuntimeOrigin::signed(BOB),
+                PoolType::OTC(0),
+                DAI,
+                HDX,
+                5 * ONE,
+                25 * ONE + 1
+            )),
+            Err(ExecutorError::Error(Error::<Test>::TradeLimitReached.into()))
+        );
+    });
+}
+
+#[test]
+fn execute_buy_should_partially_fill_order_with_exact_amount_out() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+
+        // Act
+        assert_ok!(in_transaction(|| OTC::execute_buy(
+            RuntimeOrigin::signed(BOB),
+            PoolType::OTC(0),
+            DAI,
+            HDX,
+            25 * ONE + 1,
+            5 * ONE + 1
+        )));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 25 * ONE + 1);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 5 * ONE - 1);
+
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 15 * ONE - 1);
+        assert_eq!(order.amount_out, 75 * ONE - 1);
+    });
+}
+
+#[test]
+fn execute_buy_should_fail_when_max_limit_is_exceeded() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_dai_hdx_order(true);
+
+        // Act & Assert
+        assert_eq!(
+            in_transaction(|| OTC::execute_buy(
+                RuntimeOrigin::signed(BOB),
+                PoolType::OTC(0),
+                DAI,
+                HDX,
+                25 * ONE,
+                5 * ONE - 1
+            )),
+            Err(ExecutorError::Error(Error::<Test>::TradeLimitReached.into()))
+        );
+    });
+}
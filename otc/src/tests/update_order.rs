@@ -0,0 +1,183 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_tokens::Error::BalanceTooLow;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+fn place_order(expires_at: Option<u64>) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        true,
+        expires_at,
+        None
+    ));
+}
+
+#[test]
+fn update_order_should_increase_reserve_when_amount_out_increases() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(None);
+        let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        assert_ok!(OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 25 * ONE, 120 * ONE));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 25 * ONE);
+        assert_eq!(order.amount_out, 120 * ONE);
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            120 * ONE
+        );
+        assert_eq!(
+            Tokens::free_balance(HDX, &ALICE),
+            alice_free_hdx_balance_before - 20 * ONE
+        );
+
+        expect_events(vec![Event::Updated {
+            order_id: 0,
+            amount_in: 25 * ONE,
+            amount_out: 120 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn update_order_should_decrease_reserve_when_amount_out_decreases() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(None);
+        let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+
+        // Act
+        assert_ok!(OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 20 * ONE, 80 * ONE));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 20 * ONE);
+        assert_eq!(order.amount_out, 80 * ONE);
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            80 * ONE
+        );
+        assert_eq!(
+            Tokens::free_balance(HDX, &ALICE),
+            alice_free_hdx_balance_before + 20 * ONE
+        );
+
+        expect_events(vec![Event::Updated {
+            order_id: 0,
+            amount_in: 20 * ONE,
+            amount_out: 80 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_order_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 20 * ONE, 80 * ONE),
+            Error::<Test>::OrderNotFound
+        );
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_called_by_non_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(None);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(BOB), 0, 20 * ONE, 80 * ONE),
+            Error::<Test>::Forbidden
+        );
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_order_is_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(Some(5));
+        System::set_block_number(5);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 20 * ONE, 80 * ONE),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_amount_in_is_too_low() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(None);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 4 * ONE, 100 * ONE),
+            Error::<Test>::OrderAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_amount_out_is_too_low() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(None);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 20 * ONE, 4 * ONE),
+            Error::<Test>::OrderAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn update_order_should_throw_error_when_balance_is_insufficient() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(None);
+
+        // Act & Assert
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 20 * ONE, 100_000 * ONE),
+            BalanceTooLow::<Test>
+        );
+    });
+}
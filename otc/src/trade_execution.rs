@@ -0,0 +1,196 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Balance, Config, Error, OrderId, OrderOf, Pallet};
+use frame_support::ensure;
+use frame_system::ensure_signed;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use sp_runtime::{DispatchError, Permill};
+
+impl<T: Config> TradeExecution<T::RuntimeOrigin, T::AccountId, T::AssetId, Balance> for Pallet<T> {
+    type Error = DispatchError;
+
+    fn calculate_sell(
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::OTC(order_id) => {
+                let order = Self::get_order_for_trade(order_id, asset_in, asset_out).map_err(ExecutorError::Error)?;
+
+                Self::calculate_sell_amount_out(&order, amount_in)
+                    .and_then(Self::amount_out_without_fee)
+                    .map_err(ExecutorError::Error)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn calculate_buy(
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::OTC(order_id) => {
+                let order = Self::get_order_for_trade(order_id, asset_in, asset_out).map_err(ExecutorError::Error)?;
+
+                Self::amount_out_with_fee(amount_out)
+                    .and_then(|amount_out| Self::calculate_buy_amount_in(&order, amount_out))
+                    .map_err(ExecutorError::Error)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn execute_sell(
+        who: T::RuntimeOrigin,
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+        min_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::OTC(order_id) => {
+                Self::sell(who, order_id, asset_in, asset_out, amount_in, min_limit).map_err(ExecutorError::Error)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn execute_buy(
+        who: T::RuntimeOrigin,
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+        max_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::OTC(order_id) => {
+                Self::buy(who, order_id, asset_in, asset_out, amount_out, max_limit).map_err(ExecutorError::Error)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn get_order_for_trade(
+        order_id: OrderId,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+    ) -> Result<OrderOf<T>, DispatchError> {
+        let order = Self::get_priced_order(order_id)?;
+        ensure!(
+            order.asset_in == asset_in && order.asset_out == asset_out,
+            Error::<T>::AssetMismatch
+        );
+        Self::ensure_not_expired(&order)?;
+
+        Ok(order)
+    }
+
+    fn calculate_sell_amount_out(order: &OrderOf<T>, amount_in: Balance) -> Result<Balance, DispatchError> {
+        if amount_in == order.amount_in {
+            return Ok(order.amount_out);
+        }
+
+        ensure!(amount_in < order.amount_in, Error::<T>::OrderAmountExceeded);
+        ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+
+        Self::calculate_partial_fill_amount_out(order, amount_in)
+    }
+
+    fn calculate_buy_amount_in(order: &OrderOf<T>, amount_out: Balance) -> Result<Balance, DispatchError> {
+        if amount_out == order.amount_out {
+            return Ok(order.amount_in);
+        }
+
+        ensure!(amount_out < order.amount_out, Error::<T>::OrderAmountExceeded);
+        ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+
+        Self::calculate_partial_fill_amount_in(order, amount_out)
+    }
+
+    /// Smallest fill of `asset_out` from which the taker receives exactly `amount_out`
+    fn amount_out_with_fee(amount_out: Balance) -> Result<Balance, DispatchError> {
+        if T::FeeOnAmountIn::get() {
+            return Ok(amount_out);
+        }
+
+        let fee_complement =
+            Permill::from_parts(Permill::one().deconstruct().saturating_sub(T::Fee::get().deconstruct()));
+        ensure!(!fee_complement.is_zero(), Error::<T>::MathError);
+
+        // the estimate never exceeds the result and the received amount grows by at most one per unit
+        let mut amount_out_with_fee = fee_complement.saturating_reciprocal_mul_floor(amount_out);
+        while Self::amount_out_without_fee(amount_out_with_fee)? < amount_out {
+            amount_out_with_fee = amount_out_with_fee.checked_add(1).ok_or(Error::<T>::MathError)?;
+        }
+
+        Ok(amount_out_with_fee)
+    }
+
+    fn sell(
+        origin: T::RuntimeOrigin,
+        order_id: OrderId,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+        min_limit: Balance,
+    ) -> Result<(), DispatchError> {
+        let who = ensure_signed(origin)?;
+        let order = Self::get_order_for_trade(order_id, asset_in, asset_out)?;
+
+        let amount_out = Self::calculate_sell_amount_out(&order, amount_in)?;
+        ensure!(
+            Self::amount_out_without_fee(amount_out)? >= min_limit,
+            Error::<T>::TradeLimitReached
+        );
+
+        if amount_in == order.amount_in {
+            Self::do_fill_order(order_id, who)
+        } else {
+            Self::do_partial_fill_order(order_id, who, amount_in, amount_out)
+        }
+    }
+
+    fn buy(
+        origin: T::RuntimeOrigin,
+        order_id: OrderId,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+        max_limit: Balance,
+    ) -> Result<(), DispatchError> {
+        let who = ensure_signed(origin)?;
+        let order = Self::get_order_for_trade(order_id, asset_in, asset_out)?;
+
+        let amount_out = Self::amount_out_with_fee(amount_out)?;
+        let amount_in = Self::calculate_buy_amount_in(&order, amount_out)?;
+        ensure!(amount_in <= max_limit, Error::<T>::TradeLimitReached);
+
+        if amount_out == order.amount_out {
+            Self::do_fill_order(order_id, who)
+        } else {
+            Self::do_partial_fill_order(order_id, who, amount_in, amount_out)
+        }
+    }
+}
@@ -51,6 +51,12 @@ pub trait WeightInfo {
     fn partial_fill_order() -> Weight;
     fn fill_order() -> Weight;
     fn cancel_order() -> Weight;
+    fn cleanup_expired() -> Weight;
+    fn remove_expired_order() -> Weight;
+    fn match_orders() -> Weight;
+    fn update_order() -> Weight;
+    fn fill_orders(n: u32) -> Weight;
+    fn place_pegged_order() -> Weight;
 }
 
 /// Weights for pallet_otc using the hydraDX node and recommended hardware.
@@ -63,20 +69,51 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
             .saturating_add(T::DbWeight::get().writes(4 as u64))
     }
     fn partial_fill_order() -> Weight {
-        Weight::from_ref_time(125_008_000 as u64)
-            .saturating_add(T::DbWeight::get().reads(8 as u64))
-            .saturating_add(T::DbWeight::get().writes(6 as u64))
+        Weight::from_ref_time(139_612_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(9 as u64))
+            .saturating_add(T::DbWeight::get().writes(7 as u64))
     }
     fn fill_order() -> Weight {
-        Weight::from_ref_time(120_345_000 as u64)
-            .saturating_add(T::DbWeight::get().reads(7 as u64))
-            .saturating_add(T::DbWeight::get().writes(6 as u64))
+        Weight::from_ref_time(134_917_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(8 as u64))
+            .saturating_add(T::DbWeight::get().writes(7 as u64))
     }
     fn cancel_order() -> Weight {
         Weight::from_ref_time(55_731_000 as u64)
             .saturating_add(T::DbWeight::get().reads(3 as u64))
             .saturating_add(T::DbWeight::get().writes(3 as u64))
     }
+    fn cleanup_expired() -> Weight {
+        Weight::from_ref_time(83_162_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(5 as u64))
+    }
+    fn remove_expired_order() -> Weight {
+        Weight::from_ref_time(57_904_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(3 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+    fn match_orders() -> Weight {
+        Weight::from_ref_time(141_276_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(11 as u64))
+            .saturating_add(T::DbWeight::get().writes(11 as u64))
+    }
+    fn update_order() -> Weight {
+        Weight::from_ref_time(61_487_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(4 as u64))
+            .saturating_add(T::DbWeight::get().writes(3 as u64))
+    }
+    fn fill_orders(n: u32) -> Weight {
+        Weight::from_ref_time(17_263_000 as u64) // Standard Error: 41_000
+            .saturating_add(Weight::from_ref_time(137_958_000 as u64).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads((9 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes((7 as u64).saturating_mul(n as u64)))
+    }
+    fn place_pegged_order() -> Weight {
+        Weight::from_ref_time(74_386_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -87,18 +124,49 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(4 as u64))
     }
     fn partial_fill_order() -> Weight {
-        Weight::from_ref_time(125_008_000 as u64)
-            .saturating_add(RocksDbWeight::get().reads(8 as u64))
-            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+        Weight::from_ref_time(139_612_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(9 as u64))
+            .saturating_add(RocksDbWeight::get().writes(7 as u64))
     }
     fn fill_order() -> Weight {
-        Weight::from_ref_time(120_345_000 as u64)
-            .saturating_add(RocksDbWeight::get().reads(7 as u64))
-            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+        Weight::from_ref_time(134_917_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(8 as u64))
+            .saturating_add(RocksDbWeight::get().writes(7 as u64))
     }
     fn cancel_order() -> Weight {
         Weight::from_ref_time(55_731_000 as u64)
             .saturating_add(RocksDbWeight::get().reads(3 as u64))
             .saturating_add(RocksDbWeight::get().writes(3 as u64))
     }
+    fn cleanup_expired() -> Weight {
+        Weight::from_ref_time(83_162_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(5 as u64))
+    }
+    fn remove_expired_order() -> Weight {
+        Weight::from_ref_time(57_904_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(3 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+    fn match_orders() -> Weight {
+        Weight::from_ref_time(141_276_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(11 as u64))
+            .saturating_add(RocksDbWeight::get().writes(11 as u64))
+    }
+    fn update_order() -> Weight {
+        Weight::from_ref_time(61_487_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(4 as u64))
+            .saturating_add(RocksDbWeight::get().writes(3 as u64))
+    }
+    fn fill_orders(n: u32) -> Weight {
+        Weight::from_ref_time(17_263_000 as u64) // Standard Error: 41_000
+            .saturating_add(Weight::from_ref_time(137_958_000 as u64).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads((9 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes((7 as u64).saturating_mul(n as u64)))
+    }
+    fn place_pegged_order() -> Weight {
+        Weight::from_ref_time(74_386_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
 }
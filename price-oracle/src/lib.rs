@@ -21,6 +21,7 @@ use frame_support::ensure;
 use frame_support::pallet_prelude::{DispatchError, Weight};
 use frame_support::sp_runtime::traits::{CheckedDiv, Zero};
 use frame_support::sp_runtime::{DispatchResult, FixedPointNumber};
+use frame_support::traits::Get;
 use hydradx_traits::{OnCreatePoolHandler, OnTradeHandler, Source};
 use sp_std::convert::TryInto;
 use sp_std::marker::PhantomData;
@@ -315,6 +316,13 @@ impl<T: Config> OnCreatePoolHandler<AssetId> for PriceOracleHandler<T> {
         Pallet::<T>::on_create_pool(asset_a, asset_b)?;
         Ok(())
     }
+
+    // `PriceDataTen` is read and `NewAssets` is updated, the new asset is added to the buckets in `on_finalize`.
+    fn on_create_pool_weight() -> Weight {
+        T::DbWeight::get()
+            .reads_writes(2, 1)
+            .saturating_add(T::WeightInfo::on_finalize_one_token() - T::WeightInfo::on_finalize_no_entry())
+    }
 }
 
 impl<T: Config> OnTradeHandler<AssetId, Balance> for PriceOracleHandler<T> {
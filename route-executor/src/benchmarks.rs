@@ -0,0 +1,94 @@
+// This file is part of pallet-route-executor.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "runtime-benchmarks")]
+
+// The weights of `sell` and `buy` depend on the AMMs plugged into the router and are benchmarked in the runtime.
+// `arbitrage` is benchmarked against the pools created by `T::BenchmarkHelper`.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+const ASSET: u32 = 1;
+
+fn referral_code<T: Config>() -> ReferralCode<T::MaxReferralCodeLength> {
+    // worst case: the longest code
+    vec![b'a'; T::MaxReferralCodeLength::get() as usize].try_into().unwrap()
+}
+
+benchmarks! {
+    where_clause { where
+        T::AssetId: From<u32>,
+    }
+
+    register_referral_code {
+        let owner: T::AccountId = account("owner", 1, 1);
+        let code = referral_code::<T>();
+    }: _(RawOrigin::Signed(owner.clone()), code.clone())
+    verify {
+        assert_eq!(ReferralCodes::<T>::get(&code), Some(owner));
+    }
+
+    link_referral_code {
+        let owner: T::AccountId = account("owner", 1, 1);
+        let who: T::AccountId = account("who", 2, 2);
+        let code = referral_code::<T>();
+        assert_ok!(Pallet::<T>::register_referral_code(RawOrigin::Signed(owner.clone()).into(), code.clone()));
+    }: _(RawOrigin::Signed(who.clone()), code)
+    verify {
+        assert_eq!(Referrers::<T>::get(&who), Some(owner));
+    }
+
+    router_fee {
+        let owner: T::AccountId = account("owner", 1, 1);
+        let who: T::AccountId = account("who", 2, 2);
+        let fee: T::Balance = 1_000_000_000u32.into();
+
+        // worst case: the trader is linked to a referrer, so the fee is split into two transfers
+        let code = referral_code::<T>();
+        assert_ok!(Pallet::<T>::register_referral_code(RawOrigin::Signed(owner.clone()).into(), code.clone()));
+        assert_ok!(Pallet::<T>::link_referral_code(RawOrigin::Signed(who.clone()).into(), code));
+        assert_ok!(T::MultiCurrency::deposit(ASSET.into(), &who, fee));
+    }: {
+        Pallet::<T>::transfer_router_fee(&who, ASSET.into(), fee)?;
+    }
+    verify {
+        assert_eq!(T::MultiCurrency::free_balance(ASSET.into(), &who), T::Balance::zero());
+    }
+
+    arbitrage {
+        let n in 1 .. T::MaxNumberOfTrades::get() as u32;
+
+        // the router fee is charged separately by `router_fee`
+        let (who, asset, amount_in, route) = T::BenchmarkHelper::create_arbitrage(n)?;
+        let balance_before = T::Currency::reducible_balance(asset, &who, false);
+    }: _(RawOrigin::Signed(who.clone()), asset, amount_in, T::Balance::zero(), route)
+    verify {
+        assert!(T::Currency::reducible_balance(asset, &who, false) > balance_before);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pallet;
+    use crate::tests::mock::*;
+    use frame_benchmarking::impl_benchmark_test_suite;
+
+    impl_benchmark_test_suite!(Pallet, super::ExtBuilder::default().build(), super::Test);
+}
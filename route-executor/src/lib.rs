@@ -26,13 +26,18 @@ use frame_system::ensure_signed;
 use hydradx_traits::router::TradeExecution;
 use hydradx_traits::router::{ExecutorError, PoolType};
 use orml_traits::arithmetic::{CheckedAdd, CheckedSub};
+use orml_traits::MultiCurrency;
 use scale_info::TypeInfo;
-use sp_runtime::DispatchError;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Zero};
+use sp_runtime::{BoundedVec, DispatchError, Permill};
 use sp_std::vec::Vec;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarks;
+
 pub mod weights;
 
 use weights::WeightInfo;
@@ -48,6 +53,17 @@ pub struct Trade<AssetId> {
     pub asset_out: AssetId,
 }
 
+///Referral code which links an account to its referrer
+pub type ReferralCode<MaxLength> = BoundedVec<u8, MaxLength>;
+
+/// Creates the pools the benchmarked arbitrage is executed against.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, AssetId, Balance> {
+    /// Creates and funds the pools of a profitable cyclic route of `n` trades and funds the trader.
+    /// Returns the trader, the asset the route starts and ends with, the amount to sell and the route.
+    fn create_arbitrage(n: u32) -> Result<(AccountId, AssetId, Balance, Vec<Trade<AssetId>>), DispatchError>;
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -73,7 +89,8 @@ pub mod pallet {
             + MaybeSerializeDeserialize
             + Default
             + CheckedSub
-            + CheckedAdd;
+            + CheckedAdd
+            + AtLeast32BitUnsigned;
 
         /// Max limit for the number of trades within a route
         #[pallet::constant]
@@ -82,6 +99,25 @@ pub mod pallet {
         /// Currency for checking balances
         type Currency: Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = Self::Balance>;
 
+        /// Multi currency mechanism for transferring the router fee
+        type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Self::Balance>;
+
+        /// Router fee, taken in `asset_out` for sells and in `asset_in` for buys
+        #[pallet::constant]
+        type RouterFee: Get<Permill>;
+
+        /// Share of the router fee which is paid to the referrer of the trader
+        #[pallet::constant]
+        type ReferralShare: Get<Permill>;
+
+        /// Account which receives the router fee
+        #[pallet::constant]
+        type FeeReceiver: Get<Self::AccountId>;
+
+        /// Max length of a referral code
+        #[pallet::constant]
+        type MaxReferralCodeLength: Get<u32>;
+
         /// Handlers for AMM pools to calculate and execute trades
         type AMM: TradeExecution<
             <Self as frame_system::Config>::RuntimeOrigin,
@@ -93,18 +129,41 @@ pub mod pallet {
 
         /// Weight information for the extrinsics.
         type WeightInfo: WeightInfo;
+
+        /// Helper creating the pools of the arbitrage executed in benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::AssetId, Self::Balance>;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(crate) fn deposit_event)]
     pub enum Event<T: Config> {
         ///The route with trades has been successfully executed
+        ///`fee` is the router fee charged on top of the route amounts, `referral_fee` is the part of it paid to the referrer
         RouteExecuted {
             asset_in: T::AssetId,
             asset_out: T::AssetId,
             amount_in: T::Balance,
             amount_out: T::Balance,
+            fee: T::Balance,
+            referral_fee: T::Balance,
         },
+        ///The cyclic route has been successfully executed with a profit
+        ArbitrageExecuted {
+            asset: T::AssetId,
+            amount_in: T::Balance,
+            amount_out: T::Balance,
+            profit: T::Balance,
+            fee: T::Balance,
+            referral_fee: T::Balance,
+        },
+        ///A referral code has been registered
+        ReferralCodeRegistered {
+            code: ReferralCode<T::MaxReferralCodeLength>,
+            who: T::AccountId,
+        },
+        ///An account has been linked to a referrer
+        ReferralLinked { who: T::AccountId, referrer: T::AccountId },
     }
 
     #[pallet::error]
@@ -119,10 +178,33 @@ pub mod pallet {
         RouteHasNoTrades,
         ///The user has not enough balance to execute the trade
         InsufficientBalance,
+        ///The route does not start and end with the arbitraged asset
+        RouteIsNotCyclic,
+        ///The profit of the arbitrage is lower than the required minimum
+        InsufficientProfit,
+        ///The referral code is already registered
+        ReferralCodeAlreadyExists,
+        ///The referral code is not registered
+        ReferralCodeNotFound,
+        ///The account is already linked to a referrer
+        AlreadyLinked,
+        ///An account cannot be linked to its own referral code
+        LinkToSelfNotAllowed,
         ///Unexpected error which should never really happen, but the error case must be handled to prevent panics.
         UnexpectedError,
     }
 
+    /// Owners of registered referral codes
+    #[pallet::storage]
+    #[pallet::getter(fn referral_codes)]
+    pub type ReferralCodes<T: Config> =
+        StorageMap<_, Blake2_128Concat, ReferralCode<T::MaxReferralCodeLength>, T::AccountId, OptionQuery>;
+
+    /// Referrers of the linked accounts
+    #[pallet::storage]
+    #[pallet::getter(fn referrer_of)]
+    pub type Referrers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Executes a sell with a series of trades specified in the route.
@@ -132,12 +214,13 @@ pub mod pallet {
         /// - `asset_in`: The identifier of the asset to sell
         /// - `asset_out`: The identifier of the asset to receive
         /// - `amount_in`: The amount of `asset_in` to sell
-        /// - `min_amount_out`: The minimum amount of `asset_out` to receive.
+        /// - `min_amount_out`: The minimum amount of `asset_out` to receive, after the router fee is deducted.
         /// - `route`: Series of [`Trade<AssetId>`] to be executed. A [`Trade<AssetId>`] specifies the asset pair (`asset_in`, `asset_out`) and the AMM (`pool`) in which the trade is executed.
         ///
         /// Emits `RouteExecuted` when successful.
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::sell(route.len() as u32))]
+        #[pallet::weight(<T as Config>::WeightInfo::sell(route.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::router_fee()))]
         #[transactional]
         pub fn sell(
             origin: OriginFor<T>,
@@ -160,8 +243,13 @@ pub mod pallet {
             let trade_amounts = Self::calculate_sell_trade_amounts(&route, amount_in)?;
 
             let last_trade_amount = trade_amounts.last().ok_or(Error::<T>::UnexpectedError)?;
+            let fee = Self::calculate_router_fee(last_trade_amount.amount_out);
+            let amount_out_without_fee = last_trade_amount
+                .amount_out
+                .checked_sub(&fee)
+                .ok_or(Error::<T>::UnexpectedError)?;
             ensure!(
-                last_trade_amount.amount_out >= min_amount_out,
+                amount_out_without_fee >= min_amount_out,
                 Error::<T>::TradingLimitReached
             );
 
@@ -188,17 +276,21 @@ pub mod pallet {
             }
 
             Self::ensure_that_user_received_asset_out(
-                who,
+                who.clone(),
                 asset_out,
                 user_balance_of_asset_out_before_trade,
                 last_trade_amount.amount_out,
             )?;
 
+            let referral_fee = Self::transfer_router_fee(&who, asset_out, fee)?;
+
             Self::deposit_event(Event::RouteExecuted {
                 asset_in,
                 asset_out,
                 amount_in,
                 amount_out: last_trade_amount.amount_out,
+                fee,
+                referral_fee,
             });
 
             Ok(())
@@ -211,12 +303,13 @@ pub mod pallet {
         /// - `asset_in`: The identifier of the asset to be swapped to buy `asset_out`
         /// - `asset_out`: The identifier of the asset to buy
         /// - `amount_out`: The amount of `asset_out` to buy
-        /// - `max_amount_in`: The max amount of `asset_in` to spend on the buy.
+        /// - `max_amount_in`: The max amount of `asset_in` to spend on the buy, including the router fee.
         /// - `route`: Series of [`Trade<AssetId>`] to be executed. A [`Trade<AssetId>`] specifies the asset pair (`asset_in`, `asset_out`) and the AMM (`pool`) in which the trade is executed.
         ///
         /// Emits `RouteExecuted` when successful.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::buy(route.len() as u32))]
+        #[pallet::weight(<T as Config>::WeightInfo::buy(route.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::router_fee()))]
         #[transactional]
         pub fn buy(
             origin: OriginFor<T>,
@@ -234,10 +327,12 @@ pub mod pallet {
             let trade_amounts = Self::calculate_buy_trade_amounts(&route, amount_out)?;
 
             let last_trade_amount = trade_amounts.last().ok_or(Error::<T>::UnexpectedError)?;
-            ensure!(
-                last_trade_amount.amount_in <= max_amount_in,
-                Error::<T>::TradingLimitReached
-            );
+            let fee = Self::calculate_router_fee(last_trade_amount.amount_in);
+            let amount_in_with_fee = last_trade_amount
+                .amount_in
+                .checked_add(&fee)
+                .ok_or(Error::<T>::UnexpectedError)?;
+            ensure!(amount_in_with_fee <= max_amount_in, Error::<T>::TradingLimitReached);
 
             for (trade_amount, trade) in trade_amounts.iter().rev().zip(route) {
                 let user_balance_of_asset_out_before_trade =
@@ -263,25 +358,167 @@ pub mod pallet {
             }
 
             Self::ensure_that_user_spent_asset_in(
-                who,
+                who.clone(),
                 asset_in,
                 user_balance_of_asset_in_before_trade,
                 last_trade_amount.amount_in,
             )?;
 
+            let referral_fee = Self::transfer_router_fee(&who, asset_in, fee)?;
+
             Self::deposit_event(Event::RouteExecuted {
                 asset_in,
                 asset_out,
                 amount_in: last_trade_amount.amount_in,
                 amount_out,
+                fee,
+                referral_fee,
             });
 
             Ok(())
         }
+
+        /// Executes a cyclic sell route which starts and ends with the same asset.
+        /// The whole route is reverted unless the balance of the asset increases by at least `min_profit`,
+        /// after the router fee is deducted.
+        ///
+        /// - `origin`: The executor of the trade
+        /// - `asset`: The identifier of the asset to sell at the start and receive at the end of the route
+        /// - `amount_in`: The amount of `asset` to sell in the first trade
+        /// - `min_profit`: The minimum increase of the balance of `asset` after all trades are executed.
+        /// - `route`: Series of [`Trade<AssetId>`] to be executed. The first trade has to sell `asset` and the last trade has to buy `asset`.
+        ///
+        /// Emits `ArbitrageExecuted` when successful.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::arbitrage(route.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::router_fee()))]
+        #[transactional]
+        pub fn arbitrage(
+            origin: OriginFor<T>,
+            asset: T::AssetId,
+            amount_in: T::Balance,
+            min_profit: T::Balance,
+            route: Vec<Trade<T::AssetId>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin.clone())?;
+            Self::ensure_route_size(route.len())?;
+            Self::ensure_route_is_cyclic(asset, &route)?;
+
+            let user_balance_of_asset_before_trade = T::Currency::reducible_balance(asset, &who, false);
+            ensure!(
+                user_balance_of_asset_before_trade >= amount_in,
+                Error::<T>::InsufficientBalance
+            );
+
+            let trade_amounts = Self::calculate_sell_trade_amounts(&route, amount_in)?;
+
+            let last_trade_amount = trade_amounts.last().ok_or(Error::<T>::UnexpectedError)?;
+            let fee = Self::calculate_router_fee(last_trade_amount.amount_out);
+            let expected_profit = last_trade_amount
+                .amount_out
+                .checked_sub(&fee)
+                .and_then(|amount_out| amount_out.checked_sub(&amount_in))
+                .ok_or(Error::<T>::InsufficientProfit)?;
+            ensure!(expected_profit >= min_profit, Error::<T>::InsufficientProfit);
+
+            for (trade_amount, trade) in trade_amounts.iter().zip(route) {
+                let user_balance_of_asset_in_before_trade = T::Currency::reducible_balance(trade.asset_in, &who, false);
+
+                let execution_result = T::AMM::execute_sell(
+                    origin.clone(),
+                    trade.pool,
+                    trade.asset_in,
+                    trade.asset_out,
+                    trade_amount.amount_in,
+                    trade_amount.amount_out,
+                );
+
+                handle_execution_error!(execution_result);
+
+                Self::ensure_that_user_spent_asset_in(
+                    who.clone(),
+                    trade.asset_in,
+                    user_balance_of_asset_in_before_trade,
+                    trade_amount.amount_in,
+                )?;
+            }
+
+            let referral_fee = Self::transfer_router_fee(&who, asset, fee)?;
+
+            // The asset is both spent and received within the route, so only the net change can be verified.
+            Self::ensure_that_user_received_asset_out(who, asset, user_balance_of_asset_before_trade, expected_profit)?;
+
+            Self::deposit_event(Event::ArbitrageExecuted {
+                asset,
+                amount_in,
+                amount_out: last_trade_amount.amount_out,
+                profit: expected_profit,
+                fee,
+                referral_fee,
+            });
+
+            Ok(())
+        }
+
+        /// Registers a referral code owned by the caller.
+        /// Accounts linked to the code pay a share of their router fees to the owner of the code.
+        ///
+        /// - `origin`: The owner of the referral code
+        /// - `code`: The referral code to register
+        ///
+        /// Emits `ReferralCodeRegistered` when successful.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_referral_code())]
+        pub fn register_referral_code(
+            origin: OriginFor<T>,
+            code: ReferralCode<T::MaxReferralCodeLength>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <ReferralCodes<T>>::try_mutate(&code, |maybe_owner| -> DispatchResult {
+                ensure!(maybe_owner.is_none(), Error::<T>::ReferralCodeAlreadyExists);
+                *maybe_owner = Some(who.clone());
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ReferralCodeRegistered { code, who });
+
+            Ok(())
+        }
+
+        /// Links the caller to the owner of a referral code.
+        /// An account can be linked to a referrer only once.
+        ///
+        /// - `origin`: The account to link
+        /// - `code`: The referral code of the referrer
+        ///
+        /// Emits `ReferralLinked` when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::link_referral_code())]
+        pub fn link_referral_code(
+            origin: OriginFor<T>,
+            code: ReferralCode<T::MaxReferralCodeLength>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let referrer = <ReferralCodes<T>>::get(&code).ok_or(Error::<T>::ReferralCodeNotFound)?;
+            ensure!(referrer != who, Error::<T>::LinkToSelfNotAllowed);
+
+            <Referrers<T>>::try_mutate(&who, |maybe_referrer| -> DispatchResult {
+                ensure!(maybe_referrer.is_none(), Error::<T>::AlreadyLinked);
+                *maybe_referrer = Some(referrer.clone());
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ReferralLinked { who, referrer });
+
+            Ok(())
+        }
     }
 }
 
-struct AmountInAndOut<T: Config> {
+/// Amounts of a single trade of a route
+pub struct AmountInAndOut<T: Config> {
     pub amount_in: T::Balance,
     pub amount_out: T::Balance,
 }
@@ -297,6 +534,18 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    fn ensure_route_is_cyclic(asset: T::AssetId, route: &[Trade<T::AssetId>]) -> Result<(), DispatchError> {
+        let first_trade = route.first().ok_or(Error::<T>::RouteHasNoTrades)?;
+        let last_trade = route.last().ok_or(Error::<T>::RouteHasNoTrades)?;
+        ensure!(
+            first_trade.asset_in == asset && last_trade.asset_out == asset,
+            Error::<T>::RouteIsNotCyclic
+        );
+
+        Ok(())
+    }
+
+    /// Calculates the amounts of all trades of a sell route, starting with `amount_in` of the first trade.
     fn calculate_sell_trade_amounts(
         route: &Vec<Trade<T::AssetId>>,
         amount_in: T::Balance,
@@ -342,6 +591,40 @@ impl<T: Config> Pallet<T> {
         Ok(amount_in_and_outs)
     }
 
+    fn calculate_router_fee(amount: T::Balance) -> T::Balance {
+        T::RouterFee::get().mul_floor(amount)
+    }
+
+    /// Transfers the router fee from `who` to the fee receiver and to the referrer of `who`, if there is any.
+    /// Returns the part of the fee paid to the referrer.
+    fn transfer_router_fee(
+        who: &T::AccountId,
+        asset: T::AssetId,
+        fee: T::Balance,
+    ) -> Result<T::Balance, DispatchError> {
+        if fee.is_zero() {
+            return Ok(T::Balance::zero());
+        }
+
+        let referral_fee = match <Referrers<T>>::get(who) {
+            Some(referrer) => {
+                let referral_fee = T::ReferralShare::get().mul_floor(fee);
+                if !referral_fee.is_zero() {
+                    T::MultiCurrency::transfer(asset, who, &referrer, referral_fee)?;
+                }
+                referral_fee
+            }
+            None => T::Balance::zero(),
+        };
+
+        let protocol_fee = fee.checked_sub(&referral_fee).ok_or(Error::<T>::UnexpectedError)?;
+        if !protocol_fee.is_zero() {
+            T::MultiCurrency::transfer(asset, who, &T::FeeReceiver::get(), protocol_fee)?;
+        }
+
+        Ok(referral_fee)
+    }
+
     fn ensure_that_user_received_asset_out(
         who: T::AccountId,
         asset_out: T::AssetId,
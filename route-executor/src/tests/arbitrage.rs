@@ -0,0 +1,237 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::{Error, Event, Trade};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::PoolType;
+use pretty_assertions::assert_eq;
+use sp_runtime::DispatchError::BadOrigin;
+
+const BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK: [Trade<AssetId>; 2] = [
+    Trade {
+        pool: PoolType::XYK,
+        asset_in: BSX,
+        asset_out: AUSD,
+    },
+    Trade {
+        pool: PoolType::XYK,
+        asset_in: AUSD,
+        asset_out: BSX,
+    },
+];
+
+#[test]
+fn arbitrage_should_work_when_route_is_profitable() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        let amount_to_sell = XYK_SELL_CALCULATION_RESULT - 1;
+        let min_profit = 1;
+
+        //Act
+        assert_ok!(Router::arbitrage(
+            RuntimeOrigin::signed(ALICE),
+            BSX,
+            amount_to_sell,
+            min_profit,
+            BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK.to_vec()
+        ));
+
+        //Assert
+        assert_executed_sell_trades(vec![
+            (PoolType::XYK, amount_to_sell, BSX, AUSD),
+            (PoolType::XYK, XYK_SELL_CALCULATION_RESULT, AUSD, BSX),
+        ]);
+        assert_eq!(Balances::free_balance(ALICE), ALICE_INITIAL_NATIVE_BALANCE + 1);
+        expect_events(vec![Event::ArbitrageExecuted {
+            asset: BSX,
+            amount_in: amount_to_sell,
+            amount_out: XYK_SELL_CALCULATION_RESULT,
+            profit: 1,
+            fee: 0,
+            referral_fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn arbitrage_should_work_when_route_has_multiple_trades_with_different_pool_type() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        let amount_to_sell = 1;
+        let min_profit = 1;
+        let trade1 = Trade {
+            pool: PoolType::XYK,
+            asset_in: BSX,
+            asset_out: AUSD,
+        };
+        let trade2 = Trade {
+            pool: PoolType::Stableswap(AUSD),
+            asset_in: AUSD,
+            asset_out: MOVR,
+        };
+        let trade3 = Trade {
+            pool: PoolType::Omnipool,
+            asset_in: MOVR,
+            asset_out: BSX,
+        };
+        let trades = vec![trade1, trade2, trade3];
+
+        //Act
+        assert_ok!(Router::arbitrage(
+            RuntimeOrigin::signed(ALICE),
+            BSX,
+            amount_to_sell,
+            min_profit,
+            trades
+        ));
+
+        //Assert
+        assert_executed_sell_trades(vec![
+            (PoolType::XYK, amount_to_sell, BSX, AUSD),
+            (PoolType::Stableswap(AUSD), XYK_SELL_CALCULATION_RESULT, AUSD, MOVR),
+            (PoolType::Omnipool, STABLESWAP_SELL_CALCULATION_RESULT, MOVR, BSX),
+        ]);
+        expect_events(vec![Event::ArbitrageExecuted {
+            asset: BSX,
+            amount_in: amount_to_sell,
+            amount_out: OMNIPOOL_SELL_CALCULATION_RESULT,
+            profit: OMNIPOOL_SELL_CALCULATION_RESULT - amount_to_sell,
+            fee: 0,
+            referral_fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_route_makes_a_loss() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        let amount_to_sell = XYK_SELL_CALCULATION_RESULT + 1;
+
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(
+                RuntimeOrigin::signed(ALICE),
+                BSX,
+                amount_to_sell,
+                0,
+                BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK.to_vec()
+            ),
+            Error::<Test>::InsufficientProfit
+        );
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_min_profit_is_not_reached() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        let amount_to_sell = XYK_SELL_CALCULATION_RESULT - 1;
+        let min_profit = 2;
+
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(
+                RuntimeOrigin::signed(ALICE),
+                BSX,
+                amount_to_sell,
+                min_profit,
+                BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK.to_vec()
+            ),
+            Error::<Test>::InsufficientProfit
+        );
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_route_does_not_end_with_the_sold_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        let trades = vec![BSX_AUSD_TRADE_IN_XYK];
+
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(RuntimeOrigin::signed(ALICE), BSX, 5, 1, trades),
+            Error::<Test>::RouteIsNotCyclic
+        );
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_route_does_not_start_with_the_sold_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(
+                RuntimeOrigin::signed(ALICE),
+                AUSD,
+                5,
+                1,
+                BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK.to_vec()
+            ),
+            Error::<Test>::RouteIsNotCyclic
+        );
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_route_has_no_trades() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(RuntimeOrigin::signed(ALICE), BSX, 5, 1, vec![]),
+            Error::<Test>::RouteHasNoTrades
+        );
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_caller_has_not_enough_balance() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(
+                RuntimeOrigin::signed(ALICE),
+                BSX,
+                ALICE_INITIAL_NATIVE_BALANCE + 1,
+                1,
+                BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK.to_vec()
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn arbitrage_should_fail_when_called_with_non_signed_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Act and Assert
+        assert_noop!(
+            Router::arbitrage(
+                RuntimeOrigin::none(),
+                BSX,
+                5,
+                1,
+                BSX_AUSD_AUSD_BSX_CYCLE_IN_XYK.to_vec()
+            ),
+            BadOrigin
+        );
+    });
+}
@@ -49,6 +49,8 @@ fn buy_should_work_when_route_has_single_trade() {
             asset_out: AUSD,
             amount_in: XYK_BUY_CALCULATION_RESULT,
             amount_out: amount_to_buy,
+            fee: 0,
+            referral_fee: 0,
         }
         .into()]);
     });
@@ -196,6 +198,8 @@ fn buy_should_when_route_has_multiple_trades_with_same_pool_type() {
                 asset_out: KSM,
                 amount_in: XYK_BUY_CALCULATION_RESULT,
                 amount_out: amount_to_buy,
+                fee: 0,
+                referral_fee: 0,
             }
             .into()]);
         });
@@ -249,6 +253,8 @@ fn buy_should_work_when_route_has_multiple_trades_with_different_pool_type() {
                 asset_out: KSM,
                 amount_in: XYK_BUY_CALCULATION_RESULT,
                 amount_out: amount_to_buy,
+                fee: 0,
+                referral_fee: 0,
             }
             .into()]);
         });
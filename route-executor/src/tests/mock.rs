@@ -18,7 +18,7 @@
 use crate as router;
 use crate::{Config, Trade};
 use frame_support::parameter_types;
-use frame_support::traits::{Everything, GenesisBuild, Nothing};
+use frame_support::traits::{ConstU32, Everything, GenesisBuild, Get, Nothing};
 use frame_system as system;
 use frame_system::pallet_prelude::OriginFor;
 use hydradx_adapters::inspect::MultiInspectAdapter;
@@ -30,7 +30,7 @@ use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup, One},
-    DispatchError,
+    DispatchError, Permill,
 };
 use std::borrow::Borrow;
 use std::cell::RefCell;
@@ -140,6 +140,26 @@ type Pools = (XYK, StableSwap, OmniPool);
 parameter_types! {
     pub NativeCurrencyId: AssetId = 1000;
     pub MaxNumberOfTrades: u8 = MAX_LIMIT_FOR_TRADES;
+    pub const FeeReceiver: AccountId = FEE_RECEIVER;
+}
+
+thread_local! {
+    static ROUTER_FEE: RefCell<Permill> = RefCell::new(Permill::zero());
+    static REFERRAL_SHARE: RefCell<Permill> = RefCell::new(Permill::zero());
+}
+
+pub struct RouterFee;
+impl Get<Permill> for RouterFee {
+    fn get() -> Permill {
+        ROUTER_FEE.with(|v| *v.borrow())
+    }
+}
+
+pub struct ReferralShare;
+impl Get<Permill> for ReferralShare {
+    fn get() -> Permill {
+        REFERRAL_SHARE.with(|v| *v.borrow())
+    }
 }
 
 impl Config for Test {
@@ -148,14 +168,47 @@ impl Config for Test {
     type Balance = Balance;
     type MaxNumberOfTrades = MaxNumberOfTrades;
     type Currency = MultiInspectAdapter<AccountId, AssetId, Balance, Balances, Tokens, NativeCurrencyId>;
+    type MultiCurrency = Currencies;
+    type RouterFee = RouterFee;
+    type ReferralShare = ReferralShare;
+    type FeeReceiver = FeeReceiver;
+    type MaxReferralCodeLength = ConstU32<8>;
     type AMM = Pools;
     type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockedAmmBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockedAmmBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<AccountId, AssetId, Balance> for MockedAmmBenchmarkHelper {
+    fn create_arbitrage(n: u32) -> Result<(AccountId, AssetId, Balance, Vec<Trade<AssetId>>), DispatchError> {
+        // every XYK trade of the mock sends XYK_SELL_CALCULATION_RESULT to ALICE, which is funded by the pair account
+        let assets = [AUSD, MOVR, KSM, RMRK, SDN];
+        let mut trades = Vec::new();
+        let mut asset_in = BSX;
+        for i in 0..n as usize {
+            let asset_out = if i + 1 == n as usize { BSX } else { assets[i] };
+            trades.push(Trade {
+                pool: PoolType::XYK,
+                asset_in,
+                asset_out,
+            });
+            asset_in = asset_out;
+        }
+
+        Ok((ALICE, BSX, XYK_SELL_CALCULATION_RESULT - 1, trades))
+    }
 }
 
 pub type AccountId = u64;
 
 pub const ALICE: AccountId = 1;
 pub const ASSET_PAIR_ACCOUNT: AccountId = 2;
+pub const FEE_RECEIVER: AccountId = 3;
+pub const BOB: AccountId = 4;
 
 pub const BSX: AssetId = 1000;
 pub const AUSD: AssetId = 1001;
@@ -185,6 +238,8 @@ pub const MAX_LIMIT_FOR_TRADES: u8 = 3;
 
 pub struct ExtBuilder {
     endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+    router_fee: Permill,
+    referral_share: Permill,
 }
 
 // Returns default values for genesis config
@@ -192,6 +247,8 @@ impl Default for ExtBuilder {
     fn default() -> Self {
         Self {
             endowed_accounts: vec![(ALICE, BSX, 1000u128)],
+            router_fee: Permill::zero(),
+            referral_share: Permill::zero(),
         }
     }
 }
@@ -202,7 +259,16 @@ impl ExtBuilder {
         self
     }
 
+    pub fn with_router_fee(mut self, fee: Permill, referral_share: Permill) -> Self {
+        self.router_fee = fee;
+        self.referral_share = referral_share;
+        self
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
+        ROUTER_FEE.with(|v| *v.borrow_mut() = self.router_fee);
+        REFERRAL_SHARE.with(|v| *v.borrow_mut() = self.referral_share);
+
         let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
         pallet_balances::GenesisConfig::<Test> {
@@ -1,3 +1,6 @@
+mod arbitrage;
 mod buy;
 pub mod mock;
+mod referral;
+mod router_fee;
 pub mod sell;
@@ -0,0 +1,139 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::{Error, Event, ReferralCode};
+use frame_support::{assert_noop, assert_ok};
+use pretty_assertions::assert_eq;
+
+fn referral_code(code: &[u8]) -> ReferralCode<<Test as crate::Config>::MaxReferralCodeLength> {
+    code.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn register_referral_code_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Act
+        assert_ok!(Router::register_referral_code(
+            RuntimeOrigin::signed(BOB),
+            referral_code(b"BOB")
+        ));
+
+        //Assert
+        assert_eq!(Router::referral_codes(referral_code(b"BOB")), Some(BOB));
+        expect_events(vec![Event::ReferralCodeRegistered {
+            code: referral_code(b"BOB"),
+            who: BOB,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn register_referral_code_should_fail_when_code_already_exists() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        assert_ok!(Router::register_referral_code(
+            RuntimeOrigin::signed(BOB),
+            referral_code(b"BOB")
+        ));
+
+        //Act and Assert
+        assert_noop!(
+            Router::register_referral_code(RuntimeOrigin::signed(ALICE), referral_code(b"BOB")),
+            Error::<Test>::ReferralCodeAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn link_referral_code_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        assert_ok!(Router::register_referral_code(
+            RuntimeOrigin::signed(BOB),
+            referral_code(b"BOB")
+        ));
+
+        //Act
+        assert_ok!(Router::link_referral_code(
+            RuntimeOrigin::signed(ALICE),
+            referral_code(b"BOB")
+        ));
+
+        //Assert
+        assert_eq!(Router::referrer_of(ALICE), Some(BOB));
+        expect_events(vec![Event::ReferralLinked {
+            who: ALICE,
+            referrer: BOB,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn link_referral_code_should_fail_when_code_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Act and Assert
+        assert_noop!(
+            Router::link_referral_code(RuntimeOrigin::signed(ALICE), referral_code(b"BOB")),
+            Error::<Test>::ReferralCodeNotFound
+        );
+    });
+}
+
+#[test]
+fn link_referral_code_should_fail_when_linking_to_own_code() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        assert_ok!(Router::register_referral_code(
+            RuntimeOrigin::signed(BOB),
+            referral_code(b"BOB")
+        ));
+
+        //Act and Assert
+        assert_noop!(
+            Router::link_referral_code(RuntimeOrigin::signed(BOB), referral_code(b"BOB")),
+            Error::<Test>::LinkToSelfNotAllowed
+        );
+    });
+}
+
+#[test]
+fn link_referral_code_should_fail_when_account_is_already_linked() {
+    ExtBuilder::default().build().execute_with(|| {
+        //Arrange
+        assert_ok!(Router::register_referral_code(
+            RuntimeOrigin::signed(BOB),
+            referral_code(b"BOB")
+        ));
+        assert_ok!(Router::register_referral_code(
+            RuntimeOrigin::signed(FEE_RECEIVER),
+            referral_code(b"FEE")
+        ));
+        assert_ok!(Router::link_referral_code(
+            RuntimeOrigin::signed(ALICE),
+            referral_code(b"BOB")
+        ));
+
+        //Act and Assert
+        assert_noop!(
+            Router::link_referral_code(RuntimeOrigin::signed(ALICE), referral_code(b"FEE")),
+            Error::<Test>::AlreadyLinked
+        );
+    });
+}
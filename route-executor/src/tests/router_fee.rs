@@ -0,0 +1,209 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::{Error, Event, ReferralCode, Trade};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::PoolType;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+const KSM_AUSD_TRADE_IN_XYK: Trade<AssetId> = Trade {
+    pool: PoolType::XYK,
+    asset_in: KSM,
+    asset_out: AUSD,
+};
+
+const AUSD_KSM_TRADE_IN_XYK: Trade<AssetId> = Trade {
+    pool: PoolType::XYK,
+    asset_in: AUSD,
+    asset_out: KSM,
+};
+
+fn link_alice_to_bob() {
+    let code: ReferralCode<_> = b"BOB".to_vec().try_into().unwrap();
+    assert_ok!(Router::register_referral_code(RuntimeOrigin::signed(BOB), code.clone()));
+    assert_ok!(Router::link_referral_code(RuntimeOrigin::signed(ALICE), code));
+}
+
+#[test]
+fn sell_should_transfer_router_fee_in_asset_out_to_fee_receiver() {
+    ExtBuilder::default()
+        .with_endowed_accounts(vec![(ALICE, KSM, 1000)])
+        .with_router_fee(Permill::from_percent(50), Permill::from_percent(50))
+        .build()
+        .execute_with(|| {
+            //Arrange
+            let amount_to_sell = 10;
+            let fee = 3;
+
+            //Act
+            assert_ok!(Router::sell(
+                RuntimeOrigin::signed(ALICE),
+                KSM,
+                AUSD,
+                amount_to_sell,
+                XYK_SELL_CALCULATION_RESULT - fee,
+                vec![KSM_AUSD_TRADE_IN_XYK]
+            ));
+
+            //Assert
+            assert_eq!(
+                Currencies::free_balance(AUSD, &ALICE),
+                XYK_SELL_CALCULATION_RESULT - fee
+            );
+            assert_eq!(Currencies::free_balance(AUSD, &FEE_RECEIVER), fee);
+            expect_events(vec![Event::RouteExecuted {
+                asset_in: KSM,
+                asset_out: AUSD,
+                amount_in: amount_to_sell,
+                amount_out: XYK_SELL_CALCULATION_RESULT,
+                fee,
+                referral_fee: 0,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn sell_should_pay_referral_share_of_router_fee_when_account_is_linked_to_referrer() {
+    ExtBuilder::default()
+        .with_endowed_accounts(vec![(ALICE, KSM, 1000)])
+        .with_router_fee(Permill::from_percent(50), Permill::from_percent(50))
+        .build()
+        .execute_with(|| {
+            //Arrange
+            let amount_to_sell = 10;
+            let fee = 3;
+            let referral_fee = 1;
+            link_alice_to_bob();
+
+            //Act
+            assert_ok!(Router::sell(
+                RuntimeOrigin::signed(ALICE),
+                KSM,
+                AUSD,
+                amount_to_sell,
+                XYK_SELL_CALCULATION_RESULT - fee,
+                vec![KSM_AUSD_TRADE_IN_XYK]
+            ));
+
+            //Assert
+            assert_eq!(
+                Currencies::free_balance(AUSD, &ALICE),
+                XYK_SELL_CALCULATION_RESULT - fee
+            );
+            assert_eq!(Currencies::free_balance(AUSD, &BOB), referral_fee);
+            assert_eq!(Currencies::free_balance(AUSD, &FEE_RECEIVER), fee - referral_fee);
+            expect_events(vec![Event::RouteExecuted {
+                asset_in: KSM,
+                asset_out: AUSD,
+                amount_in: amount_to_sell,
+                amount_out: XYK_SELL_CALCULATION_RESULT,
+                fee,
+                referral_fee,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn sell_should_fail_when_min_limit_is_not_reached_after_router_fee() {
+    ExtBuilder::default()
+        .with_endowed_accounts(vec![(ALICE, KSM, 1000)])
+        .with_router_fee(Permill::from_percent(50), Permill::zero())
+        .build()
+        .execute_with(|| {
+            //Act and Assert
+            assert_noop!(
+                Router::sell(
+                    RuntimeOrigin::signed(ALICE),
+                    KSM,
+                    AUSD,
+                    10,
+                    XYK_SELL_CALCULATION_RESULT - 2,
+                    vec![KSM_AUSD_TRADE_IN_XYK]
+                ),
+                Error::<Test>::TradingLimitReached
+            );
+        });
+}
+
+#[test]
+fn buy_should_transfer_router_fee_in_asset_in_to_fee_receiver_and_referrer() {
+    ExtBuilder::default()
+        .with_endowed_accounts(vec![(ALICE, AUSD, 1000)])
+        .with_router_fee(Permill::from_percent(50), Permill::from_percent(50))
+        .build()
+        .execute_with(|| {
+            //Arrange
+            let amount_to_buy = 10;
+            let fee = 2;
+            let referral_fee = 1;
+            link_alice_to_bob();
+
+            //Act
+            assert_ok!(Router::buy(
+                RuntimeOrigin::signed(ALICE),
+                AUSD,
+                KSM,
+                amount_to_buy,
+                XYK_BUY_CALCULATION_RESULT + fee,
+                vec![AUSD_KSM_TRADE_IN_XYK]
+            ));
+
+            //Assert
+            assert_eq!(
+                Currencies::free_balance(AUSD, &ALICE),
+                1000 - XYK_BUY_CALCULATION_RESULT - fee
+            );
+            assert_eq!(Currencies::free_balance(AUSD, &BOB), referral_fee);
+            assert_eq!(Currencies::free_balance(AUSD, &FEE_RECEIVER), fee - referral_fee);
+            expect_events(vec![Event::RouteExecuted {
+                asset_in: AUSD,
+                asset_out: KSM,
+                amount_in: XYK_BUY_CALCULATION_RESULT,
+                amount_out: amount_to_buy,
+                fee,
+                referral_fee,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn buy_should_fail_when_max_limit_is_exceeded_by_router_fee() {
+    ExtBuilder::default()
+        .with_endowed_accounts(vec![(ALICE, AUSD, 1000)])
+        .with_router_fee(Permill::from_percent(50), Permill::zero())
+        .build()
+        .execute_with(|| {
+            //Act and Assert
+            assert_noop!(
+                Router::buy(
+                    RuntimeOrigin::signed(ALICE),
+                    AUSD,
+                    KSM,
+                    10,
+                    XYK_BUY_CALCULATION_RESULT + 1,
+                    vec![AUSD_KSM_TRADE_IN_XYK]
+                ),
+                Error::<Test>::TradingLimitReached
+            );
+        });
+}
@@ -49,6 +49,8 @@ fn sell_should_work_when_route_has_single_trade() {
             asset_out: AUSD,
             amount_in: amount_to_sell,
             amount_out: XYK_SELL_CALCULATION_RESULT,
+            fee: 0,
+            referral_fee: 0,
         }
         .into()]);
     });
@@ -155,6 +157,8 @@ fn sell_should_work_when_route_has_multiple_trades_with_same_pooltype() {
             asset_out: KSM,
             amount_in: amount_to_sell,
             amount_out: XYK_SELL_CALCULATION_RESULT,
+            fee: 0,
+            referral_fee: 0,
         }
         .into()]);
     });
@@ -205,6 +209,8 @@ fn sell_should_work_when_route_has_multiple_trades_with_different_pool_type() {
             asset_out: KSM,
             amount_in: amount_to_sell,
             amount_out: OMNIPOOL_SELL_CALCULATION_RESULT,
+            fee: 0,
+            referral_fee: 0,
         }
         .into()]);
     });
@@ -20,6 +20,12 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-09-16, STEPS: 5, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! `arbitrage` was added after this run. Its figures are taken from `sell`, which executes the same trades, until
+//! the `arbitrage` benchmark is run.
+//!
+//! `router_fee` was not benchmarked either. Its figures are an estimate of the `Referrers` read and two
+//! `transfer_non_native_currency` calls of pallet-currencies, to be replaced by the `router_fee` benchmark.
 
 // Executed Command:
 // target/release/basilisk
@@ -48,6 +54,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn sell(n: u32) -> Weight;
     fn buy(n: u32) -> Weight;
+    fn arbitrage(n: u32) -> Weight;
+    fn register_referral_code() -> Weight;
+    fn link_referral_code() -> Weight;
+    fn router_fee() -> Weight;
 }
 
 pub struct BasiliskWeight<T>(PhantomData<T>);
@@ -69,6 +79,29 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
             .saturating_add(T::DbWeight::get().writes(2 as u64))
             .saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
     }
+    fn arbitrage(n: u32) -> Weight {
+        Weight::from_ref_time(27_428_000 as u64)
+            .saturating_add(Weight::from_ref_time(84_248_000 as u64).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(4 as u64))
+            .saturating_add(T::DbWeight::get().reads((8 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes(2 as u64))
+            .saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+    }
+    fn register_referral_code() -> Weight {
+        Weight::from_ref_time(19_734_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn link_referral_code() -> Weight {
+        Weight::from_ref_time(21_056_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(2 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn router_fee() -> Weight {
+        Weight::from_ref_time(120_000_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(11 as u64))
+            .saturating_add(T::DbWeight::get().writes(8 as u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -89,4 +122,27 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(2 as u64))
             .saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
     }
+    fn arbitrage(n: u32) -> Weight {
+        Weight::from_ref_time(27_428_000 as u64)
+            .saturating_add(Weight::from_ref_time(84_248_000 as u64).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(4 as u64))
+            .saturating_add(RocksDbWeight::get().reads((8 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes(2 as u64))
+            .saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+    }
+    fn register_referral_code() -> Weight {
+        Weight::from_ref_time(19_734_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn link_referral_code() -> Weight {
+        Weight::from_ref_time(21_056_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(2 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn router_fee() -> Weight {
+        Weight::from_ref_time(120_000_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(11 as u64))
+            .saturating_add(RocksDbWeight::get().writes(8 as u64))
+    }
 }
@@ -146,12 +146,20 @@ pub trait OnCreatePoolHandler<AssetId> {
     /// If an asset is not registered, calling `on_trade` results in populating the price buffer in the price oracle pallet,
     /// but the entries are ignored and the average price for the asset is not calculated.
     fn on_create_pool(asset_a: AssetId, asset_b: AssetId) -> dispatch::DispatchResult;
+
+    /// Known overhead of registering a pool, including its share of `on_initialize/on_finalize`.
+    /// Add this weight to an extrinsic from which you call `on_create_pool`.
+    fn on_create_pool_weight() -> Weight;
 }
 
 impl<AssetId> OnCreatePoolHandler<AssetId> for () {
     fn on_create_pool(_asset_a: AssetId, _asset_b: AssetId) -> dispatch::DispatchResult {
         Ok(())
     }
+
+    fn on_create_pool_weight() -> Weight {
+        Weight::zero()
+    }
 }
 
 /// Handler used by AMM pools to perform some tasks when a trade is executed.
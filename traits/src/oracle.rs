@@ -56,6 +56,8 @@ pub enum OraclePeriod {
     Day,
     /// The oracle data was aggregated over the blocks of the last week.
     Week,
+    /// The oracle data was aggregated over the given number of blocks. Configured at runtime.
+    Custom(u32),
 }
 
 impl OraclePeriod {
@@ -299,6 +301,84 @@ impl<AssetId, BlockNumber, Price> AggregatedPriceOracle<AssetId, BlockNumber, Pr
     }
 }
 
+/// An oracle returning the time-weighted average price over the last `window` blocks.
+pub trait TwapOracle<AssetId, BlockNumber, Price> {
+    type Error;
+    fn get_twap(asset_a: AssetId, asset_b: AssetId, source: Source, window: BlockNumber) -> Result<Price, Self::Error>;
+
+    fn get_twap_weight() -> Weight;
+}
+
+/// Default implementation of the oracle trait that always returns `Err`.
+impl<AssetId, BlockNumber, Price> TwapOracle<AssetId, BlockNumber, Price> for () {
+    type Error = ();
+
+    fn get_twap(
+        _asset_a: AssetId,
+        _asset_b: AssetId,
+        _source: Source,
+        _window: BlockNumber,
+    ) -> Result<Price, Self::Error> {
+        Err(())
+    }
+
+    fn get_twap_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Provides the block in which the oracles of an asset pair were last updated, i.e. how recent the
+/// data of the oracles is. Note that this is different from the `oracle_age` of an entry, which
+/// counts the blocks since the oracle was initialized.
+pub trait OracleLastUpdate<AssetId, BlockNumber> {
+    fn last_update(asset_a: AssetId, asset_b: AssetId, source: Source) -> Option<BlockNumber>;
+
+    fn last_update_weight() -> Weight;
+}
+
+/// Default implementation of the trait that never provides an update.
+impl<AssetId, BlockNumber> OracleLastUpdate<AssetId, BlockNumber> for () {
+    fn last_update(_asset_a: AssetId, _asset_b: AssetId, _source: Source) -> Option<BlockNumber> {
+        None
+    }
+
+    fn last_update_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// An oracle returning the volatility of the price, i.e. the exponentially weighted variance of the
+/// price returns over the given period, together with the age of the oracle.
+pub trait VolatilityOracle<AssetId, BlockNumber, Volatility> {
+    type Error;
+    fn get_volatility(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<(Volatility, BlockNumber), Self::Error>;
+
+    fn get_volatility_weight() -> Weight;
+}
+
+/// Default implementation of the oracle trait that always returns `Err`.
+impl<AssetId, BlockNumber, Volatility> VolatilityOracle<AssetId, BlockNumber, Volatility> for () {
+    type Error = ();
+
+    fn get_volatility(
+        _asset_a: AssetId,
+        _asset_b: AssetId,
+        _period: OraclePeriod,
+        _source: Source,
+    ) -> Result<(Volatility, BlockNumber), Self::Error> {
+        Err(())
+    }
+
+    fn get_volatility_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 /// Mock implementation of the oracle trait that always returns `Price::one()` and oracle age of
 /// `BlockNumber::one()`.
 impl<AssetId, BlockNumber, Price> AggregatedPriceOracle<AssetId, BlockNumber, Price> for AlwaysPriceOfOne
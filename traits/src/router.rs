@@ -7,6 +7,8 @@ pub enum PoolType<AssetId> {
     LBP,
     Stableswap(AssetId),
     Omnipool,
+    /// OTC order identified by its order id
+    OTC(u32),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -0,0 +1,117 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use orml_traits::MultiCurrencyExtended;
+pub const ONE: Balance = 1_000_000_000_000;
+
+const ASSET_A: u32 = 1;
+const ASSET_B: u32 = 2;
+
+benchmarks! {
+    where_clause { where
+        T::AssetId: From<u32>,
+        T::Currency: MultiCurrencyExtended<T::AccountId, Amount=i128>,
+        T: crate::pallet::Config,
+    }
+  create_pool {
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 1)?;
+  }:  _(RawOrigin::Signed(caller.clone()), ASSET_A.into(), 1_000 * ONE, ASSET_B.into(), 2_000 * ONE)
+    verify {
+        assert_eq!(<TotalLiquidity<T>>::get(pair_account::<T>()), 1_000 * ONE);
+    }
+
+    add_liquidity {
+        let maker: T::AccountId = create_account_with_balances::<T>("maker", 1)?;
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 2)?;
+        create_pool_of::<T>(&maker)?;
+  }:  _(RawOrigin::Signed(caller.clone()), ASSET_A.into(), ASSET_B.into(), 10 * ONE, Balance::MAX)
+    verify {
+        let share_token = <ShareToken<T>>::get(pair_account::<T>());
+        assert_eq!(T::Currency::free_balance(share_token, &caller), 10 * ONE);
+    }
+
+    remove_liquidity {
+        let maker: T::AccountId = create_account_with_balances::<T>("maker", 1)?;
+        create_pool_of::<T>(&maker)?;
+        // worst case: all liquidity is removed and the pool is destroyed
+  }:  _(RawOrigin::Signed(maker.clone()), ASSET_A.into(), ASSET_B.into(), 1_000 * ONE)
+    verify {
+        assert!(<PoolAssets<T>>::get(pair_account::<T>()).is_none());
+    }
+
+    sell {
+        let maker: T::AccountId = create_account_with_balances::<T>("maker", 1)?;
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 2)?;
+        create_pool_of::<T>(&maker)?;
+  }:  _(RawOrigin::Signed(caller.clone()), ASSET_A.into(), ASSET_B.into(), 10 * ONE, 0)
+    verify {
+        assert!(T::Currency::free_balance(ASSET_B.into(), &caller) > 1_000_000 * ONE);
+    }
+
+    buy {
+        let maker: T::AccountId = create_account_with_balances::<T>("maker", 1)?;
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 2)?;
+        create_pool_of::<T>(&maker)?;
+  }:  _(RawOrigin::Signed(caller.clone()), ASSET_B.into(), ASSET_A.into(), 10 * ONE, Balance::MAX)
+    verify {
+        assert_eq!(T::Currency::free_balance(ASSET_B.into(), &caller), 1_000_010 * ONE);
+    }
+}
+
+fn pair_account<T: Config>() -> T::AccountId
+where
+    T::AssetId: From<u32>,
+{
+    <Pallet<T> as AMM<_, _, _, _>>::get_pair_id(AssetPair::new(ASSET_A.into(), ASSET_B.into()))
+}
+
+/// Pool of 1_000 `ASSET_A` and 2_000 `ASSET_B`
+fn create_pool_of<T: Config>(maker: &T::AccountId) -> DispatchResult
+where
+    T::AssetId: From<u32>,
+{
+    crate::Pallet::<T>::create_pool(
+        RawOrigin::Signed(maker.clone()).into(),
+        ASSET_A.into(),
+        1_000 * ONE,
+        ASSET_B.into(),
+        2_000 * ONE,
+    )
+}
+
+fn create_account_with_balances<T: Config>(name: &'static str, index: u32) -> Result<T::AccountId, DispatchError>
+where
+    T::AssetId: From<u32>,
+    T::Currency: MultiCurrencyExtended<T::AccountId, Amount = i128>,
+{
+    let account_id: T::AccountId = account(name, index, index);
+
+    T::Currency::update_balance(ASSET_A.into(), &account_id, 1_000_000 * ONE as i128)?;
+    T::Currency::update_balance(ASSET_B.into(), &account_id, 1_000_000 * ONE as i128)?;
+
+    Ok(account_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pallet;
+    use crate::tests::mock::*;
+    use frame_benchmarking::impl_benchmark_test_suite;
+
+    impl_benchmark_test_suite!(Pallet, super::ExtBuilder::default().build(), super::Test);
+}
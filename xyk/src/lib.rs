@@ -0,0 +1,874 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// # XYK pallet
+// ## General description
+// This pallet provides constant product (x * y = k) pools of two assets. Anyone can create a pool of
+// two assets, provide liquidity to it in exchange for the share token of the pool and trade against it.
+//
+// ## Notes
+// The share token of a pool is registered through `AssetRegistry` when the pool is created, the initial
+// liquidity provider receives the amount of the lower asset id as shares.
+//
+// A `TradeFee` is charged on every trade - in `asset_out` when selling and in `asset_in` when buying.
+// The fee stays in the pool.
+//
+// The pool is destroyed when all of its liquidity is removed.
+//
+// Every trade and liquidity change is reported to `AMMHandler` so that the oracles are fed.
+//
+// ## Dispatachable functions
+// * `create_pool` - create a new pool with initial liquidity.
+// * `add_liquidity` - add liquidity to an existing pool.
+// * `remove_liquidity` - remove liquidity from a pool.
+// * `sell` - sell an asset to a pool.
+// * `buy` - buy an asset from a pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use frame_system::ensure_signed;
+use hydradx_traits::{
+    pools::{DustRemovalAccountWhitelist, SpotPriceProvider},
+    AMMPosition, AMMTransfer, AccountIdFor, CanCreatePool, OnCreatePoolHandler, OnLiquidityChangedHandler,
+    OnTradeHandler, ShareTokenRegistry, Source, AMM,
+};
+use orml_traits::MultiCurrency;
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational_with_rounding, traits::Zero, ArithmeticError, DispatchError, FixedPointNumber,
+    FixedU128, Permill, Rounding,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarks;
+
+mod trade_execution;
+pub mod types;
+pub mod weights;
+
+pub use trade_execution::*;
+pub use types::{AssetPair, Balance};
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// XYK share token and account id identifier.
+/// Used as identifier to create share token unique names and account ids.
+pub const POOL_IDENTIFIER: &[u8] = b"xyk";
+
+/// Oracle source of the XYK pools.
+pub const SOURCE: Source = *b"snakexyk";
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use codec::HasCompact;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::OriginFor;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Identifier for the class of asset.
+        type AssetId: Member
+            + Parameter
+            + Ord
+            + Default
+            + Copy
+            + HasCompact
+            + MaybeSerializeDeserialize
+            + MaxEncodedLen
+            + TypeInfo;
+
+        /// Registry of the share tokens.
+        type AssetRegistry: ShareTokenRegistry<Self::AssetId, Vec<u8>, Balance, DispatchError>;
+
+        /// Account ID and share token name constructor.
+        type AssetPairAccountId: AccountIdFor<Vec<Self::AssetId>, AccountId = Self::AccountId>;
+
+        /// Multi currency mechanism.
+        type Currency: MultiCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
+
+        /// Trading fee rate.
+        #[pallet::constant]
+        type TradeFee: Get<Permill>;
+
+        /// Minimum trading amount.
+        #[pallet::constant]
+        type MinTradingLimit: Get<Balance>;
+
+        /// Minimum pool liquidity.
+        #[pallet::constant]
+        type MinPoolLiquidity: Get<Balance>;
+
+        /// Max fraction of the pool reserve that can be sold in a single transaction.
+        #[pallet::constant]
+        type MaxInRatio: Get<u128>;
+
+        /// Max fraction of the pool reserve that can be bought in a single transaction.
+        #[pallet::constant]
+        type MaxOutRatio: Get<u128>;
+
+        /// Filter of the asset pairs which can be used to create a pool.
+        type CanCreatePool: CanCreatePool<Self::AssetId>;
+
+        /// Handler called when a pool is created, a trade is executed or the liquidity of a pool changes.
+        type AMMHandler: OnCreatePoolHandler<Self::AssetId>
+            + OnTradeHandler<Self::AssetId, Balance>
+            + OnLiquidityChangedHandler<Self::AssetId, Balance>;
+
+        /// Whitelist of the accounts which are not dusted, used to protect the pool accounts.
+        type NonDustableWhitelistHandler: DustRemovalAccountWhitelist<Self::AccountId, Error = DispatchError>;
+
+        /// Weight information for the extrinsics.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Share token of a pool.
+    #[pallet::storage]
+    #[pallet::getter(fn share_token)]
+    pub type ShareToken<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AssetId, ValueQuery>;
+
+    /// Total amount of shares of a pool.
+    #[pallet::storage]
+    #[pallet::getter(fn total_liquidity)]
+    pub type TotalLiquidity<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+    /// Assets of a pool.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_assets)]
+    pub type PoolAssets<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (T::AssetId, T::AssetId)>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A pool was created.
+        PoolCreated {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            initial_shares_amount: Balance,
+            share_token: T::AssetId,
+            pool: T::AccountId,
+        },
+        /// Liquidity was added to a pool.
+        LiquidityAdded {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b: Balance,
+            shares: Balance,
+        },
+        /// Liquidity was removed from a pool.
+        LiquidityRemoved {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b: Balance,
+            shares: Balance,
+        },
+        /// All liquidity was removed from a pool and the pool was destroyed.
+        PoolDestroyed {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            share_token: T::AssetId,
+            pool: T::AccountId,
+        },
+        /// Sell trade executed. Trade fee paid in asset leaving the pool (already subtracted from amount_out).
+        SellExecuted {
+            who: T::AccountId,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            amount_out: Balance,
+            fee: Balance,
+            pool: T::AccountId,
+        },
+        /// Buy trade executed. Trade fee paid in asset entering the pool (already included in amount_in).
+        BuyExecuted {
+            who: T::AccountId,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            amount_out: Balance,
+            fee: Balance,
+            pool: T::AccountId,
+        },
+    }
+
+    #[pallet::error]
+    #[cfg_attr(test, derive(PartialEq, Eq))]
+    pub enum Error<T> {
+        /// Creating a pool with same assets is not allowed.
+        CannotCreatePoolWithSameAssets,
+        /// Creating a pool of the given assets is not allowed.
+        CannotCreatePool,
+        /// A pool with given assets already exists.
+        TokenPoolAlreadyExists,
+        /// A pool with given assets does not exist.
+        TokenPoolNotFound,
+        /// Liquidity has not reached the required minimum.
+        InsufficientLiquidity,
+        /// Amount is less than the minimum trading amount configured.
+        InsufficientTradingAmount,
+        /// Liquidity amount must be greater than zero.
+        InvalidLiquidityAmount,
+        /// Minted or burned amount of shares is zero.
+        InvalidMintedLiquidity,
+        /// Balance of an asset is not sufficient to perform the operation.
+        InsufficientAssetBalance,
+        /// Balance of a share token is not sufficient to remove liquidity.
+        InsufficientShares,
+        /// Reserve of the pool is not sufficient to perform the trade.
+        InsufficientPoolAssetBalance,
+        /// Required amount of the second asset exceeds the given limit.
+        AssetAmountExceededLimit,
+        /// Minimum limit has not been reached during trade.
+        BuyLimitNotReached,
+        /// Maximum limit has been exceeded during trade.
+        SellLimitExceeded,
+        /// Amount sold exceeds `MaxInRatio` of the pool reserve.
+        MaxInRatioExceeded,
+        /// Amount bought exceeds `MaxOutRatio` of the pool reserve.
+        MaxOutRatioExceeded,
+        /// Discounted trades are not supported.
+        DiscountNotSupported,
+        /// Calculation of the amounts failed.
+        MathError,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a pool of `asset_a` and `asset_b` and provide its initial liquidity.
+        ///
+        /// The share token of the pool is created through `AssetRegistry` if it does not exist yet.
+        /// Origin receives the initial amount of the lower asset id of the pair as shares.
+        ///
+        /// Parameters:
+        /// - `origin`: liquidity provider
+        /// - `asset_a`: first asset of the pool
+        /// - `amount_a`: initial liquidity of `asset_a`
+        /// - `asset_b`: second asset of the pool
+        /// - `amount_b`: initial liquidity of `asset_b`
+        ///
+        /// Emits `PoolCreated` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_pool()
+            .saturating_add(T::AMMHandler::on_create_pool_weight())
+            .saturating_add(T::AMMHandler::on_liquidity_changed_weight()))]
+        #[transactional]
+        pub fn create_pool(
+            origin: OriginFor<T>,
+            asset_a: T::AssetId,
+            amount_a: Balance,
+            asset_b: T::AssetId,
+            amount_b: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
+            ensure!(
+                T::CanCreatePool::can_create(asset_a, asset_b),
+                Error::<T>::CannotCreatePool
+            );
+            ensure!(
+                amount_a >= T::MinPoolLiquidity::get() && amount_b >= T::MinPoolLiquidity::get(),
+                Error::<T>::InsufficientLiquidity
+            );
+
+            let asset_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(!Self::exists(asset_pair), Error::<T>::TokenPoolAlreadyExists);
+
+            ensure!(
+                T::Currency::free_balance(asset_a, &who) >= amount_a,
+                Error::<T>::InsufficientAssetBalance
+            );
+            ensure!(
+                T::Currency::free_balance(asset_b, &who) >= amount_b,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            let shares_added = if asset_a < asset_b { amount_a } else { amount_b };
+
+            let pair_account = Self::get_pair_id(asset_pair);
+            let ordered_assets = asset_pair.ordered_assets();
+            let token_name = T::AssetPairAccountId::name(&ordered_assets, Some(POOL_IDENTIFIER));
+            let share_token =
+                T::AssetRegistry::get_or_create_shared_asset(token_name, ordered_assets, T::MinPoolLiquidity::get())?;
+
+            T::AMMHandler::on_create_pool(asset_a, asset_b)?;
+            T::NonDustableWhitelistHandler::add_account(&pair_account)?;
+
+            <ShareToken<T>>::insert(&pair_account, share_token);
+            <PoolAssets<T>>::insert(&pair_account, asset_pair.ordered_pair());
+            <TotalLiquidity<T>>::insert(&pair_account, shares_added);
+
+            T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
+            T::Currency::transfer(asset_b, &who, &pair_account, amount_b)?;
+            T::Currency::deposit(share_token, &who, shares_added)?;
+
+            T::AMMHandler::on_liquidity_changed(SOURCE, asset_a, asset_b, amount_a, amount_b, amount_a, amount_b)
+                .map_err(|(_, e)| e)?;
+
+            Self::deposit_event(Event::PoolCreated {
+                who,
+                asset_a,
+                asset_b,
+                initial_shares_amount: shares_added,
+                share_token,
+                pool: pair_account,
+            });
+
+            Ok(())
+        }
+
+        /// Add liquidity to the pool of `asset_a` and `asset_b`.
+        ///
+        /// `amount_a` of `asset_a` is provided, the amount of `asset_b` is determined by the ratio
+        /// of the pool reserves and must not exceed `amount_b_max_limit`.
+        ///
+        /// Parameters:
+        /// - `origin`: liquidity provider
+        /// - `asset_a`: first asset of the pool
+        /// - `asset_b`: second asset of the pool
+        /// - `amount_a`: amount of `asset_a` to provide
+        /// - `amount_b_max_limit`: maximum amount of `asset_b` to provide
+        ///
+        /// Emits `LiquidityAdded` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_liquidity()
+            .saturating_add(T::AMMHandler::on_liquidity_changed_weight()))]
+        #[transactional]
+        pub fn add_liquidity(
+            origin: OriginFor<T>,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b_max_limit: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let asset_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+            ensure!(
+                amount_a >= T::MinTradingLimit::get(),
+                Error::<T>::InsufficientTradingAmount
+            );
+            ensure!(!amount_b_max_limit.is_zero(), Error::<T>::InvalidLiquidityAmount);
+            ensure!(
+                T::Currency::free_balance(asset_a, &who) >= amount_a,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            let pair_account = Self::get_pair_id(asset_pair);
+            let share_token = Self::share_token(&pair_account);
+            let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+            let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+            let total_liquidity = Self::total_liquidity(&pair_account);
+
+            let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
+                .map_err(|_| Error::<T>::MathError)?;
+            ensure!(!amount_b.is_zero(), Error::<T>::InvalidLiquidityAmount);
+            ensure!(amount_b <= amount_b_max_limit, Error::<T>::AssetAmountExceededLimit);
+            ensure!(
+                T::Currency::free_balance(asset_b, &who) >= amount_b,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            let shares_added =
+                multiply_by_rational_with_rounding(amount_a, total_liquidity, asset_a_reserve, Rounding::Down)
+                    .ok_or(Error::<T>::MathError)?;
+            ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
+
+            let liquidity_amount = total_liquidity
+                .checked_add(shares_added)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
+            T::Currency::transfer(asset_b, &who, &pair_account, amount_b)?;
+            T::Currency::deposit(share_token, &who, shares_added)?;
+
+            <TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
+
+            T::AMMHandler::on_liquidity_changed(
+                SOURCE,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                asset_a_reserve.saturating_add(amount_a),
+                asset_b_reserve.saturating_add(amount_b),
+            )
+            .map_err(|(_, e)| e)?;
+
+            Self::deposit_event(Event::LiquidityAdded {
+                who,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                shares: shares_added,
+            });
+
+            Ok(())
+        }
+
+        /// Remove liquidity from the pool of `asset_a` and `asset_b`.
+        ///
+        /// The shares are burned and origin receives the corresponding amounts of both assets.
+        /// The pool is destroyed when all of its liquidity is removed.
+        ///
+        /// Parameters:
+        /// - `origin`: liquidity provider
+        /// - `asset_a`: first asset of the pool
+        /// - `asset_b`: second asset of the pool
+        /// - `liquidity_amount`: amount of shares to burn
+        ///
+        /// Emits `LiquidityRemoved` event when successful and `PoolDestroyed` event when the pool is destroyed.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_liquidity()
+            .saturating_add(T::AMMHandler::on_liquidity_changed_weight()))]
+        #[transactional]
+        pub fn remove_liquidity(
+            origin: OriginFor<T>,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            liquidity_amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let asset_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+            ensure!(!liquidity_amount.is_zero(), Error::<T>::InvalidLiquidityAmount);
+
+            let pair_account = Self::get_pair_id(asset_pair);
+            let share_token = Self::share_token(&pair_account);
+            let total_shares = Self::total_liquidity(&pair_account);
+
+            ensure!(
+                T::Currency::free_balance(share_token, &who) >= liquidity_amount,
+                Error::<T>::InsufficientShares
+            );
+
+            let liquidity_left = total_shares
+                .checked_sub(liquidity_amount)
+                .ok_or(Error::<T>::InsufficientShares)?;
+            ensure!(
+                liquidity_left.is_zero() || liquidity_left >= T::MinPoolLiquidity::get(),
+                Error::<T>::InsufficientLiquidity
+            );
+
+            let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+            let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+            let (amount_a, amount_b) = hydra_dx_math::xyk::calculate_liquidity_out(
+                asset_a_reserve,
+                asset_b_reserve,
+                liquidity_amount,
+                total_shares,
+            )
+            .map_err(|_| Error::<T>::MathError)?;
+
+            T::Currency::transfer(asset_a, &pair_account, &who, amount_a)?;
+            T::Currency::transfer(asset_b, &pair_account, &who, amount_b)?;
+            T::Currency::withdraw(share_token, &who, liquidity_amount)?;
+
+            <TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
+
+            T::AMMHandler::on_liquidity_changed(
+                SOURCE,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                asset_a_reserve.saturating_sub(amount_a),
+                asset_b_reserve.saturating_sub(amount_b),
+            )
+            .map_err(|(_, e)| e)?;
+
+            Self::deposit_event(Event::LiquidityRemoved {
+                who: who.clone(),
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                shares: liquidity_amount,
+            });
+
+            if liquidity_left.is_zero() {
+                T::NonDustableWhitelistHandler::remove_account(&pair_account)?;
+
+                <ShareToken<T>>::remove(&pair_account);
+                <PoolAssets<T>>::remove(&pair_account);
+                <TotalLiquidity<T>>::remove(&pair_account);
+
+                Self::deposit_event(Event::PoolDestroyed {
+                    who,
+                    asset_a,
+                    asset_b,
+                    share_token,
+                    pool: pair_account,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Sell `amount` of `asset_in` to the pool of `asset_in` and `asset_out`.
+        ///
+        /// Parameters:
+        /// - `origin`: origin of the caller
+        /// - `asset_in`: ID of asset sold to the pool
+        /// - `asset_out`: ID of asset bought from the pool
+        /// - `amount`: amount of `asset_in` to sell
+        /// - `min_bought`: minimum amount of `asset_out` to receive
+        ///
+        /// Emits `SellExecuted` event when successful.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::sell()
+            .saturating_add(T::AMMHandler::on_trade_weight()))]
+        #[transactional]
+        pub fn sell(
+            origin: OriginFor<T>,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount: Balance,
+            min_bought: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <Self as AMM<_, _, _, _>>::sell(&who, AssetPair::new(asset_in, asset_out), amount, min_bought, false)
+        }
+
+        /// Buy `amount` of `asset_out` from the pool of `asset_in` and `asset_out`.
+        ///
+        /// Parameters:
+        /// - `origin`: origin of the caller
+        /// - `asset_out`: ID of asset bought from the pool
+        /// - `asset_in`: ID of asset sold to the pool
+        /// - `amount`: amount of `asset_out` to buy
+        /// - `max_sold`: maximum amount of `asset_in` to pay
+        ///
+        /// Emits `BuyExecuted` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::buy()
+            .saturating_add(T::AMMHandler::on_trade_weight()))]
+        #[transactional]
+        pub fn buy(
+            origin: OriginFor<T>,
+            asset_out: T::AssetId,
+            asset_in: T::AssetId,
+            amount: Balance,
+            max_sold: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <Self as AMM<_, _, _, _>>::buy(&who, AssetPair::new(asset_in, asset_out), amount, max_sold, false)
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Amount of `asset_out` received for `amount_in` of `asset_in` and the trade fee, in `asset_out`.
+    pub(crate) fn calculate_out_amount(
+        asset_pair: AssetPair<T::AssetId>,
+        amount_in: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+        let pair_account = Self::get_pair_id(asset_pair);
+        let asset_in_reserve = T::Currency::free_balance(asset_pair.asset_in, &pair_account);
+        let asset_out_reserve = T::Currency::free_balance(asset_pair.asset_out, &pair_account);
+
+        ensure!(
+            amount_in <= asset_in_reserve / T::MaxInRatio::get(),
+            Error::<T>::MaxInRatioExceeded
+        );
+
+        let amount_out = hydra_dx_math::xyk::calculate_out_given_in(asset_in_reserve, asset_out_reserve, amount_in)
+            .map_err(|_| Error::<T>::MathError)?;
+        let fee = T::TradeFee::get().mul_ceil(amount_out);
+        let amount_out = amount_out.checked_sub(fee).ok_or(Error::<T>::MathError)?;
+
+        ensure!(
+            amount_out <= asset_out_reserve / T::MaxOutRatio::get(),
+            Error::<T>::MaxOutRatioExceeded
+        );
+
+        Ok((amount_out, fee))
+    }
+
+    /// Amount of `asset_in` paid for `amount_out` of `asset_out` and the trade fee, in `asset_in`.
+    pub(crate) fn calculate_in_amount(
+        asset_pair: AssetPair<T::AssetId>,
+        amount_out: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+        let pair_account = Self::get_pair_id(asset_pair);
+        let asset_in_reserve = T::Currency::free_balance(asset_pair.asset_in, &pair_account);
+        let asset_out_reserve = T::Currency::free_balance(asset_pair.asset_out, &pair_account);
+
+        ensure!(amount_out < asset_out_reserve, Error::<T>::InsufficientPoolAssetBalance);
+        ensure!(
+            amount_out <= asset_out_reserve / T::MaxOutRatio::get(),
+            Error::<T>::MaxOutRatioExceeded
+        );
+
+        let amount_in = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount_out)
+            .map_err(|_| Error::<T>::MathError)?;
+        let fee = T::TradeFee::get().mul_ceil(amount_in);
+        let amount_in = amount_in.checked_add(fee).ok_or(Error::<T>::MathError)?;
+
+        ensure!(
+            amount_in <= asset_in_reserve / T::MaxInRatio::get(),
+            Error::<T>::MaxInRatioExceeded
+        );
+
+        Ok((amount_in, fee))
+    }
+}
+
+impl<T: Config> AMM<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance> for Pallet<T> {
+    fn exists(assets: AssetPair<T::AssetId>) -> bool {
+        <PoolAssets<T>>::contains_key(Self::get_pair_id(assets))
+    }
+
+    fn get_pair_id(assets: AssetPair<T::AssetId>) -> T::AccountId {
+        T::AssetPairAccountId::from_assets(&assets.ordered_assets(), Some(POOL_IDENTIFIER))
+    }
+
+    fn get_share_token(assets: AssetPair<T::AssetId>) -> T::AssetId {
+        Self::share_token(Self::get_pair_id(assets))
+    }
+
+    fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<T::AssetId>> {
+        Self::pool_assets(pool_account_id).map(|(asset_a, asset_b)| vec![asset_a, asset_b])
+    }
+
+    fn get_spot_price_unchecked(asset_a: T::AssetId, asset_b: T::AssetId, amount: Balance) -> Balance {
+        let pair_account = Self::get_pair_id(AssetPair::new(asset_a, asset_b));
+        let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+        let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+        hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
+            .unwrap_or_else(|_| Balance::zero())
+    }
+
+    fn validate_sell(
+        origin: &T::AccountId,
+        assets: AssetPair<T::AssetId>,
+        amount: Balance,
+        min_bought: Balance,
+        discount: bool,
+    ) -> Result<AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>, DispatchError> {
+        ensure!(!discount, Error::<T>::DiscountNotSupported);
+        ensure!(
+            amount >= T::MinTradingLimit::get(),
+            Error::<T>::InsufficientTradingAmount
+        );
+        ensure!(
+            T::Currency::free_balance(assets.asset_in, origin) >= amount,
+            Error::<T>::InsufficientAssetBalance
+        );
+
+        let (amount_out, fee) = Self::calculate_out_amount(assets, amount)?;
+        ensure!(amount_out >= min_bought, Error::<T>::BuyLimitNotReached);
+
+        Ok(AMMTransfer {
+            origin: origin.clone(),
+            assets,
+            amount,
+            amount_b: amount_out,
+            discount,
+            discount_amount: Balance::zero(),
+            fee: (assets.asset_out, fee),
+        })
+    }
+
+    fn execute_sell(
+        transfer: &AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>,
+    ) -> DispatchResult {
+        let pair_account = Self::get_pair_id(transfer.assets);
+        let AssetPair { asset_in, asset_out } = transfer.assets;
+
+        T::Currency::transfer(asset_in, &transfer.origin, &pair_account, transfer.amount)?;
+        T::Currency::transfer(asset_out, &pair_account, &transfer.origin, transfer.amount_b)?;
+
+        T::AMMHandler::on_trade(
+            SOURCE,
+            asset_in,
+            asset_out,
+            transfer.amount,
+            transfer.amount_b,
+            T::Currency::free_balance(asset_in, &pair_account),
+            T::Currency::free_balance(asset_out, &pair_account),
+        )
+        .map_err(|(_, e)| e)?;
+
+        Self::deposit_event(Event::SellExecuted {
+            who: transfer.origin.clone(),
+            asset_in,
+            asset_out,
+            amount_in: transfer.amount,
+            amount_out: transfer.amount_b,
+            fee: transfer.fee.1,
+            pool: pair_account,
+        });
+
+        Ok(())
+    }
+
+    fn validate_buy(
+        origin: &T::AccountId,
+        assets: AssetPair<T::AssetId>,
+        amount: Balance,
+        max_limit: Balance,
+        discount: bool,
+    ) -> Result<AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>, DispatchError> {
+        ensure!(!discount, Error::<T>::DiscountNotSupported);
+        ensure!(
+            amount >= T::MinTradingLimit::get(),
+            Error::<T>::InsufficientTradingAmount
+        );
+
+        let (amount_in, fee) = Self::calculate_in_amount(assets, amount)?;
+        ensure!(amount_in <= max_limit, Error::<T>::SellLimitExceeded);
+        ensure!(
+            T::Currency::free_balance(assets.asset_in, origin) >= amount_in,
+            Error::<T>::InsufficientAssetBalance
+        );
+
+        Ok(AMMTransfer {
+            origin: origin.clone(),
+            assets,
+            amount,
+            amount_b: amount_in,
+            discount,
+            discount_amount: Balance::zero(),
+            fee: (assets.asset_in, fee),
+        })
+    }
+
+    fn execute_buy(transfer: &AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>) -> DispatchResult {
+        let pair_account = Self::get_pair_id(transfer.assets);
+        let AssetPair { asset_in, asset_out } = transfer.assets;
+
+        T::Currency::transfer(asset_in, &transfer.origin, &pair_account, transfer.amount_b)?;
+        T::Currency::transfer(asset_out, &pair_account, &transfer.origin, transfer.amount)?;
+
+        T::AMMHandler::on_trade(
+            SOURCE,
+            asset_in,
+            asset_out,
+            transfer.amount_b,
+            transfer.amount,
+            T::Currency::free_balance(asset_in, &pair_account),
+            T::Currency::free_balance(asset_out, &pair_account),
+        )
+        .map_err(|(_, e)| e)?;
+
+        Self::deposit_event(Event::BuyExecuted {
+            who: transfer.origin.clone(),
+            asset_in,
+            asset_out,
+            amount_in: transfer.amount_b,
+            amount_out: transfer.amount,
+            fee: transfer.fee.1,
+            pool: pair_account,
+        });
+
+        Ok(())
+    }
+
+    fn get_min_trading_limit() -> Balance {
+        T::MinTradingLimit::get()
+    }
+
+    fn get_min_pool_liquidity() -> Balance {
+        T::MinPoolLiquidity::get()
+    }
+
+    fn get_max_in_ratio() -> u128 {
+        T::MaxInRatio::get()
+    }
+
+    fn get_max_out_ratio() -> u128 {
+        T::MaxOutRatio::get()
+    }
+
+    fn get_fee(_pool_account_id: &T::AccountId) -> (u32, u32) {
+        (T::TradeFee::get().deconstruct(), Permill::one().deconstruct())
+    }
+}
+
+impl<T: Config> AMMPosition<T::AssetId, Balance> for Pallet<T> {
+    type Error = DispatchError;
+
+    fn get_liquidity_behind_shares(
+        asset_a: T::AssetId,
+        asset_b: T::AssetId,
+        shares_amount: Balance,
+    ) -> Result<(Balance, Balance), Self::Error> {
+        let asset_pair = AssetPair::new(asset_a, asset_b);
+        ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+        let pair_account = Self::get_pair_id(asset_pair);
+        let total_shares = Self::total_liquidity(&pair_account);
+        let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+        let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+        hydra_dx_math::xyk::calculate_liquidity_out(asset_a_reserve, asset_b_reserve, shares_amount, total_shares)
+            .map_err(|_| Error::<T>::MathError.into())
+    }
+}
+
+/// Spot prices of the XYK pools.
+pub struct XYKSpotPrice<T>(PhantomData<T>);
+
+impl<T: Config> SpotPriceProvider<T::AssetId> for XYKSpotPrice<T> {
+    type Price = FixedU128;
+
+    fn pair_exists(asset_a: T::AssetId, asset_b: T::AssetId) -> bool {
+        Pallet::<T>::exists(AssetPair::new(asset_a, asset_b))
+    }
+
+    fn spot_price(asset_a: T::AssetId, asset_b: T::AssetId) -> Option<Self::Price> {
+        if !Self::pair_exists(asset_a, asset_b) {
+            return None;
+        }
+
+        let pair_account = Pallet::<T>::get_pair_id(AssetPair::new(asset_a, asset_b));
+        let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+        let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+        FixedU128::checked_from_rational(asset_a_reserve, asset_b_reserve)
+    }
+}
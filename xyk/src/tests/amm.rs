@@ -0,0 +1,81 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{AssetPair, Error, XYKSpotPrice};
+use frame_support::assert_noop;
+use hydradx_traits::{pools::SpotPriceProvider, AMMPosition, AMM};
+use pretty_assertions::assert_eq;
+use sp_runtime::FixedU128;
+
+#[test]
+fn amm_should_provide_pool_info() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert!(XYK::exists(AssetPair::new(DAI, HDX)));
+        assert!(!XYK::exists(AssetPair::new(HDX, DOT)));
+        assert_eq!(XYK::get_pair_id(AssetPair::new(DAI, HDX)), HDX_DAI_POOL);
+        assert_eq!(XYK::get_share_token(AssetPair::new(HDX, DAI)), FIRST_SHARE_TOKEN);
+        assert_eq!(XYK::get_pool_assets(&HDX_DAI_POOL), Some(vec![HDX, DAI]));
+        assert_eq!(XYK::get_fee(&HDX_DAI_POOL), (3_000, 1_000_000));
+    });
+}
+
+#[test]
+fn amm_should_fail_discounted_trades() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            <XYK as AMM<_, _, _, _>>::sell(&BOB, AssetPair::new(HDX, DAI), 10 * ONE, 0, true),
+            Error::<Test>::DiscountNotSupported
+        );
+    });
+}
+
+#[test]
+fn spot_price_unchecked_should_return_price_of_amount() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_eq!(XYK::get_spot_price_unchecked(HDX, DAI, ONE), 2 * ONE);
+        assert_eq!(XYK::get_spot_price_unchecked(DAI, HDX, 2 * ONE), ONE);
+    });
+}
+
+#[test]
+fn spot_price_provider_should_return_price_of_asset_b_in_asset_a() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert!(XYKSpotPrice::<Test>::pair_exists(HDX, DAI));
+        assert_eq!(
+            XYKSpotPrice::<Test>::spot_price(DAI, HDX),
+            Some(FixedU128::from_rational(2, 1))
+        );
+        assert_eq!(
+            XYKSpotPrice::<Test>::spot_price(HDX, DAI),
+            Some(FixedU128::from_rational(1, 2))
+        );
+        assert_eq!(XYKSpotPrice::<Test>::spot_price(HDX, DOT), None);
+    });
+}
+
+#[test]
+fn liquidity_behind_shares_should_be_proportional_to_reserves() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_eq!(
+            XYK::get_liquidity_behind_shares(HDX, DAI, 100 * ONE),
+            Ok((100 * ONE, 200 * ONE))
+        );
+        assert_eq!(
+            XYK::get_liquidity_behind_shares(HDX, DOT, 100 * ONE),
+            Err(Error::<Test>::TokenPoolNotFound.into())
+        );
+    });
+}
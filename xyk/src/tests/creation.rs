@@ -0,0 +1,180 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::weights::WeightInfo;
+use crate::{Error, Event, SOURCE};
+use frame_support::dispatch::GetDispatchInfo;
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::{OnCreatePoolHandler, OnLiquidityChangedHandler};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn create_pool_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            HDX,
+            1_000 * ONE,
+            DAI,
+            2_000 * ONE
+        ));
+
+        // Assert
+        assert_eq!(pool_account(HDX, DAI), HDX_DAI_POOL);
+        assert_eq!(XYK::share_token(HDX_DAI_POOL), FIRST_SHARE_TOKEN);
+        assert_eq!(XYK::total_liquidity(HDX_DAI_POOL), 1_000 * ONE);
+        assert_eq!(XYK::pool_assets(HDX_DAI_POOL), Some((HDX, DAI)));
+
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 1_000 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 2_000 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 9_000 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 8_000 * ONE);
+        assert_eq!(Tokens::free_balance(FIRST_SHARE_TOKEN, &ALICE), 1_000 * ONE);
+
+        assert!(is_whitelisted(HDX_DAI_POOL));
+        assert_eq!(CREATED_POOLS.with(|v| v.borrow().clone()), vec![(HDX, DAI)]);
+        assert_eq!(
+            last_liquidity_change(),
+            Some((SOURCE, HDX, DAI, 1_000 * ONE, 2_000 * ONE, 1_000 * ONE, 2_000 * ONE))
+        );
+
+        expect_events(vec![Event::PoolCreated {
+            who: ALICE,
+            asset_a: HDX,
+            asset_b: DAI,
+            initial_shares_amount: 1_000 * ONE,
+            share_token: FIRST_SHARE_TOKEN,
+            pool: HDX_DAI_POOL,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn create_pool_should_use_same_pool_when_assets_are_in_reverse_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            2_000 * ONE,
+            HDX,
+            1_000 * ONE
+        ));
+
+        // Assert
+        assert_eq!(pool_account(DAI, HDX), HDX_DAI_POOL);
+        assert_eq!(XYK::pool_assets(HDX_DAI_POOL), Some((HDX, DAI)));
+        // shares are always initialized to the amount of the lower asset id
+        assert_eq!(XYK::total_liquidity(HDX_DAI_POOL), 1_000 * ONE);
+        assert_eq!(Tokens::free_balance(FIRST_SHARE_TOKEN, &ALICE), 1_000 * ONE);
+    });
+}
+
+#[test]
+fn create_pool_should_register_new_share_token_for_each_pool() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            HDX,
+            1_000 * ONE,
+            DOT,
+            100 * ONE
+        ));
+
+        // Assert
+        assert_eq!(XYK::share_token(pool_account(HDX, DOT)), FIRST_SHARE_TOKEN + 1);
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_assets_are_same() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 1_000 * ONE, HDX, 1_000 * ONE),
+            Error::<Test>::CannotCreatePoolWithSameAssets
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_is_not_allowed() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 1_000 * ONE, BSX, 1_000 * ONE),
+            Error::<Test>::CannotCreatePool
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_initial_liquidity_is_too_low() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 1_000 * ONE, DAI, 999),
+            Error::<Test>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_already_exists() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(BOB), DAI, 1_000 * ONE, HDX, 1_000 * ONE),
+            Error::<Test>::TokenPoolAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_balance_is_insufficient() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(BOB), HDX, 1_000 * ONE, DOT, 1_000 * ONE),
+            Error::<Test>::InsufficientAssetBalance
+        );
+    });
+}
+
+#[test]
+fn create_pool_weight_should_include_amm_handler_weights() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let call = crate::Call::<Test>::create_pool {
+            asset_a: HDX,
+            amount_a: 1_000 * ONE,
+            asset_b: DAI,
+            amount_b: 2_000 * ONE,
+        };
+
+        // Act
+        let weight = call.get_dispatch_info().weight;
+
+        // Assert
+        assert_eq!(
+            weight,
+            <Test as crate::Config>::WeightInfo::create_pool()
+                .saturating_add(<MockOracle as OnCreatePoolHandler<AssetId>>::on_create_pool_weight())
+                .saturating_add(
+                    <MockOracle as OnLiquidityChangedHandler<AssetId, Balance>>::on_liquidity_changed_weight()
+                )
+        );
+    });
+}
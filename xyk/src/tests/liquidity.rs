@@ -0,0 +1,190 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{AssetPair, Error, Event, SOURCE};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::AMM;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn add_liquidity_should_work() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::add_liquidity(
+            RuntimeOrigin::signed(BOB),
+            HDX,
+            DAI,
+            100 * ONE,
+            300 * ONE
+        ));
+
+        // Assert
+        assert_eq!(XYK::total_liquidity(HDX_DAI_POOL), 1_100 * ONE);
+        assert_eq!(Tokens::free_balance(FIRST_SHARE_TOKEN, &BOB), 100 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), 9_900 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), 9_800 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 1_100 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 2_200 * ONE);
+
+        assert_eq!(
+            last_liquidity_change(),
+            Some((SOURCE, HDX, DAI, 100 * ONE, 200 * ONE, 1_100 * ONE, 2_200 * ONE))
+        );
+
+        expect_events(vec![Event::LiquidityAdded {
+            who: BOB,
+            asset_a: HDX,
+            asset_b: DAI,
+            amount_a: 100 * ONE,
+            amount_b: 200 * ONE,
+            shares: 100 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_amount_b_exceeds_limit() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DAI, 100 * ONE, 199 * ONE),
+            Error::<Test>::AssetAmountExceededLimit
+        );
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DAI, 100 * ONE, 300 * ONE),
+            Error::<Test>::TokenPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_amount_is_too_low() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DAI, 999, 300 * ONE),
+            Error::<Test>::InsufficientTradingAmount
+        );
+    });
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DAI, 500 * ONE));
+
+        // Assert
+        assert_eq!(XYK::total_liquidity(HDX_DAI_POOL), 500 * ONE);
+        assert_eq!(Tokens::free_balance(FIRST_SHARE_TOKEN, &ALICE), 500 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 9_500 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 9_000 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 500 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_000 * ONE);
+
+        assert_eq!(
+            last_liquidity_change(),
+            Some((SOURCE, HDX, DAI, 500 * ONE, 1_000 * ONE, 500 * ONE, 1_000 * ONE))
+        );
+
+        expect_events(vec![Event::LiquidityRemoved {
+            who: ALICE,
+            asset_a: HDX,
+            asset_b: DAI,
+            amount_a: 500 * ONE,
+            amount_b: 1_000 * ONE,
+            shares: 500 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn remove_liquidity_should_destroy_pool_when_all_liquidity_is_removed() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::remove_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            HDX,
+            DAI,
+            1_000 * ONE
+        ));
+
+        // Assert
+        assert!(!XYK::exists(AssetPair::new(HDX, DAI)));
+        assert_eq!(XYK::pool_assets(HDX_DAI_POOL), None);
+        assert_eq!(XYK::total_liquidity(HDX_DAI_POOL), 0);
+        assert!(!is_whitelisted(HDX_DAI_POOL));
+        assert_eq!(Tokens::free_balance(HDX, &ALICE), 10_000 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), 10_000 * ONE);
+
+        expect_events(vec![
+            Event::LiquidityRemoved {
+                who: ALICE,
+                asset_a: HDX,
+                asset_b: DAI,
+                amount_a: 1_000 * ONE,
+                amount_b: 2_000 * ONE,
+                shares: 1_000 * ONE,
+            }
+            .into(),
+            Event::PoolDestroyed {
+                who: ALICE,
+                asset_a: HDX,
+                asset_b: DAI,
+                share_token: FIRST_SHARE_TOKEN,
+                pool: HDX_DAI_POOL,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_shares_are_insufficient() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::remove_liquidity(RuntimeOrigin::signed(BOB), HDX, DAI, ONE),
+            Error::<Test>::InsufficientShares
+        );
+    });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_remaining_liquidity_is_too_low() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DAI, 1_000 * ONE - 1),
+            Error::<Test>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_amount_is_zero() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DAI, 0),
+            Error::<Test>::InvalidLiquidityAmount
+        );
+    });
+}
@@ -0,0 +1,397 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as xyk;
+use crate::{AssetPair, Config};
+use frame_support::{
+    assert_ok, parameter_types,
+    traits::{Everything, GenesisBuild, Nothing},
+    weights::Weight,
+};
+use frame_system as system;
+use hydradx_traits::{
+    pools::DustRemovalAccountWhitelist, AccountIdFor, CanCreatePool, OnCreatePoolHandler, OnLiquidityChangedHandler,
+    OnTradeHandler, Registry, ShareTokenRegistry, Source, AMM,
+};
+use orml_tokens::AccountData;
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, Permill,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Amount = i128;
+pub type AssetId = u32;
+pub type Balance = u128;
+
+pub const HDX: AssetId = 0;
+pub const DAI: AssetId = 2;
+pub const DOT: AssetId = 3;
+/// Pools of this asset are not allowed
+pub const BSX: AssetId = 4;
+
+/// Share tokens are registered with ids starting from this one
+pub const FIRST_SHARE_TOKEN: AssetId = 1_000;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+
+/// Account of the HDX/DAI pool created by `AccountIdConstructor`
+pub const HDX_DAI_POOL: AccountId = 1_000_002;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+     Block = Block,
+     NodeBlock = Block,
+     UncheckedExtrinsic = UncheckedExtrinsic,
+     {
+         System: frame_system,
+         XYK: xyk,
+         Tokens: orml_tokens,
+     }
+);
+
+/// (source, asset_a, asset_b, amount_a, amount_b, liquidity_a, liquidity_b)
+pub type OracleCall = (Source, AssetId, AssetId, Balance, Balance, Balance, Balance);
+
+thread_local! {
+    pub static SHARE_TOKENS: RefCell<HashMap<Vec<u8>, AssetId>> = RefCell::new(HashMap::default());
+    pub static WHITELIST: RefCell<Vec<AccountId>> = RefCell::new(Vec::new());
+    pub static CREATED_POOLS: RefCell<Vec<(AssetId, AssetId)>> = RefCell::new(Vec::new());
+    pub static TRADES: RefCell<Vec<OracleCall>> = RefCell::new(Vec::new());
+    pub static LIQUIDITY_CHANGES: RefCell<Vec<OracleCall>> = RefCell::new(Vec::new());
+}
+
+parameter_types! {
+    pub TradeFee: Permill = Permill::from_rational(3u32, 1_000u32);
+    pub const MinTradingLimit: Balance = 1_000;
+    pub const MinPoolLiquidity: Balance = 1_000;
+    pub const MaxInRatio: u128 = 3;
+    pub const MaxOutRatio: u128 = 3;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type AssetId = AssetId;
+    type AssetRegistry = DummyRegistry;
+    type AssetPairAccountId = AccountIdConstructor;
+    type Currency = Tokens;
+    type TradeFee = TradeFee;
+    type MinTradingLimit = MinTradingLimit;
+    type MinPoolLiquidity = MinPoolLiquidity;
+    type MaxInRatio = MaxInRatio;
+    type MaxOutRatio = MaxOutRatio;
+    type CanCreatePool = DisallowBsxPools;
+    type AMMHandler = MockOracle;
+    type NonDustableWhitelistHandler = Whitelist;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 63;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_type_with_key! {
+    pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+        1
+    };
+}
+
+impl orml_tokens::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type Amount = Amount;
+    type CurrencyId = AssetId;
+    type WeightInfo = ();
+    type ExistentialDeposits = ExistentialDeposits;
+    type MaxLocks = ();
+    type DustRemovalWhitelist = Nothing;
+    type ReserveIdentifier = [u8; 8];
+    type MaxReserves = MaxReserves;
+    type CurrencyHooks = ();
+}
+
+pub struct DummyRegistry;
+
+impl Registry<AssetId, Vec<u8>, Balance, DispatchError> for DummyRegistry {
+    fn exists(asset_id: AssetId) -> bool {
+        asset_id < FIRST_SHARE_TOKEN || SHARE_TOKENS.with(|v| v.borrow().values().any(|id| *id == asset_id))
+    }
+
+    fn retrieve_asset(name: &Vec<u8>) -> Result<AssetId, DispatchError> {
+        SHARE_TOKENS
+            .with(|v| v.borrow().get(name).copied())
+            .ok_or(DispatchError::Other("Asset not registered"))
+    }
+
+    fn create_asset(name: &Vec<u8>, _existential_deposit: Balance) -> Result<AssetId, DispatchError> {
+        Ok(SHARE_TOKENS.with(|v| {
+            let asset_id = FIRST_SHARE_TOKEN + v.borrow().len() as AssetId;
+            v.borrow_mut().insert(name.clone(), asset_id);
+            asset_id
+        }))
+    }
+}
+
+impl ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError> for DummyRegistry {
+    fn retrieve_shared_asset(name: &Vec<u8>, _assets: &[AssetId]) -> Result<AssetId, DispatchError> {
+        Self::retrieve_asset(name)
+    }
+
+    fn create_shared_asset(
+        name: &Vec<u8>,
+        _assets: &[AssetId],
+        existential_deposit: Balance,
+    ) -> Result<AssetId, DispatchError> {
+        Self::create_asset(name, existential_deposit)
+    }
+}
+
+pub struct AccountIdConstructor;
+
+impl AccountIdFor<Vec<AssetId>> for AccountIdConstructor {
+    type AccountId = AccountId;
+
+    fn from_assets(assets: &Vec<AssetId>, _identifier: Option<&[u8]>) -> Self::AccountId {
+        1_000_000 + (assets[0] * 1_000 + assets[1]) as u64
+    }
+
+    fn name(assets: &Vec<AssetId>, identifier: Option<&[u8]>) -> Vec<u8> {
+        let mut buf: Vec<u8> = identifier.map(|ident| ident.to_vec()).unwrap_or_default();
+        buf.extend_from_slice(&(assets[0]).to_le_bytes());
+        buf.extend_from_slice(&(assets[1]).to_le_bytes());
+
+        buf
+    }
+}
+
+pub struct DisallowBsxPools;
+
+impl CanCreatePool<AssetId> for DisallowBsxPools {
+    fn can_create(asset_a: AssetId, asset_b: AssetId) -> bool {
+        asset_a != BSX && asset_b != BSX
+    }
+}
+
+pub struct Whitelist;
+
+impl DustRemovalAccountWhitelist<AccountId> for Whitelist {
+    type Error = DispatchError;
+
+    fn add_account(account: &AccountId) -> Result<(), Self::Error> {
+        WHITELIST.with(|v| v.borrow_mut().push(*account));
+        Ok(())
+    }
+
+    fn remove_account(account: &AccountId) -> Result<(), Self::Error> {
+        WHITELIST.with(|v| v.borrow_mut().retain(|a| a != account));
+        Ok(())
+    }
+}
+
+/// Records the calls of the pallet to its AMM handler.
+pub struct MockOracle;
+
+impl OnCreatePoolHandler<AssetId> for MockOracle {
+    fn on_create_pool(asset_a: AssetId, asset_b: AssetId) -> frame_support::dispatch::DispatchResult {
+        CREATED_POOLS.with(|v| v.borrow_mut().push((asset_a, asset_b)));
+        Ok(())
+    }
+
+    fn on_create_pool_weight() -> Weight {
+        Weight::from_ref_time(1_000)
+    }
+}
+
+impl OnTradeHandler<AssetId, Balance> for MockOracle {
+    fn on_trade(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+        amount_a: Balance,
+        amount_b: Balance,
+        liquidity_a: Balance,
+        liquidity_b: Balance,
+    ) -> Result<Weight, (Weight, DispatchError)> {
+        TRADES.with(|v| {
+            v.borrow_mut()
+                .push((source, asset_a, asset_b, amount_a, amount_b, liquidity_a, liquidity_b))
+        });
+        Ok(Weight::zero())
+    }
+
+    fn on_trade_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl OnLiquidityChangedHandler<AssetId, Balance> for MockOracle {
+    fn on_liquidity_changed(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+        amount_a: Balance,
+        amount_b: Balance,
+        liquidity_a: Balance,
+        liquidity_b: Balance,
+    ) -> Result<Weight, (Weight, DispatchError)> {
+        LIQUIDITY_CHANGES.with(|v| {
+            v.borrow_mut()
+                .push((source, asset_a, asset_b, amount_a, amount_b, liquidity_a, liquidity_b))
+        });
+        Ok(Weight::zero())
+    }
+
+    fn on_liquidity_changed_weight() -> Weight {
+        Weight::from_ref_time(2_000)
+    }
+}
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+    pools: Vec<(AccountId, AssetId, Balance, AssetId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        // If eg. tests running on one thread only, this thread local is shared.
+        // let's make sure that it is empty for each test case
+        SHARE_TOKENS.with(|v| v.borrow_mut().clear());
+        WHITELIST.with(|v| v.borrow_mut().clear());
+        CREATED_POOLS.with(|v| v.borrow_mut().clear());
+        TRADES.with(|v| v.borrow_mut().clear());
+        LIQUIDITY_CHANGES.with(|v| v.borrow_mut().clear());
+
+        Self {
+            endowed_accounts: vec![
+                (ALICE, HDX, 10_000 * ONE),
+                (ALICE, DAI, 10_000 * ONE),
+                (ALICE, DOT, 10_000 * ONE),
+                (ALICE, BSX, 10_000 * ONE),
+                (BOB, HDX, 10_000 * ONE),
+                (BOB, DAI, 10_000 * ONE),
+            ],
+            pools: vec![],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn with_pool(
+        mut self,
+        who: AccountId,
+        asset_a: AssetId,
+        amount_a: Balance,
+        asset_b: AssetId,
+        amount_b: Balance,
+    ) -> Self {
+        self.pools.push((who, asset_a, amount_a, asset_b, amount_b));
+        self
+    }
+
+    /// ALICE's pool of 1_000 HDX and 2_000 DAI
+    pub fn with_hdx_dai_pool(self) -> Self {
+        self.with_pool(ALICE, HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        orml_tokens::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut r: sp_io::TestExternalities = t.into();
+
+        r.execute_with(|| {
+            System::set_block_number(1);
+
+            for (who, asset_a, amount_a, asset_b, amount_b) in self.pools {
+                assert_ok!(XYK::create_pool(
+                    RuntimeOrigin::signed(who),
+                    asset_a,
+                    amount_a,
+                    asset_b,
+                    amount_b
+                ));
+            }
+            TRADES.with(|v| v.borrow_mut().clear());
+            LIQUIDITY_CHANGES.with(|v| v.borrow_mut().clear());
+        });
+
+        r
+    }
+}
+
+pub fn pool_account(asset_a: AssetId, asset_b: AssetId) -> AccountId {
+    XYK::get_pair_id(AssetPair::new(asset_a, asset_b))
+}
+
+pub fn last_trade() -> Option<OracleCall> {
+    TRADES.with(|v| v.borrow().last().copied())
+}
+
+pub fn last_liquidity_change() -> Option<OracleCall> {
+    LIQUIDITY_CHANGES.with(|v| v.borrow().last().copied())
+}
+
+pub fn is_whitelisted(account: AccountId) -> bool {
+    WHITELIST.with(|v| v.borrow().contains(&account))
+}
+
+pub fn expect_events(e: Vec<RuntimeEvent>) {
+    test_utils::expect_events::<RuntimeEvent, Test>(e);
+}
@@ -0,0 +1,113 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use frame_support::{assert_ok, traits::Get};
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn calculate_sell_should_return_amount_out_without_fee() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_eq!(
+            XYK::calculate_sell(PoolType::XYK, HDX, DAI, 250 * ONE),
+            Ok(398_800_000_000_000)
+        );
+    });
+}
+
+#[test]
+fn calculate_buy_should_return_amount_in_with_fee() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Arrange
+        let amount_in = hydra_dx_math::xyk::calculate_in_given_out(2_000 * ONE, 1_000 * ONE, 400 * ONE).unwrap();
+
+        // Act & Assert
+        assert_eq!(
+            XYK::calculate_buy(PoolType::XYK, HDX, DAI, 400 * ONE),
+            Ok(amount_in + TradeFee::get().mul_ceil(amount_in))
+        );
+    });
+}
+
+#[test]
+fn trade_execution_should_not_support_other_pools() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_eq!(
+            XYK::calculate_sell(PoolType::Omnipool, HDX, DAI, 250 * ONE),
+            Err(ExecutorError::NotSupported)
+        );
+        assert_eq!(
+            XYK::calculate_buy(PoolType::LBP, HDX, DAI, 400 * ONE),
+            Err(ExecutorError::NotSupported)
+        );
+        assert_eq!(
+            XYK::execute_sell(
+                RuntimeOrigin::signed(BOB),
+                PoolType::Stableswap(HDX),
+                HDX,
+                DAI,
+                250 * ONE,
+                0
+            ),
+            Err(ExecutorError::NotSupported)
+        );
+    });
+}
+
+#[test]
+fn execute_sell_should_trade_calculated_amounts() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Arrange
+        let amount_out = XYK::calculate_sell(PoolType::XYK, HDX, DAI, 250 * ONE).unwrap();
+
+        // Act
+        assert_ok!(XYK::execute_sell(
+            RuntimeOrigin::signed(BOB),
+            PoolType::XYK,
+            HDX,
+            DAI,
+            250 * ONE,
+            amount_out
+        ));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &BOB), 9_750 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), 10_000 * ONE + amount_out);
+    });
+}
+
+#[test]
+fn execute_buy_should_trade_calculated_amounts() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Arrange
+        let amount_in = XYK::calculate_buy(PoolType::XYK, HDX, DAI, 400 * ONE).unwrap();
+
+        // Act
+        assert_ok!(XYK::execute_buy(
+            RuntimeOrigin::signed(BOB),
+            PoolType::XYK,
+            HDX,
+            DAI,
+            400 * ONE,
+            amount_in
+        ));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &BOB), 10_000 * ONE - amount_in);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), 10_400 * ONE);
+    });
+}
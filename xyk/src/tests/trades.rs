@@ -0,0 +1,174 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::*;
+use crate::{Error, Event, SOURCE};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+/// 400 DAI for 250 HDX, reduced by 0.3% fee
+const SELL_AMOUNT_OUT: Balance = 398_800_000_000_000;
+const SELL_FEE: Balance = 1_200_000_000_000;
+
+#[test]
+fn sell_should_work() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 250 * ONE, 398 * ONE));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &BOB), 9_750 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), 10_000 * ONE + SELL_AMOUNT_OUT);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 1_250 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 2_000 * ONE - SELL_AMOUNT_OUT);
+
+        assert_eq!(
+            last_trade(),
+            Some((
+                SOURCE,
+                HDX,
+                DAI,
+                250 * ONE,
+                SELL_AMOUNT_OUT,
+                1_250 * ONE,
+                2_000 * ONE - SELL_AMOUNT_OUT
+            ))
+        );
+
+        expect_events(vec![Event::SellExecuted {
+            who: BOB,
+            asset_in: HDX,
+            asset_out: DAI,
+            amount_in: 250 * ONE,
+            amount_out: SELL_AMOUNT_OUT,
+            fee: SELL_FEE,
+            pool: HDX_DAI_POOL,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn sell_should_fail_when_min_bought_is_not_reached() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 250 * ONE, 399 * ONE),
+            Error::<Test>::BuyLimitNotReached
+        );
+    });
+}
+
+#[test]
+fn sell_should_fail_when_max_in_ratio_is_exceeded() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 400 * ONE, 0),
+            Error::<Test>::MaxInRatioExceeded
+        );
+    });
+}
+
+#[test]
+fn sell_should_fail_when_amount_is_too_low() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 999, 0),
+            Error::<Test>::InsufficientTradingAmount
+        );
+    });
+}
+
+#[test]
+fn sell_should_fail_when_balance_is_insufficient() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 10_001 * ONE, 0),
+            Error::<Test>::InsufficientAssetBalance
+        );
+    });
+}
+
+#[test]
+fn sell_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::sell(RuntimeOrigin::signed(ALICE), HDX, DOT, 10 * ONE, 0),
+            Error::<Test>::TokenPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn buy_should_work() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        // Arrange
+        let amount_in = hydra_dx_math::xyk::calculate_in_given_out(2_000 * ONE, 1_000 * ONE, 400 * ONE).unwrap();
+        let fee = TradeFee::get().mul_ceil(amount_in);
+
+        // Act
+        assert_ok!(XYK::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 400 * ONE, 260 * ONE));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(HDX, &BOB), 10_000 * ONE - amount_in - fee);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), 10_400 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &HDX_DAI_POOL), 1_000 * ONE + amount_in + fee);
+        assert_eq!(Tokens::free_balance(DAI, &HDX_DAI_POOL), 1_600 * ONE);
+
+        assert_eq!(
+            last_trade(),
+            Some((
+                SOURCE,
+                HDX,
+                DAI,
+                amount_in + fee,
+                400 * ONE,
+                1_000 * ONE + amount_in + fee,
+                1_600 * ONE
+            ))
+        );
+
+        expect_events(vec![Event::BuyExecuted {
+            who: BOB,
+            asset_in: HDX,
+            asset_out: DAI,
+            amount_in: amount_in + fee,
+            amount_out: 400 * ONE,
+            fee,
+            pool: HDX_DAI_POOL,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn buy_should_fail_when_max_sold_is_exceeded() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 400 * ONE, 250 * ONE),
+            Error::<Test>::SellLimitExceeded
+        );
+    });
+}
+
+#[test]
+fn buy_should_fail_when_max_out_ratio_is_exceeded() {
+    ExtBuilder::default().with_hdx_dai_pool().build().execute_with(|| {
+        assert_noop!(
+            XYK::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 700 * ONE, 10_000 * ONE),
+            Error::<Test>::MaxOutRatioExceeded
+        );
+    });
+}
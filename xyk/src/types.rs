@@ -0,0 +1,51 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+use sp_std::prelude::*;
+
+pub type Balance = u128;
+
+/// Pair of assets traded in a pool - `asset_in` is sold to the pool, `asset_out` is bought from it.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct AssetPair<AssetId> {
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+}
+
+impl<AssetId> AssetPair<AssetId>
+where
+    AssetId: Ord + Copy,
+{
+    pub fn new(asset_in: AssetId, asset_out: AssetId) -> Self {
+        Self { asset_in, asset_out }
+    }
+
+    /// Assets of the pair ordered by asset id, independently of the trade direction.
+    pub fn ordered_pair(&self) -> (AssetId, AssetId) {
+        if self.asset_in <= self.asset_out {
+            (self.asset_in, self.asset_out)
+        } else {
+            (self.asset_out, self.asset_in)
+        }
+    }
+
+    pub(crate) fn ordered_assets(&self) -> Vec<AssetId> {
+        let (asset_a, asset_b) = self.ordered_pair();
+        vec![asset_a, asset_b]
+    }
+}
@@ -0,0 +1,114 @@
+// This file is part of galacticcouncil/warehouse.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_xyk
+//!
+//! The benchmarks in `benchmarks.rs` have not been run yet, the figures below are estimates which have to be
+//! replaced by the generated weights. The `AMMHandler` weights are charged on top of them.
+
+// Command to generate the weights:
+// target/release/hydradx
+// benchmark
+// pallet
+// --chain=dev
+// --steps=5
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --template=.maintain/pallet-weight-template.hbs
+// --pallet=pallet-xyk
+// --output=xyk.rs
+// --extrinsic=*
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_xyk.
+pub trait WeightInfo {
+    fn create_pool() -> Weight;
+    fn add_liquidity() -> Weight;
+    fn remove_liquidity() -> Weight;
+    fn sell() -> Weight;
+    fn buy() -> Weight;
+}
+
+/// Weights for pallet_xyk using the hydraDX node and recommended hardware.
+pub struct HydraWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
+    fn create_pool() -> Weight {
+        Weight::from_ref_time(55_912_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(10 as u64))
+    }
+    fn add_liquidity() -> Weight {
+        Weight::from_ref_time(49_183_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(7 as u64))
+            .saturating_add(T::DbWeight::get().writes(5 as u64))
+    }
+    fn remove_liquidity() -> Weight {
+        Weight::from_ref_time(52_340_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(5 as u64))
+    }
+    fn sell() -> Weight {
+        Weight::from_ref_time(36_278_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(3 as u64))
+    }
+    fn buy() -> Weight {
+        Weight::from_ref_time(37_194_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(6 as u64))
+            .saturating_add(T::DbWeight::get().writes(3 as u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_pool() -> Weight {
+        Weight::from_ref_time(55_912_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(10 as u64))
+    }
+    fn add_liquidity() -> Weight {
+        Weight::from_ref_time(49_183_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(7 as u64))
+            .saturating_add(RocksDbWeight::get().writes(5 as u64))
+    }
+    fn remove_liquidity() -> Weight {
+        Weight::from_ref_time(52_340_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(5 as u64))
+    }
+    fn sell() -> Weight {
+        Weight::from_ref_time(36_278_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(3 as u64))
+    }
+    fn buy() -> Weight {
+        Weight::from_ref_time(37_194_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(6 as u64))
+            .saturating_add(RocksDbWeight::get().writes(3 as u64))
+    }
+}